@@ -24,20 +24,45 @@ let getPolkadotJsExtensionMod = (() => {
 })();
 
 /**
- *  Queries wallets installed from browser.
+ * The `getPolkadotUtilCryptoMod` closure returns the `@polkadot/util-crypto` module on demand, once its
+ * wasm crypto is ready, as `getPolkadotJsExtensionMod` does.
+ */
+let getPolkadotUtilCryptoMod = (() => {
+    let mod = null;
+
+    // initialize `@polkadot/util-crypto` module on page load
+    let initPromise = (async () => {
+        const utilCrypto = await import(
+            "https://cdn.jsdelivr.net/npm/@polkadot/util-crypto@13/+esm"
+            );
+        await utilCrypto.cryptoWaitReady();
+        mod = utilCrypto;
+    })();
+
+    // return a function that waits for initialization to be finished, in case mod is not initialized yet.
+    return async () => {
+        if (mod == null) {
+            await initPromise;
+        }
+        return mod;
+    };
+})();
+
+/**
+ *  Queries wallets installed from browser, i.e. every provider injected in `window.injectedWeb3`.
  *
- *  @returns a json string that contains all the wallets installed.
+ *  @returns a list of the wallets installed, with the name they are injected with and their version.
  */
 let getExtensions = () => {
-    return new Promise((resolve, reject) => {
+    return new Promise((resolve) => {
         setTimeout(() => {
-            const { injectedWeb3 } = window;
-            if (injectedWeb3 !== null) {
-                const extensionObjects = Object.keys(injectedWeb3).map((key) => key);
-                resolve(extensionObjects);
-            } else {
-                reject({error: "Something wrong happened, couldn't obtain wallets installed from browser!"});
-            }
+            // Note: no wallet is injected at all when none is installed
+            const injectedWeb3 = window.injectedWeb3 || {};
+            const extensionObjects = Object.entries(injectedWeb3).map(([name, injected]) => ({
+                name: name, // e.g. "polkadot-js", "nova"
+                version: injected?.version ?? null // e.g. "0.49.3"
+            }));
+            resolve(extensionObjects);
         }, 1000 );
     });
 };
@@ -71,6 +96,18 @@ async function getAccounts(source) {
     return JSON.stringify(accountObjects);
 }
 
+/**
+ *  Queries the signing methods a wallet exposes on its signer, once enabled by `getAccounts`.
+ *
+ *  @returns a json string that contains the methods found, e.g. ["signPayload", "signRaw"].
+ */
+async function getSigningMethods(source) {
+    const extensionMod = await getPolkadotJsExtensionMod();
+    const injector = await extensionMod.web3FromSource(source);
+    const methods = ["signPayload", "signRaw"]
+        .filter((method) => typeof injector?.signer?.[method] === "function");
+    return JSON.stringify(methods);
+}
 
 /**
  * Signs a payload via browser extension
@@ -117,3 +154,60 @@ async function signPayload(payloadAsStr, source, address) {
         throw "The extension's injector does not have a `signPayload` function on its `signer`";
     }
 }
+
+/**
+ * Signs the SCALE encoded signer payload as bytes via browser extension, for wallets without `signPayload`.
+ *
+ * The signature is verified against the payload before it is returned, since wallets of the polkadot-js
+ * family sign the data wrapped in `<Bytes>...</Bytes>`, which is not a valid transaction signature.
+ *
+ * @param data the signer payload as a hex string, i.e. the call followed by the transaction extensions
+ * @param source the extension used for signing as a string
+ * @param address the ss58 encoded address as a string
+ * @returns {Promise<*>} the signature as a hex string, raw or prefixed by its crypto type
+ */
+async function signRaw(data, source, address) {
+    const extensionMod = await getPolkadotJsExtensionMod();
+    const injector = await extensionMod.web3FromSource(source);
+    const signRaw = injector?.signer?.signRaw;
+    if (!!signRaw) {
+        const {signature} = await signRaw({address, data, type: "payload"});
+        const utilCryptoMod = await getPolkadotUtilCryptoMod();
+        const {isValid, isWrapped} = utilCryptoMod.signatureVerify(data, signature, address);
+        if (isWrapped) {
+            throw "The wallet signed the transaction wrapped in <Bytes>, so it can not be submitted. " +
+            "Please use a wallet that supports signPayload, or sign offline.";
+        } else if (!isValid) {
+            throw "The signature returned by the wallet does not match the transaction.";
+        }
+        return signature;
+    } else {
+        throw "The extension's injector does not have a `signRaw` function on its `signer`";
+    }
+}
+
+/**
+ * Provides the chain metadata to the browser extension, so it can decode the calls it signs.
+ * Extensions that already know the runtime version are not asked again.
+ *
+ * @param source the extension to provide the metadata to as a string
+ * @param metadataAsStr a string representing a `MetadataDef` JSON object, i.e. chain, genesisHash, icon,
+ * ss58Format, specVersion, tokenDecimals, tokenSymbol, types and rawMetadata
+ * @returns {Promise<boolean>} whether the metadata was provided
+ */
+async function provideMetadata(source, metadataAsStr) {
+    const definition = JSON.parse(metadataAsStr);
+    const extensionMod = await getPolkadotJsExtensionMod();
+    const injector = await extensionMod.web3FromSource(source);
+    const metadata = injector?.metadata;
+    if (!metadata) {
+        return false;
+    }
+    const known = await metadata.get();
+    const isKnown = known.some((def) =>
+        def.genesisHash === definition.genesisHash && def.specVersion === definition.specVersion);
+    if (isKnown) {
+        return false;
+    }
+    return await metadata.provide(definition);
+}
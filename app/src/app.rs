@@ -1,28 +1,35 @@
 use crate::components::{
     accounts::{AccountsCard, TotalBalancesCard},
     child_bounties::ChildBountiesCard,
-    modals::{AddAccountModal, ClaimModal},
+    modals::{AddAccountModal, ClaimModal, RpcEndpointsModal},
     nav::{Footer, Navbar},
+    payouts::PayoutsHistoryCard,
     steps::OnboardingSteps,
 };
 use crate::router::Query;
-use crate::state::{account_key, onboarded_key, signer_key, Action, State, StateContext};
+use crate::state::{
+    account_key, onboarded_key, payouts_history_key, rpc_endpoints_key, signer_key, Action, State,
+    StateContext,
+};
 use claimit_common::types::{
     accounts::Account,
     child_bounties::Filter,
     claims::ClaimStatus,
-    extensions::{ExtensionAccount, ExtensionState},
+    extensions::{provide_metadata, ExtensionAccount, ExtensionState, DEFAULT_MORTALITY_PERIOD},
+    fees::FeeAsset,
     layout::LayoutState,
-    network::{NetworkState, NetworkStatus},
-    worker::{Input as WorkerInput, Output as WorkerOutput},
+    network::{ConnectionEvent, NetworkState, NetworkStatus, RpcEndpoints},
+    payouts::PayoutsHistory,
+    worker::{Input as WorkerInput, Output as WorkerOutput, RequestId, RequestKind},
 };
 use claimit_workers::api::Worker;
 use gloo::storage::{LocalStorage, Storage};
-use std::str::FromStr;
+use log::{error, warn};
+use std::{cell::RefCell, rc::Rc, str::FromStr};
 use subxt::config::substrate::AccountId32;
 use yew::{
-    classes, function_component, html, prelude::use_reducer, use_callback, use_effect_with,
-    ContextProvider, Html,
+    classes, function_component, html, platform::spawn_local, prelude::use_reducer, use_callback,
+    use_effect_with, use_mut_ref, ContextProvider, Html,
 };
 use yew_agent::reactor::{
     use_reactor_bridge, ReactorEvent, ReactorProvider, UseReactorBridgeHandle,
@@ -37,7 +44,21 @@ pub fn main() -> Html {
         .map(|q| q.chain)
         .unwrap_or_default();
 
-    let use_light_client = location.query::<Query>().map(|q| q.lc).unwrap_or_default();
+    // Custom endpoints given in the query take precedence over the persisted ones and force RPC mode
+    let query_endpoints = location.query::<Query>().ok().and_then(|q| q.endpoints());
+
+    let use_light_client =
+        query_endpoints.is_none() && location.query::<Query>().map(|q| q.lc).unwrap_or_default();
+
+    let mortality_period = location
+        .query::<Query>()
+        .ok()
+        .and_then(|q| q.mortality)
+        .unwrap_or(DEFAULT_MORTALITY_PERIOD);
+
+    let endpoints: RpcEndpoints = query_endpoints.unwrap_or_else(|| {
+        LocalStorage::get(rpc_endpoints_key(current_runtime)).unwrap_or_default()
+    });
 
     let state = use_reducer(|| {
         let accounts: Vec<Account> =
@@ -60,10 +81,15 @@ pub fn main() -> Html {
         let signer: Option<ExtensionAccount> =
             LocalStorage::get(signer_key(current_runtime.clone())).unwrap_or_default();
 
+        let payouts_history: PayoutsHistory =
+            LocalStorage::get(payouts_history_key(current_runtime)).unwrap_or_default();
+
         State {
             accounts,
-            network: NetworkState::new(current_runtime.clone(), use_light_client),
+            network: NetworkState::new(current_runtime, use_light_client, endpoints),
             child_bounties_raw: None,
+            parent_bounties_raw: None,
+            payouts_history,
             filter,
             extension: ExtensionState::new(signer.clone()),
             claim: None,
@@ -71,6 +97,9 @@ pub fn main() -> Html {
         }
     });
 
+    // Generate request ids to correlate requests sent to the worker with its responses
+    let request_counter = use_mut_ref(|| 0 as RequestId);
+
     // Handle api calls over bridge (all async network calls are handled in a specific web worker)
     let worker_api_bridge: UseReactorBridgeHandle<Worker> = use_reactor_bridge({
         let state = state.clone();
@@ -84,22 +113,94 @@ pub fn main() -> Html {
                         state.dispatch(Action::UpdateBlockNumber(block_number));
                     }
                 }
-                WorkerOutput::ChildBounties(data) => {
+                WorkerOutput::ChildBounties(_request_id, data) => {
                     state.dispatch(Action::UpdateChildBountiesRaw(data));
                 }
-                WorkerOutput::AccountBalance(account, balance) => {
+                WorkerOutput::ChildBountyAdded(_request_id, child_bounty)
+                | WorkerOutput::ChildBountyUpdated(_request_id, child_bounty) => {
+                    state.dispatch(Action::UpdateChildBounty(child_bounty));
+                }
+                WorkerOutput::ChildBountyRemoved(_request_id, id) => {
+                    state.dispatch(Action::RemoveChildBounty(id));
+                }
+                WorkerOutput::ParentBounties(request_id, data) => {
+                    state.dispatch(Action::UpdateParentBountiesRaw(data));
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::AccountBalance(request_id, account, balance) => {
                     state.dispatch(Action::UpdateAccountBalance(account, balance));
+                    state.dispatch(Action::CompleteRequest(request_id));
                 }
-                WorkerOutput::AccountIdentity(account, identity) => {
+                WorkerOutput::AccountIdentity(request_id, account, identity) => {
                     state.dispatch(Action::UpdateAccountIdentity(account, identity));
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::Proxies(request_id, delegate, proxies) => {
+                    state.dispatch(Action::UpdateClaimProxies(delegate, proxies));
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::FeeAssetBalances(request_id, account, balances) => {
+                    state.dispatch(Action::UpdateClaimFeeAssetBalances(account, balances));
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::PayoutsHistory(_request_id, payouts, from, to) => {
+                    state.dispatch(Action::UpdatePayoutsHistory(payouts, from, to));
+                }
+                WorkerOutput::PayoutsHistoryLimit(_request_id, block_number, limit) => {
+                    state.dispatch(Action::UpdatePayoutsHistoryLimit(block_number, limit));
+                }
+                WorkerOutput::ClaimBatches(request_id, batches) => {
+                    state.dispatch(Action::UpdateClaimBatches(request_id, batches));
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::MultisigStatus(request_id, status) => {
+                    state.dispatch(Action::UpdateClaimMultisigStatus(request_id, status));
+                    state.dispatch(Action::CompleteRequest(request_id));
                 }
-                WorkerOutput::TxPayload(payload) => {
-                    state.dispatch(Action::GetSignature(payload));
+                WorkerOutput::TxPayload(request_id, payload, params, fee) => {
+                    state.dispatch(Action::GetSignature(request_id, payload, params, fee));
+                    state.dispatch(Action::CompleteRequest(request_id));
                 }
-                WorkerOutput::TxCompleted(child_bounties_ids) => {
-                    state.dispatch(Action::CompleteClaim(child_bounties_ids));
+                WorkerOutput::TxProgress(request_id, progress) => {
+                    state.dispatch(Action::UpdateClaimProgress(request_id, progress));
                 }
-                WorkerOutput::Err(_) => {
+                WorkerOutput::TxCompleted(request_id, outcomes) => {
+                    state.dispatch(Action::CompleteClaim(request_id, outcomes));
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::MetadataDef(request_id, metadata) => {
+                    if let Some(signer) = state.extension.signer.clone() {
+                        spawn_local(async move {
+                            if let Err(e) = provide_metadata(signer.source, &metadata).await {
+                                warn!("Metadata could not be provided to the wallet: {e:?}");
+                            }
+                        });
+                    }
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::Completed(request_id) => {
+                    state.dispatch(Action::CompleteRequest(request_id));
+                }
+                WorkerOutput::Failed(request_id, err) => {
+                    error!("request {request_id} failed: {err}");
+                    state.dispatch(Action::FailRequest(request_id, err));
+                }
+                WorkerOutput::Compatibility(sub_id, compatibility) => {
+                    if state.network.subscription_id == sub_id {
+                        state.dispatch(Action::UpdateCompatibility(compatibility));
+                    }
+                }
+                WorkerOutput::Connection(sub_id, event) => {
+                    if state.network.subscription_id == sub_id {
+                        if let ConnectionEvent::Disconnected(_) = event {
+                            state
+                                .dispatch(Action::ChangeNetworkStatus(NetworkStatus::Reconnecting));
+                        }
+                        state.dispatch(Action::UpdateConnection(event));
+                    }
+                }
+                WorkerOutput::Err(_sub_id, err) => {
+                    error!("{err}");
                     state.dispatch(Action::ChangeNetworkStatus(NetworkStatus::Inactive));
                 }
             },
@@ -111,21 +212,59 @@ pub fn main() -> Html {
     use_effect_with(state.network.status.clone(), {
         let state = state.clone();
         let worker_api_bridge = worker_api_bridge.clone();
+        let request_counter = request_counter.clone();
         move |status| match status {
             NetworkStatus::Initializing => {
                 worker_api_bridge.send(WorkerInput::Start(
                     state.network.subscription_id,
                     state.network.runtime,
                     state.network.use_light_client_as_network_provider,
+                    state.network.endpoints.clone(),
                 ));
             }
             NetworkStatus::Active => {
                 state.dispatch(Action::IncreaseFetch);
-                worker_api_bridge.send(WorkerInput::FetchChildBounties);
+                send_request(
+                    &state,
+                    &worker_api_bridge,
+                    &request_counter,
+                    WorkerInput::FetchChildBounties,
+                );
+                send_request(
+                    &state,
+                    &worker_api_bridge,
+                    &request_counter,
+                    WorkerInput::SubscribeChildBounties,
+                );
+                send_request(
+                    &state,
+                    &worker_api_bridge,
+                    &request_counter,
+                    WorkerInput::FetchParentBounties,
+                );
+                let following = state.following();
+                send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                    WorkerInput::SubscribePayoutsHistory(
+                        id,
+                        state.payouts_history.resume_from(&following),
+                    )
+                });
+                if state.payouts_history.has_unindexed(&following) {
+                    send_request(
+                        &state,
+                        &worker_api_bridge,
+                        &request_counter,
+                        WorkerInput::FetchPayoutsHistory,
+                    );
+                }
                 for account in &state.accounts {
                     let acc = AccountId32::from_str(&account.address).unwrap();
-                    worker_api_bridge.send(WorkerInput::FetchAccountBalance(acc.clone()));
-                    worker_api_bridge.send(WorkerInput::FetchAccountIdentity(acc));
+                    send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                        WorkerInput::FetchAccountBalance(id, acc.clone())
+                    });
+                    send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                        WorkerInput::FetchAccountIdentity(id, acc.clone())
+                    });
                 }
             }
             _ => (),
@@ -136,45 +275,199 @@ pub fn main() -> Html {
     use_effect_with(state.accounts.clone(), {
         let state = state.clone();
         let worker_api_bridge = worker_api_bridge.clone();
+        let request_counter = request_counter.clone();
         move |accounts| {
             if accounts.len() == 0 {
                 state.dispatch(Action::StartOnboarding);
             }
             for account in &state.accounts {
                 let acc = AccountId32::from_str(&account.address).unwrap();
-                worker_api_bridge.send(WorkerInput::FetchAccountBalance(acc.clone()));
-                worker_api_bridge.send(WorkerInput::FetchAccountIdentity(acc));
+                send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                    WorkerInput::FetchAccountBalance(id, acc.clone())
+                });
+                send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                    WorkerInput::FetchAccountIdentity(id, acc.clone())
+                });
+            }
+            // Accounts followed later are indexed back too, unless already being indexed
+            let is_indexing = state
+                .network
+                .requests
+                .values()
+                .any(|kind| *kind == RequestKind::PayoutsHistory);
+            if state.network.is_active()
+                && !is_indexing
+                && state.payouts_history.has_unindexed(&state.following())
+            {
+                send_request(
+                    &state,
+                    &worker_api_bridge,
+                    &request_counter,
+                    WorkerInput::FetchPayoutsHistory,
+                );
+            }
+        }
+    });
+
+    // Look up the accounts the signer can claim on behalf of, i.e. the beneficiaries of the child
+    // bounties being claimed and the accounts being followed, and the balances of the signer in
+    // the assets the fee can be paid with, while the claim is initializing
+    let proxies_lookup = state
+        .claim
+        .as_ref()
+        .filter(|claim| claim.is_initializing())
+        .map(|claim| claim.child_bounty_ids.clone())
+        .zip(
+            state
+                .extension
+                .signer
+                .as_ref()
+                .map(|signer| signer.address.clone()),
+        );
+    use_effect_with(proxies_lookup, {
+        let state = state.clone();
+        let worker_api_bridge = worker_api_bridge.clone();
+        let request_counter = request_counter.clone();
+        move |lookup| {
+            if let Some((child_bounty_ids, signer_address)) = lookup {
+                let Ok(delegate) = AccountId32::from_str(signer_address) else {
+                    return;
+                };
+
+                let beneficiaries = state
+                    .child_bounties_raw
+                    .iter()
+                    .flat_map(|child_bounties| child_bounties.values())
+                    .filter(|child_bounty| child_bounty_ids.contains(&child_bounty.key()))
+                    .filter_map(|child_bounty| child_bounty.beneficiary.clone());
+                let followed = state
+                    .accounts
+                    .iter()
+                    .filter_map(|account| AccountId32::from_str(&account.address).ok());
+
+                let mut reals: Vec<AccountId32> = beneficiaries
+                    .chain(followed)
+                    .filter(|account| account != &delegate)
+                    .collect();
+                reals.sort();
+                reals.dedup();
+
+                if !FeeAsset::supported(state.network.runtime).is_empty() {
+                    send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                        WorkerInput::FetchFeeAssetBalances(id, delegate.clone())
+                    });
+                }
+
+                if !reals.is_empty() {
+                    send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                        WorkerInput::FetchProxies(id, delegate, reals)
+                    });
+                }
+            }
+        }
+    });
+
+    // Provide the wallet the signer is from with the live metadata, so it can decode the claim
+    let metadata_provider = state
+        .extension
+        .signer
+        .as_ref()
+        .filter(|signer| state.extension.is_ready() && !signer.is_offline())
+        .filter(|_| state.network.status == NetworkStatus::Active)
+        .map(|signer| (signer.source.clone(), state.network.runtime));
+    use_effect_with(metadata_provider, {
+        let state = state.clone();
+        let worker_api_bridge = worker_api_bridge.clone();
+        let request_counter = request_counter.clone();
+        move |provider| {
+            if provider.is_some() {
+                send_request(
+                    &state,
+                    &worker_api_bridge,
+                    &request_counter,
+                    WorkerInput::FetchMetadataDef,
+                );
             }
         }
     });
 
-    //
-    use_effect_with(state.claim.clone(), {
+    // Note: only run on claim steps, since tracking the requests sent on behalf of the claim also changes it
+    let claim_step = state.claim.as_ref().map(|claim| {
+        (
+            claim.status.clone(),
+            claim.batch_index,
+            claim.is_split(),
+            claim.multisig_status.is_some(),
+        )
+    });
+    use_effect_with(claim_step, {
         let state = state.clone();
         let worker_api_bridge = worker_api_bridge.clone();
+        let request_counter = request_counter.clone();
         let extension = state.extension.clone();
-        move |claim| {
-            if let Some(claim) = claim {
+        move |_| {
+            if let Some(claim) = &state.claim {
                 match &claim.status {
                     ClaimStatus::Preparing => {
-                        if extension.is_ready() {
+                        if extension.is_ready() && !claim.is_split() {
+                            // Split the claim in batches first, the payload of each one is prepared in turn
+                            let claim = claim.clone();
+                            send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                                WorkerInput::SplitClaim(
+                                    id,
+                                    claim.child_bounty_ids.clone(),
+                                    claim.mode,
+                                )
+                            });
+                        } else if extension.is_ready() && claim.needs_multisig_status() {
+                            // Read the approvals of the batch first, the payload depends on them
+                            let claim = claim.clone();
+                            send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                                WorkerInput::FetchMultisigStatus(
+                                    id,
+                                    claim.current_batch(),
+                                    claim.mode,
+                                    claim.multisig.clone().unwrap(),
+                                )
+                            });
+                        } else if extension.is_ready() {
                             let signer = extension.signer.as_ref().unwrap().clone();
+                            let Ok(signer_account) = AccountId32::from_str(&signer.address) else {
+                                return;
+                            };
                             let claim = claim.clone();
-                            worker_api_bridge.send(WorkerInput::CreatePayloadTx(
-                                claim.child_bounty_ids.clone(),
-                                signer.address.clone(),
-                            ));
+                            send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                                WorkerInput::CreatePayloadTx(
+                                    id,
+                                    claim.current_batch(),
+                                    claim.mode,
+                                    claim.origin(&signer_account),
+                                    signer.address.clone(),
+                                    mortality_period,
+                                    claim.fee_asset,
+                                )
+                            });
                         }
                     }
                     ClaimStatus::Submitting(signature) => {
-                        if extension.is_ready() {
+                        if let (true, Some(params)) = (extension.is_ready(), &claim.params) {
                             let signer = extension.signer.as_ref().unwrap().clone();
+                            let Ok(signer_account) = AccountId32::from_str(&signer.address) else {
+                                return;
+                            };
                             let claim = claim.clone();
-                            worker_api_bridge.send(WorkerInput::SignAndSubmitTx(
-                                claim.child_bounty_ids.clone(),
-                                signer.address.clone(),
-                                signature.clone(),
-                            ));
+                            send_request(&state, &worker_api_bridge, &request_counter, |id| {
+                                WorkerInput::SignAndSubmitTx(
+                                    id,
+                                    claim.current_batch(),
+                                    claim.mode,
+                                    claim.origin(&signer_account),
+                                    signer.address.clone(),
+                                    signature.clone(),
+                                    params.clone(),
+                                    claim.fee_asset,
+                                )
+                            });
                         }
                     }
                     _ => {}
@@ -192,6 +485,7 @@ pub fn main() -> Html {
             state.dispatch(Action::ResetNetwork(
                 runtime,
                 state.network.use_light_client_as_network_provider,
+                LocalStorage::get(rpc_endpoints_key(runtime)).unwrap_or_default(),
             ));
         },
     );
@@ -205,6 +499,26 @@ pub fn main() -> Html {
             state.dispatch(Action::ResetNetwork(
                 state.network.runtime.clone(),
                 use_light_client,
+                state.network.endpoints.clone(),
+            ));
+        },
+    );
+
+    let onchange_endpoints = use_callback(
+        (state.clone(), worker_api_bridge.clone()),
+        |endpoints: RpcEndpoints, (state, worker_api_bridge)| {
+            worker_api_bridge.send(WorkerInput::Finish);
+            worker_api_bridge.reset();
+            if endpoints.is_empty() {
+                LocalStorage::delete(state.rpc_endpoints_key());
+            } else {
+                let _ = LocalStorage::set(state.rpc_endpoints_key(), endpoints.clone());
+            }
+            // Note: Reset network via RPC, since custom endpoints only apply to it
+            state.dispatch(Action::ResetNetwork(
+                state.network.runtime,
+                false,
+                endpoints,
             ));
         },
     );
@@ -249,9 +563,24 @@ pub fn main() -> Html {
                                     } else {
                                         html! {
                                             <>
+                                                {
+                                                    if let Some(description) = state.network.outdated_description() {
+                                                        html! {
+                                                            <div class="p-4 mb-4 max-w-[375px] sm:max-w-[828px] w-full rounded-lg bg-gray-50 text-sm text-red">
+                                                                <p>{description}</p>
+                                                                {
+                                                                    if state.network.is_claim_blocked() {
+                                                                        html! { <p class="mt-1">{"Claims are disabled until the app is updated."}</p> }
+                                                                    } else { html! {} }
+                                                                }
+                                                            </div>
+                                                        }
+                                                    } else { html! {} }
+                                                }
                                                 <TotalBalancesCard runtime={current_runtime.clone()} />
                                                 <AccountsCard runtime={current_runtime.clone()} />
                                                 <ChildBountiesCard />
+                                                <PayoutsHistoryCard />
                                             </>
                                         }
                                     }
@@ -271,11 +600,32 @@ pub fn main() -> Html {
 
                 <ClaimModal />
                 <AddAccountModal />
+                <RpcEndpointsModal onchange={&onchange_endpoints} />
             </div>
         </ContextProvider<StateContext>>
     }
 }
 
+/// Send a request to the worker with a new request id and keep track of it until it completes or fails
+fn send_request(
+    state: &StateContext,
+    worker_api_bridge: &UseReactorBridgeHandle<Worker>,
+    request_counter: &Rc<RefCell<RequestId>>,
+    request: impl FnOnce(RequestId) -> WorkerInput,
+) {
+    let request_id = {
+        let mut counter = request_counter.borrow_mut();
+        *counter = counter.wrapping_add(1);
+        *counter
+    };
+
+    let input = request(request_id);
+    if let Some((request_id, kind)) = input.request() {
+        state.dispatch(Action::StartRequest(request_id, kind));
+    }
+    worker_api_bridge.send(input);
+}
+
 #[function_component]
 pub fn App() -> Html {
     html! {
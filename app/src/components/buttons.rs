@@ -3,7 +3,9 @@ use crate::router::{Query, Routes};
 use crate::state::Action;
 use crate::state::StateContext;
 use claimit_common::runtimes::support::SupportedRelayRuntime;
-use claimit_common::types::{child_bounties::ChildBountiesIds, layout::BalanceMode};
+use claimit_common::types::{
+    child_bounties::ChildBountiesIds, claims::ClaimStatus, layout::BalanceMode,
+};
 use std::collections::BTreeSet;
 use std::str::FromStr;
 use subxt::config::substrate::AccountId32;
@@ -87,6 +89,9 @@ pub struct ExtensionButtonProps {
     #[prop_or_default]
     pub class: Option<AttrValue>,
     pub label: AttrValue,
+    /// version of the wallet, if installed
+    #[prop_or_default]
+    pub version: Option<String>,
     #[prop_or_default]
     pub disabled: bool,
     pub children: Children,
@@ -109,6 +114,8 @@ pub fn extension_button(props: &ExtensionButtonProps) -> Html {
                     {
                         if props.disabled {
                             html! { <span class="text-gray-600 text-xs font-light">{"Not installed"}</span> }
+                        } else if let Some(version) = &props.version {
+                            html! { <span class="text-gray-600 text-xs font-light">{format!("v{version}")}</span> }
                         } else {
                             html! {}
                         }
@@ -141,6 +148,11 @@ pub fn network_subscriber(props: &NetworkSubscriberProps) -> Html {
                     &Query {
                         chain,
                         bounties: BTreeSet::new(),
+                        // Custom endpoints belong to the previous chain
+                        rpc: None,
+                        people_rpc: None,
+                        asset_hub_rpc: None,
+                        explorer: None,
                         ..query
                     },
                 )
@@ -164,7 +176,7 @@ pub fn network_subscriber(props: &NetworkSubscriberProps) -> Html {
                         <span class="font-bold tracking-wide">{"Switch to Polkadot"}</span>
                     </NetworkButton>
                 },
-                SupportedRelayRuntime::Paseo => html! {
+                SupportedRelayRuntime::Paseo | SupportedRelayRuntime::Westend | SupportedRelayRuntime::Local => html! {
                     <NetworkButton chain={SupportedRelayRuntime::Polkadot} disabled={props.disabled.clone()} onclick={onclick.clone()} >
                         <img class="h-8" src="/images/polkadot_icon.svg" alt="polkadot logo" />
                         <span class="font-bold tracking-wide">{"Switch to Polkadot"}</span>
@@ -199,8 +211,7 @@ pub fn network_provider_icon_button(props: &NetworkProviderIconButtonProps) -> H
                     &Routes::Index,
                     &Query {
                         lc,
-                        bounties: query.bounties.clone(),
-                        ..query
+                        ..query.clone()
                     },
                 )
                 .unwrap();
@@ -209,7 +220,8 @@ pub fn network_provider_icon_button(props: &NetworkProviderIconButtonProps) -> H
     });
 
     html! {
-        <button class={classes!("btn", "btn__icon", "btn__gray", props.class.clone())} {onclick} disabled={props.disabled.clone()} title={state.network.provider_description()} >
+        <button class={classes!("btn", "btn__icon", "btn__gray", props.class.clone())} {onclick} disabled={props.disabled}
+            title={state.network.error_description().or(state.network.connection_description()).unwrap_or(state.network.provider_description())} >
            {
                 if state.network.is_ligh_client() {
                     html! {
@@ -229,6 +241,33 @@ pub fn network_provider_icon_button(props: &NetworkProviderIconButtonProps) -> H
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct RpcEndpointsIconButtonProps {
+    #[prop_or_default]
+    pub class: AttrValue,
+}
+
+#[function_component(RpcEndpointsIconButton)]
+pub fn rpc_endpoints_icon_button(props: &RpcEndpointsIconButtonProps) -> Html {
+    let state = use_context::<StateContext>().unwrap();
+
+    let onclick = {
+        let state = state.clone();
+        Callback::from(move |_| {
+            state.dispatch(Action::ToggleLayoutRpcEndpointsModal);
+        })
+    };
+
+    html! {
+        <button class={classes!("btn", "btn__icon", "btn__gray", props.class.clone())} {onclick}
+            title="Set custom RPC endpoints" >
+            <svg class="w-4 h-4 text-gray-900" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" width="24" height="24" fill="none" viewBox="0 0 24 24">
+                <path stroke="currentColor" stroke-linecap="round" stroke-width="2" d="M20 6H10m0 0a2 2 0 1 0-4 0m4 0a2 2 0 1 1-4 0m0 0H4m16 6h-2m0 0a2 2 0 1 0-4 0m4 0a2 2 0 1 1-4 0m0 0H4m16 6H10m0 0a2 2 0 1 0-4 0m4 0a2 2 0 1 1-4 0m0 0H4"/>
+            </svg>
+        </button>
+    }
+}
+
 #[function_component(ClaimButton)]
 pub fn claim_button() -> Html {
     let state = use_context::<StateContext>().unwrap();
@@ -243,7 +282,8 @@ pub fn claim_button() -> Html {
             let cbs = child_bounties_raw
                 .into_iter()
                 .filter(|(_, cb)| {
-                    cb.is_claimable(block_number) && accounts.contains(&cb.beneficiary)
+                    cb.is_claimable(block_number)
+                        && accounts.iter().any(|acc| cb.is_beneficiary(acc))
                 })
                 .map(|(_, cb)| (cb.parent_id.clone(), cb.id.clone()))
                 .collect::<ChildBountiesIds>();
@@ -270,7 +310,8 @@ pub fn claim_button() -> Html {
     };
 
     html! {
-        <button type="button" class={classes!("group", "btn__claim", state.network.runtime.class(), visibility)} {onclick} >
+        <button type="button" class={classes!("group", "btn__claim", state.network.runtime.class(), visibility)} {onclick}
+            disabled={state.network.is_claim_blocked()} title={state.network.outdated_description()} >
             <svg class="w-5 h-5 text-inherit dark:text-white me-2" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" width="24" height="24" fill="currentColor" viewBox="0 0 24 24">
                 <path fill-rule="evenodd" d="M20.337 3.664c.213.212.354.486.404.782.294 1.711.657 5.195-.906 6.76-1.77 1.768-8.485 5.517-10.611 6.683a.987.987 0 0 1-1.176-.173l-.882-.88-.877-.884a.988.988 0 0 1-.173-1.177c1.165-2.126 4.913-8.841 6.682-10.611 1.562-1.563 5.046-1.198 6.757-.904.296.05.57.191.782.404ZM5.407 7.576l4-.341-2.69 4.48-2.857-.334a.996.996 0 0 1-.565-1.694l2.112-2.111Zm11.357 7.02-.34 4-2.111 2.113a.996.996 0 0 1-1.69-.565l-.422-2.807 4.563-2.74Zm.84-6.21a1.99 1.99 0 1 1-3.98 0 1.99 1.99 0 0 1 3.98 0Z" clip-rule="evenodd"/>
             </svg>
@@ -297,15 +338,24 @@ pub fn sign_button() -> Html {
         };
 
         let label = if claim.is_signing_or_submitting() {
+            // Note: once submitted, the stage reached by the transaction is more telling
+            let status = match (&claim.status, &claim.progress) {
+                (ClaimStatus::Submitting(_), Some(progress)) => progress.stage.to_string(),
+                _ => claim.status.to_string(),
+            };
+            let status = match claim.batch_progress() {
+                Some(progress) => format!("{status} · {progress}"),
+                None => status,
+            };
             html! {
-                <span class="inline-flex items-center"><Spinner class="me-2" is_visible={true} />{claim.status.to_string()}</span>
+                <span class="inline-flex items-center"><Spinner class="me-2" is_visible={true} />{status}</span>
             }
         } else {
             html! { "Sign and Submit" }
         };
 
         html! {
-            <button type="button" class={classes!("btn", "btn__primary", state.network.runtime.class())} {onclick} disabled={!extension.is_ready() || claim.is_signing_or_submitting()} >{label}</button>
+            <button type="button" class={classes!("btn", "btn__primary", state.network.runtime.class())} {onclick} disabled={!extension.is_ready() || claim.is_signing_or_submitting() || claim.is_completed()} >{label}</button>
         }
     } else {
         html! {}
@@ -536,6 +586,9 @@ pub struct BountyIdToggleProps {
     pub id: u32,
     #[prop_or_default]
     pub selected: bool,
+    /// parent bounty description shown as tooltip
+    #[prop_or_default]
+    pub title: Option<AttrValue>,
     pub onclick: Callback<u32>,
 }
 
@@ -545,7 +598,7 @@ pub fn bounty_id(props: &BountyIdToggleProps) -> Html {
     let onclick = props.onclick.reform(move |_| id);
 
     html! {
-        <button class={classes!("btn", "btn__icon", "btn__white", props.selected.then(|| Some("selected")), props.class.clone())} {onclick}>
+        <button class={classes!("btn", "btn__icon", "btn__white", props.selected.then(|| Some("selected")), props.class.clone())} {onclick} title={props.title.clone()}>
             <div class="inline-flex items-center">
                 <span class="text-xs w-4 h-4">{props.id.clone()}</span>
             </div>
@@ -1,14 +1,14 @@
 use crate::components::{
     buttons::{BountyAllToggle, BountyIdToggle, ClaimButton},
     inputs::FilterInput,
-    items::{ChildBountyItem, FilterItem},
+    items::{ChildBountyItem, FilterItem, ParentBountyItem},
     spinners::Spinner,
 };
 use crate::router::{Query, Routes};
 use crate::state::{Action, StateContext};
 use claimit_common::runtimes::utils::amount_human;
-use claimit_common::types::child_bounties::Filter;
-use std::collections::BTreeSet;
+use claimit_common::types::child_bounties::{Filter, Id, ParentBountyId};
+use std::collections::{BTreeMap, BTreeSet};
 use strum::IntoEnumIterator;
 use yew::{
     function_component, html, use_context, use_effect_with, use_state, Callback, Html, Properties,
@@ -80,6 +80,14 @@ pub fn child_bounties_card() -> Html {
 
             </div>
         }
+    } else if let (None, Some(description)) =
+        (&state.child_bounties_raw, state.network.error_description())
+    {
+        html! {
+            <div class="flex flex-col justify-center items-center h-96 p-4 md:p-6 bg-gray-50 max-w-[375px] sm:max-w-[828px] rounded-lg w-full">
+                <p class="text-sm text-center text-red">{description}</p>
+            </div>
+        }
     } else if let Some(child_bounties_raw) = &state.child_bounties_raw {
         let mut all_bounties = child_bounties_raw
             .into_iter()
@@ -98,7 +106,8 @@ pub fn child_bounties_card() -> Html {
                             <div class="flex flex-nowrap gap-2 items-center mb-4 ms-1">
                                 <BountyAllToggle onclick={&ontoggle_all} selected={(*bounties_filter).len() == 0} />
                                 { for all_bounties.iter().cloned().map(|id| html! {
-                                    <BountyIdToggle id={id} onclick={&ontoggle} selected={(*bounties_filter).contains(&id)} />
+                                    <BountyIdToggle id={id} onclick={&ontoggle} selected={(*bounties_filter).contains(&id)}
+                                        title={state.parent_bounties_raw.as_ref().and_then(|pbs| pbs.get(&id)).map(|pb| pb.description.clone())} />
                                 })}
                             </div>
                         }
@@ -168,7 +177,9 @@ pub fn child_bounties_stats() -> Html {
         if let Some(block_number) = state.network.finalized_block_number {
             let amount_pending = child_bounties_raw
                 .into_iter()
-                .filter(|(_, cb)| state.filter.check(cb) && !cb.is_claimable(block_number))
+                .filter(|(_, cb)| {
+                    state.filter.check(cb) && cb.is_awarded() && !cb.is_claimable(block_number)
+                })
                 .map(|(_, cb)| cb.value)
                 .sum::<u128>();
 
@@ -244,6 +255,20 @@ pub fn child_bounties_body(props: &ChildBountiesBodyProps) -> Html {
             .filter(|(_, cb)| state.filter.check(cb))
             .count();
 
+        // Group child bounties by parent bounty so that each group can be shown with its parent metadata
+        let mut groups: BTreeMap<ParentBountyId, Vec<Id>> = BTreeMap::new();
+        for (id, cb) in child_bounties_raw.iter().filter(|(_, cb)| {
+            state.filter.check(cb)
+                && (cb
+                    .description
+                    .to_lowercase()
+                    .contains(&(*input_value).to_lowercase())
+                    && (props.bounties_filter.is_empty()
+                        || props.bounties_filter.contains(&cb.parent_id)))
+        }) {
+            groups.entry(cb.parent_id).or_default().push(*id);
+        }
+
         html! {
             <>
                 {
@@ -260,11 +285,13 @@ pub fn child_bounties_body(props: &ChildBountiesBodyProps) -> Html {
                         html! {
                             <ul class="flex-col w-full space-y space-y-4 text-sm font-medium text-gray-500 dark:text-gray-400">
                                 {
-                                    for child_bounties_raw.into_iter()
-                                        .filter(|(_, cb)| state.filter.check(cb) && (cb.description.to_lowercase().contains(&(*input_value).to_lowercase()) && (props.bounties_filter.len() == 0 || props.bounties_filter.contains(&cb.parent_id))))
-                                        .map(|(_, cb)|
-                                    html! {
-                                        <ChildBountyItem id={cb.id} is_action_hidden={!state.layout.is_onboarding} />
+                                    for groups.into_iter().map(|(parent_id, ids)| html! {
+                                        <>
+                                            <ParentBountyItem id={parent_id} />
+                                            { for ids.into_iter().map(|id| html! {
+                                                <ChildBountyItem id={id} is_action_hidden={!state.layout.is_onboarding} />
+                                            })}
+                                        </>
                                     })
                                 }
                             </ul>
@@ -1,3 +1,4 @@
+use claimit_common::types::{extensions::multi_signature_from_hex, multisigs::Multisig};
 use std::str::FromStr;
 use subxt::utils::AccountId32;
 use web_sys::{HtmlInputElement, MouseEvent};
@@ -163,3 +164,125 @@ pub fn filter_input(props: &FilterInputProps) -> Html {
         </div>
     }
 }
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct MultisigInputProps {
+    /// The signer, always one of the signatories of the multisig
+    pub signer: AccountId32,
+    #[prop_or_default]
+    pub disabled: bool,
+    pub onchange: Callback<Option<Multisig>>,
+}
+
+/// Other signatories and threshold of the multisig the signer approves the claim for
+#[function_component(MultisigInput)]
+pub fn multisig_input(props: &MultisigInputProps) -> Html {
+    let signatories_node_ref = use_node_ref();
+    let threshold_node_ref = use_node_ref();
+    let err = use_state(|| "".to_string());
+
+    let onclick = {
+        let signatories_node_ref = signatories_node_ref.clone();
+        let threshold_node_ref = threshold_node_ref.clone();
+        let signer = props.signer.clone();
+        let onchange = props.onchange.clone();
+        let err = err.clone();
+
+        move |_| {
+            let (Some(signatories), Some(threshold)) = (
+                signatories_node_ref.cast::<HtmlInputElement>(),
+                threshold_node_ref.cast::<HtmlInputElement>(),
+            ) else {
+                return;
+            };
+            // Validate if other signatories are valid SS58 accounts, separated by commas or spaces
+            let others = signatories
+                .value()
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .map(AccountId32::from_str)
+                .collect::<Result<Vec<AccountId32>, _>>();
+            let Ok(others) = others else {
+                err.set("Invalid SS58 Acccount".to_string());
+                return;
+            };
+            let Ok(threshold) = threshold.value().parse::<u16>() else {
+                err.set("Invalid threshold".to_string());
+                return;
+            };
+
+            let signatories = others.into_iter().chain([signer.clone()]).collect();
+            match Multisig::new(signatories, threshold) {
+                Ok(multisig) => {
+                    err.set("".to_string());
+                    onchange.emit(Some(multisig));
+                }
+                Err(e) => {
+                    err.set(e.to_string());
+                }
+            }
+        }
+    };
+
+    html! {
+        <div class="ms-2 mb-4">
+            <div class="flex items-center gap-2">
+                <input ref={signatories_node_ref} type="text" class="account__input" placeholder="Other signatories, separated by commas"
+                    disabled={props.disabled} />
+                <input ref={threshold_node_ref} type="number" min="2" value="2" class="account__input w-24" title="Threshold"
+                    disabled={props.disabled} />
+                <button type="button" class="btn btn__default" {onclick} disabled={props.disabled}>{"Use multisig"}</button>
+            </div>
+            <div class="ps-6 mt-1 text-xs text-red">{err.to_string()}</div>
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct SignatureInputProps {
+    /// The signer crypto, e.g. "sr25519", to encode a raw signature with
+    pub crypto_type: AttrValue,
+    #[prop_or_default]
+    pub disabled: bool,
+    pub onsubmit: Callback<Vec<u8>>,
+}
+
+/// Signature pasted back from an offline signer, submitted as a `MultiSignature`
+#[function_component(SignatureInput)]
+pub fn signature_input(props: &SignatureInputProps) -> Html {
+    let input_node_ref = use_node_ref();
+    let err = use_state(|| "".to_string());
+
+    let onclick = {
+        let input_node_ref = input_node_ref.clone();
+        let crypto_type = props.crypto_type.clone();
+        let onsubmit = props.onsubmit.clone();
+        let err = err.clone();
+
+        move |_| {
+            if let Some(input) = input_node_ref.cast::<HtmlInputElement>() {
+                match multi_signature_from_hex(&input.value(), &crypto_type) {
+                    Ok(signature) => {
+                        input.set_value("");
+                        err.set("".to_string());
+                        onsubmit.emit(signature);
+                    }
+                    Err(e) => {
+                        err.set(e.to_string());
+                    }
+                }
+            }
+        }
+    };
+
+    html! {
+        <div class="w-full">
+            <div class="flex items-center gap-2">
+                <input ref={input_node_ref} type="text" class="account__input" placeholder="Paste the signature (hex)"
+                    disabled={props.disabled} />
+                <button type="button" class="btn btn__default" {onclick} disabled={props.disabled}>{"Submit"}</button>
+            </div>
+            <div class="ps-6 mt-1 text-xs text-red">{err.to_string()}</div>
+        </div>
+    }
+}
@@ -3,19 +3,27 @@ use crate::components::{
     buttons::{PolkassemblyIconLink, SubsquareIconLink},
     chips::AccountChip,
     icons::Identicon,
+    inputs::SignatureInput,
 };
 use crate::state::{Action, StateContext};
 use claimit_common::runtimes::support::SupportedRelayRuntime;
+use claimit_common::runtimes::utils::{amount_human, compact};
 use claimit_common::types::{
     accounts::Account,
-    child_bounties::{Filter, Id},
+    batches::BatchMode,
+    child_bounties::{Filter, Id, ParentBountyId},
+    claims::{ClaimOutcome, ClaimResult, ClaimStatus},
     extensions::ExtensionAccount,
+    fees::FeeAsset,
+    payouts::Payout,
 };
+use gloo::timers::callback::Interval;
+use qrcode::{render::svg, QrCode};
 use std::str::FromStr;
 use subxt::config::substrate::AccountId32;
 use yew::{
-    classes, function_component, html, use_context, use_state, Callback, Classes, Html, MouseEvent,
-    Properties,
+    classes, function_component, html, use_context, use_effect_with, use_state, AttrValue,
+    Callback, Classes, Html, MouseEvent, Properties,
 };
 
 #[derive(PartialEq, Properties, Clone)]
@@ -167,6 +175,18 @@ pub fn account_item(props: &AccountItemProps) -> Html {
                                     <p class="ms-2 text-xl font-semibold">{props.runtime.unit()}</p>
                                 </div>
                             },
+                            SupportedRelayRuntime::Westend => html! {
+                                <div class="inline-flex items-center">
+                                    <img class="h-12" src="/images/westend_icon.svg" alt="westend logo" />
+                                    <p class="ms-2 text-xl font-semibold">{props.runtime.unit()}</p>
+                                </div>
+                            },
+                            SupportedRelayRuntime::Local => html! {
+                                <div class="inline-flex items-center">
+                                    <img class="h-12" src="/images/local_icon.svg" alt="local logo" />
+                                    <p class="ms-2 text-xl font-semibold">{props.runtime.unit()}</p>
+                                </div>
+                            },
                         }
                     }
                     <div class="relative">
@@ -401,7 +421,8 @@ pub fn filter_item(props: &FilterItemProps) -> Html {
                     let ids = child_bounties_raw
                         .into_iter()
                         .filter(|(_, cb)| {
-                            cb.is_claimable(block_number) && accounts.contains(&cb.beneficiary)
+                            cb.is_claimable(block_number)
+                                && accounts.iter().any(|acc| cb.is_beneficiary(acc))
                         })
                         .map(|(id, _)| id.clone())
                         .collect::<Vec<Id>>();
@@ -410,6 +431,7 @@ pub fn filter_item(props: &FilterItemProps) -> Html {
             }
             Filter::Claimable(Vec::new())
         }
+        Filter::Upcoming => filter.clone(),
     });
 
     html! {
@@ -421,6 +443,118 @@ pub fn filter_item(props: &FilterItemProps) -> Html {
     }
 }
 
+#[derive(PartialEq, Properties)]
+pub struct BatchModeItemProps {
+    pub mode: BatchMode,
+    pub selected: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    pub onclick: Callback<BatchMode>,
+}
+
+#[function_component(BatchModeItem)]
+pub fn batch_mode_item(props: &BatchModeItemProps) -> Html {
+    let mode = props.mode;
+
+    let mut class = Classes::from("inline-block px-4 py-2 rounded-full min-w-24");
+
+    if props.selected {
+        class.push("text-white bg-gray-500 active");
+    } else {
+        class.push(
+            "text-gray-600 dark:text-gray-400 hover:text-gray-900 hover:bg-gray-100 dark:hover:bg-gray-800 dark:hover:text-white",
+        );
+    }
+
+    let onclick = props.onclick.reform(move |_| mode);
+
+    html! {
+        <li class="inline-flex ms-2" >
+            <button type="button" {class} {onclick} disabled={props.disabled} title={mode.description()}>
+                { mode.to_string() }
+            </button>
+        </li>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ClaimProxyItemProps {
+    /// The account the claim is dispatched from, or `None` for the signer itself
+    pub real: Option<AccountId32>,
+    pub label: AttrValue,
+    #[prop_or_default]
+    pub title: AttrValue,
+    pub selected: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    pub onclick: Callback<Option<AccountId32>>,
+}
+
+#[function_component(ClaimProxyItem)]
+pub fn claim_proxy_item(props: &ClaimProxyItemProps) -> Html {
+    let mut class = Classes::from("inline-block px-4 py-2 rounded-full min-w-24");
+
+    if props.selected {
+        class.push("text-white bg-gray-500 active");
+    } else {
+        class.push(
+            "text-gray-600 dark:text-gray-400 hover:text-gray-900 hover:bg-gray-100 dark:hover:bg-gray-800 dark:hover:text-white",
+        );
+    }
+
+    let onclick = {
+        let real = props.real.clone();
+        props.onclick.reform(move |_| real.clone())
+    };
+
+    html! {
+        <li class="inline-flex ms-2" >
+            <button type="button" {class} {onclick} disabled={props.disabled} title={props.title.clone()}>
+                { props.label.clone() }
+            </button>
+        </li>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ClaimFeeAssetItemProps {
+    /// The asset the fee is paid with, or `None` for the native token
+    pub asset: Option<FeeAsset>,
+    pub label: AttrValue,
+    #[prop_or_default]
+    pub title: AttrValue,
+    pub selected: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    pub onclick: Callback<Option<FeeAsset>>,
+}
+
+#[function_component(ClaimFeeAssetItem)]
+pub fn claim_fee_asset_item(props: &ClaimFeeAssetItemProps) -> Html {
+    let mut class = Classes::from("inline-block px-4 py-2 rounded-full min-w-24");
+
+    if props.selected {
+        class.push("text-white bg-gray-500 active");
+    } else {
+        class.push(
+            "text-gray-600 dark:text-gray-400 hover:text-gray-900 hover:bg-gray-100 dark:hover:bg-gray-800 dark:hover:text-white",
+        );
+    }
+
+    let onclick = {
+        let asset = props.asset;
+        props.onclick.reform(move |_| asset)
+    };
+
+    html! {
+        <li class="inline-flex ms-2" >
+            <button type="button" {class} {onclick} disabled={props.disabled} title={props.title.clone()}>
+                { props.label.clone() }
+            </button>
+        </li>
+    }
+}
+
 #[derive(PartialEq, Properties, Clone)]
 pub struct ChildBountyItemProps {
     pub id: Id,
@@ -435,14 +569,22 @@ pub fn child_bounty_item(props: &ChildBountyItemProps) -> Html {
     if let Some(child_bounties) = &state.child_bounties_raw {
         if let Some(child_bounty) = child_bounties.get(&props.id) {
             if let Some(block_number) = state.network.finalized_block_number {
-                let is_already_following = state
-                    .accounts
-                    .iter()
-                    .any(|account| *account.address == child_bounty.beneficiary.to_string());
+                // Only awarded child bounties have a beneficiary that can be followed
+                let is_already_following = match &child_bounty.beneficiary {
+                    Some(beneficiary) => state
+                        .accounts
+                        .iter()
+                        .any(|account| *account.address == beneficiary.to_string()),
+                    None => true,
+                };
 
                 let onclick = {
                     let state = state.clone();
-                    let account = child_bounty.beneficiary.to_string();
+                    let account = child_bounty
+                        .beneficiary
+                        .as_ref()
+                        .map(|beneficiary| beneficiary.to_string())
+                        .unwrap_or_default();
                     Callback::from(move |_| {
                         state.dispatch(Action::AddAccount(account.clone()));
                     })
@@ -472,7 +614,25 @@ pub fn child_bounty_item(props: &ChildBountyItemProps) -> Html {
                             <hr class="my-2" />
                             <div class="flex items-center justify-between">
 
-                                <AccountChip account={child_bounty.beneficiary.clone()} identity={child_bounty.beneficiary_identity.clone()} />
+                                {
+                                    if let Some(beneficiary) = &child_bounty.beneficiary {
+                                        html! {
+                                            <AccountChip account={beneficiary.clone()} identity={child_bounty.beneficiary_identity.clone()} />
+                                        }
+                                    } else if let Some(curator) = &child_bounty.curator {
+                                        html! {
+                                            <div class="inline-flex items-center gap-2">
+                                                <span class="text-xs">{"Curator"}</span>
+                                                <AccountChip account={curator.clone()} />
+                                                <span class="text-xs">{format!("Fee {} {}", child_bounty.fee_human(state.network.runtime), state.network.runtime.unit())}</span>
+                                            </div>
+                                        }
+                                    } else {
+                                        html! {
+                                            <span class="text-xs">{"Awaiting curator"}</span>
+                                        }
+                                    }
+                                }
 
                                 { if child_bounty.is_claimable(block_number) {
                                     html! {
@@ -480,12 +640,18 @@ pub fn child_bounty_item(props: &ChildBountyItemProps) -> Html {
                                             {"Claimable"}
                                         </span>
                                     }
-                                } else {
+                                } else if child_bounty.is_awarded() {
                                     html! {
                                         <span class="text-xs">
                                             {format!("Claim in {}", child_bounty.unlock_duration(block_number))}
                                         </span>
                                     }
+                                } else {
+                                    html! {
+                                        <span class="chip chip__gray">
+                                            {child_bounty.status.clone()}
+                                        </span>
+                                    }
                                 }}
 
                             </div>
@@ -521,9 +687,69 @@ pub fn child_bounty_item(props: &ChildBountyItemProps) -> Html {
     html! {}
 }
 
+#[derive(PartialEq, Properties, Clone)]
+pub struct ParentBountyItemProps {
+    pub id: ParentBountyId,
+}
+
+#[function_component(ParentBountyItem)]
+pub fn parent_bounty_item(props: &ParentBountyItemProps) -> Html {
+    let state = use_context::<StateContext>().unwrap();
+    let runtime = state.network.runtime;
+
+    if let Some(parent_bounties) = &state.parent_bounties_raw {
+        if let Some(parent_bounty) = parent_bounties.get(&props.id) {
+            return html! {
+                <li class="flex flex-col px-2 pt-2">
+                    <div class="flex items-center justify-between">
+                        <div class="flex flex-col flex-1 w-1">
+                            <h4 class="flex-auto text-sm font-bold text-gray-800 dark:text-gray-200 block truncate">
+                                {parent_bounty.description.clone()}
+                            </h4>
+                            <p class="text-xs">{format!("# {}", parent_bounty.id)}</p>
+                        </div>
+                        <div class="flex flex-col items-end ms-2">
+                            <div class="inline-flex items-center text-sm text-gray-800">
+                                <span>{parent_bounty.remaining_value_human(runtime)}</span>
+                                <span class="ms-1 font-light dark:text-gray-400">{format!("/ {} {}", parent_bounty.value_human(runtime), runtime.unit())}</span>
+                            </div>
+                            <p class="text-xs">{"Remaining / Total"}</p>
+                        </div>
+                    </div>
+                    <div class="flex items-center justify-between mt-2">
+                        {
+                            if let Some(curator) = &parent_bounty.curator {
+                                html! {
+                                    <div class="inline-flex items-center gap-2">
+                                        <span class="text-xs">{"Curator"}</span>
+                                        <AccountChip account={curator.clone()} />
+                                    </div>
+                                }
+                            } else {
+                                html! { <span class="text-xs">{"Awaiting curator"}</span> }
+                            }
+                        }
+                        <span class="chip chip__gray">{parent_bounty.status.clone()}</span>
+                    </div>
+                </li>
+            };
+        }
+    }
+
+    // Fallback to the bounty id while parent bounties metadata is not available
+    html! {
+        <li class="flex px-2 pt-2">
+            <p class="text-xs">{format!("# {}", props.id)}</p>
+        </li>
+    }
+}
+
 #[derive(PartialEq, Properties, Clone)]
 pub struct ChildBountyItemSmallProps {
     pub id: Id,
+    /// The outcome of the claim, once the batch the child bounty belongs to is submitted
+    #[prop_or_default]
+    pub outcome: Option<ClaimOutcome>,
 }
 
 #[function_component(ChildBountyItemSmall)]
@@ -547,6 +773,7 @@ pub fn child_bounty_item_small(props: &ChildBountyItemSmallProps) -> Html {
                             </div>
                         </div>
                         <p class="text-xs">{format!("# {} / {}", child_bounty.parent_id, child_bounty.id)}</p>
+                        <ClaimOutcomeNote outcome={props.outcome.clone()} />
                         // <hr class="my-2" />
                         // <div class="flex items-center justify-between">
 
@@ -558,5 +785,414 @@ pub fn child_bounty_item_small(props: &ChildBountyItemSmallProps) -> Html {
             };
         }
     }
+
+    // Note: child bounties claimed are removed from the state, so only their outcome is left to show
+    if let Some(outcome) = &props.outcome {
+        return html! {
+            <li class="flex rounded-md bg-gray-50 dark:bg-gray-700">
+                <div class="flex-auto p-6 ">
+                    <p class="text-xs">{format!("# {} / {}", outcome.parent_id, outcome.child_id)}</p>
+                    <ClaimOutcomeNote outcome={props.outcome.clone()} />
+                </div>
+            </li>
+        };
+    }
     html! {}
 }
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct ClaimOutcomeNoteProps {
+    pub outcome: Option<ClaimOutcome>,
+}
+
+/// A tick for a child bounty claimed, a note if only approved by a multisig signatory, or the reason the claim failed
+#[function_component(ClaimOutcomeNote)]
+pub fn claim_outcome_note(props: &ClaimOutcomeNoteProps) -> Html {
+    match props.outcome.as_ref().map(|outcome| &outcome.result) {
+        Some(ClaimResult::Claimed) => html! {
+            <div class="inline-flex items-center mt-2 text-xs text-green-dark dark:text-green">
+                <svg class="w-4 h-4 me-1" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" width="24" height="24" fill="none" viewBox="0 0 24 24">
+                    <path stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 11.917 9.724 16.5 19 7.5"/>
+                </svg>
+                {"Claimed"}
+            </div>
+        },
+        Some(ClaimResult::Approved) => html! {
+            <p class="mt-2 text-xs text-gray-600 dark:text-gray-400">{"Approved, waiting for the other signatories"}</p>
+        },
+        Some(ClaimResult::Failed(reason)) => html! {
+            <p class="mt-2 text-xs text-red">{format!("Not claimed: {reason}")}</p>
+        },
+        None => html! {},
+    }
+}
+
+/// Total value of the child bounties being claimed, the fee estimated for the claim and the net amount.
+/// A fee paid in an asset is shown in that asset, and not deducted from the amount claimed.
+#[function_component(ClaimFeeSummary)]
+pub fn claim_fee_summary() -> Html {
+    let state = use_context::<StateContext>().unwrap();
+    let runtime = state.network.runtime;
+    let decimals = runtime.decimals().into();
+
+    let (Some(claim), Some(child_bounties)) = (&state.claim, &state.child_bounties_raw) else {
+        return html! {};
+    };
+
+    let total = claim
+        .child_bounty_ids
+        .iter()
+        .filter_map(|(_, id)| child_bounties.get(id))
+        .map(|cb| cb.value)
+        .sum::<u128>();
+
+    let (fee_decimals, fee_unit) = match claim.fee.as_ref().and_then(|fee| fee.asset) {
+        Some(asset) => (asset.decimals(), asset.symbol()),
+        None => (decimals, runtime.unit()),
+    };
+
+    let (fee, net) = match &claim.fee {
+        Some(fee) if fee.asset.is_some() => (
+            amount_human(fee.total(), fee_decimals),
+            amount_human(total, decimals),
+        ),
+        Some(fee) => (
+            amount_human(fee.total(), decimals),
+            amount_human(total.saturating_sub(fee.total()), decimals),
+        ),
+        None if claim.status == ClaimStatus::Preparing => {
+            ("Estimating...".to_string(), "-".to_string())
+        }
+        None => ("-".to_string(), "-".to_string()),
+    };
+
+    let rows = [
+        (
+            "Total claimed value",
+            amount_human(total, decimals),
+            runtime.unit(),
+        ),
+        ("Estimated fee", fee, fee_unit),
+        ("Net amount", net, runtime.unit()),
+    ];
+
+    html! {
+        <div class="rounded-md bg-gray-50 dark:bg-gray-700 px-6 py-4 space-y-1">
+            { for rows.into_iter().map(|(label, value, unit)| html! {
+                <div class="flex items-center justify-between text-sm text-gray-800 dark:text-gray-200">
+                    <span>{label}</span>
+                    <div class="inline-flex items-center">
+                        <span>{value}</span>
+                        <span class="ml-1 text-gray-600 dark:text-gray-400">{unit}</span>
+                    </div>
+                </div>
+            })}
+            {
+                if let Some(progress) = claim.batch_progress() {
+                    html! {
+                        <p class="text-xs text-gray-600 dark:text-gray-400">
+                            {format!("{progress}, the fee is estimated for the batch being signed.")}
+                        </p>
+                    }
+                } else { html! {} }
+            }
+            {
+                match &claim.fee {
+                    Some(fee) if !fee.is_covered() => html! {
+                        <p class="text-xs text-red">
+                            {format!("The free balance of the signer ({} {}) does not cover the fee.",
+                                amount_human(fee.free_balance, fee_decimals), fee_unit)}
+                        </p>
+                    },
+                    _ => html! {},
+                }
+            }
+        </div>
+    }
+}
+
+/// The multisig the claim is dispatched from and the threshold progress of the batch being signed
+#[function_component(MultisigSummary)]
+pub fn multisig_summary() -> Html {
+    let state = use_context::<StateContext>().unwrap();
+
+    let Some((multisig, claim)) = state
+        .claim
+        .as_ref()
+        .and_then(|claim| claim.multisig.as_ref().map(|multisig| (multisig, claim)))
+    else {
+        return html! {};
+    };
+
+    let account = multisig.account();
+
+    html! {
+        <div class="rounded-md bg-gray-50 dark:bg-gray-700 px-6 py-4 space-y-1 text-sm text-gray-800 dark:text-gray-200">
+            <div class="flex items-center justify-between" title={account.to_string()}>
+                <span>{"Multisig"}</span>
+                <span>{compact(&account)}</span>
+            </div>
+            {
+                if let Some(status) = &claim.multisig_status {
+                    let approvers = status
+                        .pending
+                        .as_ref()
+                        .map(|pending| pending.approvals.iter().map(compact).collect::<Vec<String>>().join(", "))
+                        .unwrap_or_default();
+                    html! {
+                        <>
+                            <div class="flex items-center justify-between">
+                                <span>{"Approvals"}</span>
+                                <span class="chip chip__gray">{format!("{} of {}", status.approvals(), multisig.threshold)}</span>
+                            </div>
+                            <div class="flex items-center justify-between text-xs" title={status.call_hash_hex()}>
+                                <span>{"Call hash"}</span>
+                                <span>{format!("{}...", &status.call_hash_hex()[..18])}</span>
+                            </div>
+                            {
+                                if approvers.is_empty() {
+                                    html! { <p class="text-xs text-gray-600 dark:text-gray-400">{"Not approved yet, the signer submits the first approval."}</p> }
+                                } else {
+                                    html! { <p class="text-xs text-gray-600 dark:text-gray-400">{format!("Approved by {approvers}.")}</p> }
+                                }
+                            }
+                        </>
+                    }
+                } else {
+                    html! {
+                        <div class="flex items-center justify-between">
+                            <span>{"Threshold"}</span>
+                            <span class="chip chip__gray">{format!("{} of {}", multisig.threshold, multisig.signatories.len())}</span>
+                        </div>
+                    }
+                }
+            }
+            {
+                if claim.has_approvals() {
+                    html! {
+                        <p class="text-xs text-gray-600 dark:text-gray-400">
+                            {"Approved. Each of the other signatories approves the same call hash from their own wallet to claim."}
+                        </p>
+                    }
+                } else { html! {} }
+            }
+        </div>
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum OfflineFormat {
+    Hex,
+    Json,
+    Qr,
+}
+
+/// The payload of the batch being signed offline, exported as hex, JSON or a QR code to be scanned
+/// by Polkadot Vault, and the input to paste the signature back
+#[function_component(OfflineSigningSummary)]
+pub fn offline_signing_summary() -> Html {
+    let state = use_context::<StateContext>().unwrap();
+    let format = use_state(|| OfflineFormat::Qr);
+    let frame = use_state(|| 0usize);
+
+    let signer = state
+        .extension
+        .signer
+        .clone()
+        .filter(|signer| signer.is_offline());
+    let payload = match state.claim.as_ref().map(|claim| &claim.status) {
+        Some(ClaimStatus::Signing(payload)) => Some(payload.clone()),
+        _ => None,
+    };
+
+    let frames = match (&signer, &payload) {
+        (Some(signer), Some(payload)) => AccountId32::from_str(&signer.address)
+            .map(|account| payload.uos_frames(&account, &signer.r#type))
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    // Show the frames of a large payload in turn, as an animated QR code
+    use_effect_with(frames.len(), {
+        let frame = frame.clone();
+        move |frames| {
+            frame.set(0);
+            let frames = *frames;
+            let interval = (frames > 1).then(|| {
+                let mut index = 0;
+                Interval::new(500, move || {
+                    index = (index + 1) % frames;
+                    frame.set(index);
+                })
+            });
+            move || drop(interval)
+        }
+    });
+
+    let (Some(signer), Some(payload)) = (signer, payload) else {
+        return html! {};
+    };
+
+    let onsubmit = {
+        let state = state.clone();
+        Callback::from(move |signature: Vec<u8>| {
+            state.dispatch(Action::SubmitWithSignature(signature));
+        })
+    };
+
+    let tab = |label: &str, value: OfflineFormat| {
+        let class = if *format == value {
+            "inline-block px-4 py-2 rounded-full text-white bg-gray-500 active"
+        } else {
+            "inline-block px-4 py-2 rounded-full text-gray-600 dark:text-gray-400 hover:text-gray-900 hover:bg-gray-100"
+        };
+        let onclick = {
+            let format = format.clone();
+            Callback::from(move |_| format.set(value))
+        };
+        html! {
+            <li class="inline-flex ms-2">
+                <button type="button" {class} {onclick}>{label.to_string()}</button>
+            </li>
+        }
+    };
+
+    let field = |label: &str, value: String| {
+        html! {
+            <div class="space-y-1">
+                <p class="text-xs text-gray-600 dark:text-gray-400">{label.to_string()}</p>
+                <textarea class="w-full h-20 p-2 text-xs font-mono rounded-md bg-white dark:bg-gray-800" readonly={true} {value} />
+            </div>
+        }
+    };
+
+    let qr = frames
+        .get(*frame)
+        .and_then(|data| QrCode::new(data).ok())
+        .map(|code| {
+            code.render::<svg::Color>()
+                .min_dimensions(240, 240)
+                .dark_color(svg::Color("#000000"))
+                .light_color(svg::Color("#ffffff"))
+                .build()
+        });
+
+    html! {
+        <div class="rounded-md bg-gray-50 dark:bg-gray-700 px-6 py-4 space-y-2 text-sm text-gray-800 dark:text-gray-200">
+            <p>{"Sign the payload offline, then paste the signature back to submit the claim."}</p>
+            <ul class="tab">
+                { tab("QR code", OfflineFormat::Qr) }
+                { tab("Hex", OfflineFormat::Hex) }
+                { tab("JSON", OfflineFormat::Json) }
+            </ul>
+            {
+                match *format {
+                    OfflineFormat::Qr => html! {
+                        <div class="flex flex-col items-center">
+                            {
+                                match qr {
+                                    Some(svg) => Html::from_html_unchecked(AttrValue::from(svg)),
+                                    None => html! { <p class="text-xs text-red">{"The payload could not be encoded as a QR code."}</p> },
+                                }
+                            }
+                            {
+                                if frames.len() > 1 {
+                                    html! { <p class="text-xs text-gray-600 dark:text-gray-400">{format!("Frame {} of {}", *frame + 1, frames.len())}</p> }
+                                } else { html! {} }
+                            }
+                        </div>
+                    },
+                    OfflineFormat::Hex => html! {
+                        <>
+                            { field("Call data", payload.call_data_hex()) }
+                            { field("Signing payload", payload.signer_payload_hex()) }
+                        </>
+                    },
+                    OfflineFormat::Json => field("Signer payload (JSON)", payload.json.clone()),
+                }
+            }
+            <SignatureInput crypto_type={signer.r#type.clone()} {onsubmit} />
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct TxProgressSummaryProps {
+    /// Block explorer to link the extrinsic and its block to, if any
+    #[prop_or_default]
+    pub explorer: Option<AttrValue>,
+}
+
+/// Stage reached by the claim transaction submitted, with links to the block explorer
+#[function_component(TxProgressSummary)]
+pub fn tx_progress_summary(props: &TxProgressSummaryProps) -> Html {
+    let state = use_context::<StateContext>().unwrap();
+
+    let Some(progress) = state
+        .claim
+        .as_ref()
+        .and_then(|claim| claim.progress.clone())
+    else {
+        return html! {};
+    };
+
+    let link = |path: String, label: String| match &props.explorer {
+        Some(explorer) => html! {
+            <a class="underline" href={format!("{explorer}/{path}")} target="_blank" rel="noopener noreferrer">{label}</a>
+        },
+        None => html! { <span>{label}</span> },
+    };
+
+    html! {
+        <div class="rounded-md bg-gray-50 dark:bg-gray-700 px-6 py-4 space-y-1 text-sm text-gray-800 dark:text-gray-200">
+            <div class="flex items-center justify-between">
+                <span>{"Transaction"}</span>
+                <span class="chip chip__gray">{progress.stage.to_string()}</span>
+            </div>
+            <div class="flex items-center justify-between text-xs" title={progress.extrinsic_hash.clone()}>
+                <span>{"Extrinsic"}</span>
+                { link(format!("extrinsic/{}", progress.extrinsic_hash), progress.extrinsic_hash_compact()) }
+            </div>
+            {
+                if let Some(block) = &progress.block {
+                    html! {
+                        <div class="flex items-center justify-between text-xs" title={block.hash.clone()}>
+                            <span>{"Block"}</span>
+                            { link(format!("block/{}", block.number), format!("# {}", block.number)) }
+                        </div>
+                    }
+                } else { html! {} }
+            }
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct PayoutItemProps {
+    pub payout: Payout,
+}
+
+#[function_component(PayoutItem)]
+pub fn payout_item(props: &PayoutItemProps) -> Html {
+    let state = use_context::<StateContext>().unwrap();
+    let runtime = state.network.runtime;
+    let payout = &props.payout;
+
+    html! {
+        <li class="flex items-center justify-between px-6 py-2 rounded-lg bg-white dark:bg-gray-700">
+            <div class="flex flex-col">
+                <p class="text-xs">{format!("# {} / {}", payout.parent_id, payout.child_id)}</p>
+                <p class="text-xs" title={payout.extrinsic_hash.clone()}>
+                    {format!("Block # {} · {}", payout.block_number, payout.extrinsic_hash_compact())}
+                </p>
+            </div>
+            <div class="inline-flex items-center gap-2">
+                <span class="chip chip__gray">{payout.kind.clone()}</span>
+                <div class="inline-flex items-center text-base text-gray-800">
+                    <div>{payout.amount_human(runtime)}</div>
+                    <div class="ml-1 font-light dark:text-gray-400">{runtime.unit()}</div>
+                </div>
+            </div>
+        </li>
+    }
+}
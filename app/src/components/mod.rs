@@ -7,5 +7,6 @@ pub mod inputs;
 pub mod items;
 pub mod modals;
 pub mod nav;
+pub mod payouts;
 pub mod spinners;
 pub mod steps;
@@ -1,21 +1,36 @@
 use crate::components::{
     buttons::{ExtensionButton, SignButton},
-    inputs::AccountInput,
-    items::{ChildBountyItemSmall, ExtensionAccountDropdown},
+    inputs::{AccountInput, MultisigInput},
+    items::{
+        BatchModeItem, ChildBountyItemSmall, ClaimFeeAssetItem, ClaimFeeSummary, ClaimProxyItem,
+        ExtensionAccountDropdown, MultisigSummary, OfflineSigningSummary, TxProgressSummary,
+    },
 };
+use crate::router::{Query, Routes};
 use crate::state::{Action, StateContext};
+use claimit_common::runtimes::utils::amount_human;
 use claimit_common::types::{
+    batches::BatchMode,
     claims::ClaimStatus,
     extensions::{
-        collect_signature, get_accounts, get_extensions, Extension, ExtensionAccount,
-        ExtensionStatus,
+        collect_signature, get_accounts, get_extensions, get_signing_methods, Extension,
+        ExtensionAccount, ExtensionStatus, SigningMethod,
     },
+    fees::FeeAsset,
+    multisigs::Multisig,
+    network::{is_valid_rpc_url, RpcEndpoints},
+    proxies::CLAIM_PROXY_TYPES,
 };
 use log::{error, warn};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use subxt::utils::AccountId32;
+use web_sys::HtmlInputElement;
 use yew::{
     classes, function_component, html, platform::spawn_local, use_context, use_effect_with,
-    use_state, AttrValue, Callback, Html,
+    use_node_ref, use_state, AttrValue, Callback, Html, NodeRef, Properties,
 };
+use yew_router::prelude::{use_location, use_navigator};
 
 #[function_component(ClaimModal)]
 pub fn claim_modal() -> Html {
@@ -25,6 +40,12 @@ pub fn claim_modal() -> Html {
     let extension_accounts = use_state(|| Vec::<ExtensionAccount>::new());
     let state = use_context::<StateContext>().unwrap();
     let extension = state.extension.clone();
+    let location = use_location().unwrap();
+    let explorer = location
+        .query::<Query>()
+        .ok()
+        .and_then(|q| q.explorer_url(state.network.runtime))
+        .map(AttrValue::from);
 
     // Load browser extensions installed on mount;
     use_effect_with((), {
@@ -60,22 +81,21 @@ pub fn claim_modal() -> Html {
                 match claim.status {
                     ClaimStatus::Initializing => {
                         is_visible.set(true);
+                        err.set("".to_string());
                         // if extension.signer.is_some() {
                         //     state.dispatch(Action::ConnectExtension);
                         // }
                     }
                     ClaimStatus::Signing(payload) => {
-                        if extension.is_ready() {
+                        // Note: payloads of an offline signer are exported, its signature is pasted back
+                        if extension.is_ready() && !extension.is_offline() {
                             let signer = extension.signer.as_ref().unwrap().clone();
+                            // Note: wallets without `signPayload` sign the signer payload bytes instead,
+                            // which fails with the reason why if the wallet wraps them in `<Bytes>`
+                            let method = SigningMethod::preferred(&extension.signing_methods);
                             err.set("".to_string());
                             spawn_local(async move {
-                                match collect_signature(
-                                    payload.clone(),
-                                    signer.source.to_string(),
-                                    signer.address.clone(),
-                                )
-                                .await
-                                {
+                                match collect_signature(&payload, &signer, method).await {
                                     Ok(signature) => {
                                         state.dispatch(Action::SubmitWithSignature(signature));
                                     }
@@ -87,11 +107,19 @@ pub fn claim_modal() -> Html {
                             });
                         }
                     }
+                    ClaimStatus::Error(e) => {
+                        err.set(e);
+                    }
                     ClaimStatus::Completed => {
-                        // TODO: wait 1 or 2 seconds close and dispatch action
-                        // TODO: add a green tick on all successfull child bounties in the modal before closing it
-                        is_visible.set(false);
-                        state.dispatch(Action::ResetClaim);
+                        // Keep the modal open to explain why some child bounties could not be claimed
+                        if claim.has_failures() {
+                            err.set("Some child bounties could not be claimed.".to_string());
+                        } else if claim.has_approvals() {
+                            // Keep the modal open to show the threshold progress of the multisig
+                        } else {
+                            is_visible.set(false);
+                            state.dispatch(Action::ResetClaim);
+                        }
                     }
                     _ => {}
                 }
@@ -108,10 +136,16 @@ pub fn claim_modal() -> Html {
             ExtensionStatus::Connecting(source) => {
                 let source = source.clone();
                 spawn_local(async move {
-                    match get_accounts(source).await {
+                    match get_accounts(source.clone()).await {
                         Ok(accounts) => {
                             if accounts.len() > 0 {
                                 extension_accounts.set(accounts);
+                                match get_signing_methods(source).await {
+                                    Ok(methods) => {
+                                        state.dispatch(Action::UpdateSigningMethods(methods))
+                                    }
+                                    Err(e) => warn!("Signing methods unavailable: {:?}", e),
+                                }
                                 state.dispatch(Action::ChangeExtensionStatus(
                                     ExtensionStatus::Connected,
                                 ));
@@ -161,6 +195,97 @@ pub fn claim_modal() -> Html {
         })
     };
 
+    let onenter_offline_signer = {
+        let state = state.clone();
+        Callback::from(move |address: String| {
+            if let Ok(account) = AccountId32::from_str(&address) {
+                state.dispatch(Action::ChangeSigner(ExtensionAccount::offline(&account)));
+            }
+        })
+    };
+
+    let onchange_batch_mode = {
+        let state = state.clone();
+        Callback::from(move |mode: BatchMode| {
+            state.dispatch(Action::ChangeBatchMode(mode));
+        })
+    };
+
+    let onchange_proxy = {
+        let state = state.clone();
+        Callback::from(move |proxy: Option<AccountId32>| {
+            // Note: selecting the signer also drops the multisig, if any
+            if proxy.is_none() {
+                state.dispatch(Action::ChangeClaimMultisig(None));
+            }
+            state.dispatch(Action::ChangeClaimProxy(proxy));
+        })
+    };
+
+    let onchange_multisig = {
+        let state = state.clone();
+        Callback::from(move |multisig: Option<Multisig>| {
+            state.dispatch(Action::ChangeClaimMultisig(multisig));
+        })
+    };
+
+    let onchange_fee_asset = {
+        let state = state.clone();
+        Callback::from(move |fee_asset: Option<FeeAsset>| {
+            state.dispatch(Action::ChangeClaimFeeAsset(fee_asset));
+        })
+    };
+
+    let cannot_claim = format!(
+        "Only {} proxies without delay can claim",
+        CLAIM_PROXY_TYPES.join(", ")
+    );
+    let signer_account = extension
+        .signer
+        .as_ref()
+        .and_then(|signer| AccountId32::from_str(&signer.address).ok());
+
+    // The wallet connected, along with the signing methods it exposes
+    let wallet_summary = extension
+        .signer
+        .as_ref()
+        .filter(|_| !extension.signing_methods.is_empty())
+        .and_then(|signer| {
+            extensions_supported
+                .iter()
+                .find(|ext| ext.name == signer.source)
+        })
+        .map(|ext| {
+            let methods = extension
+                .signing_methods
+                .iter()
+                .map(|method| method.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            match &ext.version {
+                Some(version) => format!("{} {version} · Signs via {methods}", ext.description),
+                None => format!("{} · Signs via {methods}", ext.description),
+            }
+        });
+
+    let wallet_button = |ext: &Extension| {
+        html! {
+            <ExtensionButton name={ext.name.clone()} label={ext.description.clone()} version={ext.version.clone()}
+                class={classes!("btn__ext", "px-4", "me-2")} disabled={!ext.installed} onclick={&onconnect} >
+                {
+                    match ext.icon() {
+                        Some(icon) => html! { <img class="h-6" src={icon} alt={format!("{} extension", ext.description)} /> },
+                        None => html! {
+                            <svg class="w-6 h-6 text-gray-600" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" width="24" height="24" fill="none" viewBox="0 0 24 24">
+                                <path stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 8H5m12 0a1 1 0 0 1 1 1v2.6M17 8l-4-4M5 8a1 1 0 0 0-1 1v10a1 1 0 0 0 1 1h12a1 1 0 0 0 1-1v-2.6M5 8l4-4 4 4m6 4h-4a2 2 0 1 0 0 4h4a1 1 0 0 0 1-1v-2a1 1 0 0 0-1-1Z"/>
+                            </svg>
+                        },
+                    }
+                }
+            </ExtensionButton>
+        }
+    };
+
     let visibility = if *is_visible {
         Some("flex")
     } else {
@@ -197,6 +322,13 @@ pub fn claim_modal() -> Html {
                                         <h4 class="ms-2 mb-2 text-sm text-gray-600 dark:text-gray-100">{"Claim from account"}</h4>
                                         <ExtensionAccountDropdown selected={extension.signer.clone()}
                                             options={(*extension_accounts).clone()} onchange={&onchange_extension_account} />
+                                        {
+                                            if let Some(wallet_summary) = wallet_summary {
+                                                html! { <p class="ms-2 mt-1 text-xs text-gray-600 dark:text-gray-100">{wallet_summary}</p> }
+                                            } else {
+                                                html! {}
+                                            }
+                                        }
                                     </div>
                                 }
                             } else {
@@ -204,22 +336,10 @@ pub fn claim_modal() -> Html {
                                 html! {
                                     <div>
                                         <h4 class="ms-2 mb-2 text-sm text-gray-600 dark:text-gray-100">{"Supported wallets"}</h4>
-                                        { for extensions_supported.iter().cloned().filter(|ext| ext.installed).map(|ext|
-                                            html! {
-                                                <ExtensionButton name={ext.name.clone()} label={ext.description.clone()} class={classes!("btn__ext", "px-4", "me-2")}
-                                                    disabled={!ext.installed} onclick={&onconnect} >
-                                                    <img class="h-6" src={format!("/images/ext/{}.svg", ext.name)} alt={format!("{} extension", ext.description)} />
-                                                </ExtensionButton>
-                                            })
-                                        }
-                                        { for extensions_supported.iter().cloned().filter(|ext| !(ext.installed)).map(|ext|
-                                            html! {
-                                                <ExtensionButton name={ext.name.clone()} label={ext.description.clone()} class={classes!("btn__ext", "px-4", "me-2")}
-                                                    disabled={!ext.installed} onclick={&onconnect} >
-                                                    <img class="h-6" src={format!("/images/ext/{}.svg", ext.name)} alt={format!("{} extension", ext.description)} />
-                                                </ExtensionButton>
-                                            })
-                                        }
+                                        { for extensions_supported.iter().filter(|ext| ext.installed).map(wallet_button) }
+                                        { for extensions_supported.iter().filter(|ext| !(ext.installed)).map(wallet_button) }
+                                        <h4 class="ms-2 mt-4 text-sm text-gray-600 dark:text-gray-100">{"Or sign offline"}</h4>
+                                        <AccountInput placeholder="Signer address, to sign with Polkadot Vault or a hardware wallet" onenter={&onenter_offline_signer} />
                                     </div>
                                 }
 
@@ -228,17 +348,85 @@ pub fn claim_modal() -> Html {
 
                         {
                             if extension.is_connected_or_ready() && state.claim.is_some() {
+                                    let claim = state.claim.clone().unwrap();
 
                                     html! {
                                         <div>
+                                            <h4 class="ms-2 mb-2 text-sm text-gray-600 dark:text-gray-100">{"Submit claims as"}</h4>
+                                            <ul class="tab mb-4">
+                                                { for BatchMode::iter().map(|mode| {
+                                                    html! {
+                                                        <BatchModeItem {mode} selected={claim.mode == mode}
+                                                            disabled={!claim.is_initializing()} onclick={&onchange_batch_mode} />
+                                                    }
+                                                }) }
+                                            </ul>
+                                            <h4 class="ms-2 mb-2 text-sm text-gray-600 dark:text-gray-100">{"Claim on behalf of"}</h4>
+                                            <ul class="tab flex-wrap gap-y-2 mb-4">
+                                                <ClaimProxyItem real={None::<AccountId32>} label="Signer" title="Claim from the signer account"
+                                                    selected={claim.proxy.is_none() && claim.multisig.is_none()} disabled={!claim.is_initializing()} onclick={&onchange_proxy} />
+                                                { for claim.proxies.iter().map(|proxy| {
+                                                    let title = if proxy.can_claim() { format!("Claim via Proxy::proxy from {}", proxy.real) } else { cannot_claim.clone() };
+                                                    html! {
+                                                        <ClaimProxyItem real={Some(proxy.real.clone())} label={proxy.description()} {title}
+                                                            selected={claim.proxy.as_ref() == Some(&proxy.real)}
+                                                            disabled={!claim.is_initializing() || !proxy.can_claim()} onclick={&onchange_proxy} />
+                                                    }
+                                                }) }
+                                            </ul>
+                                            {
+                                                match signer_account.clone() {
+                                                    Some(signer) if claim.is_initializing() => html! {
+                                                        <MultisigInput {signer} onchange={&onchange_multisig} />
+                                                    },
+                                                    _ => html! {},
+                                                }
+                                            }
+                                            <div class="mb-4">
+                                                <MultisigSummary />
+                                            </div>
+                                            {
+                                                if claim.fee_asset_balances.is_empty() {
+                                                    html! {}
+                                                } else {
+                                                    let unit = state.network.runtime.unit();
+                                                    html! {
+                                                        <>
+                                                            <h4 class="ms-2 mb-2 text-sm text-gray-600 dark:text-gray-100">{"Pay fees with"}</h4>
+                                                            <ul class="tab flex-wrap gap-y-2 mb-4">
+                                                                <ClaimFeeAssetItem asset={None::<FeeAsset>} label={unit} title={format!("Pay the fee in {unit}")}
+                                                                    selected={claim.fee_asset.is_none()} disabled={!claim.is_initializing()} onclick={&onchange_fee_asset} />
+                                                                { for claim.fee_asset_balances.iter().map(|(asset, balance)| {
+                                                                    let label = format!("{} {}", amount_human(*balance, asset.decimals()), asset.symbol());
+                                                                    let title = format!("Pay the fee in {}, swapped for {unit} via the asset conversion pool", asset.symbol());
+                                                                    html! {
+                                                                        <ClaimFeeAssetItem asset={Some(*asset)} {label} {title}
+                                                                            selected={claim.fee_asset == Some(*asset)}
+                                                                            disabled={!claim.is_initializing() || *balance == 0} onclick={&onchange_fee_asset} />
+                                                                    }
+                                                                }) }
+                                                            </ul>
+                                                        </>
+                                                    }
+                                                }
+                                            }
                                             <h4 class="ms-2 mb-2 text-sm text-gray-600 dark:text-gray-100">{"Claimable child bounties"}</h4>
                                             <ul class="flex-column space-y space-y-4 text-sm font-medium text-gray-600 dark:text-gray-400 overflow-y-scroll h-96">
-                                                { for state.claim.clone().unwrap().child_bounty_ids.into_iter().map(|(_, cb_id)|
+                                                { for claim.child_bounty_ids.iter().map(|(parent_id, cb_id)|
                                                     html! {
-                                                        <ChildBountyItemSmall id={cb_id} />
+                                                        <ChildBountyItemSmall id={*cb_id} outcome={claim.outcome(*parent_id, *cb_id).cloned()} />
                                                     })
                                                 }
                                             </ul>
+                                            <div class="mt-4">
+                                                <ClaimFeeSummary />
+                                            </div>
+                                            <div class="mt-4">
+                                                <OfflineSigningSummary />
+                                            </div>
+                                            <div class="mt-4">
+                                                <TxProgressSummary explorer={explorer.clone()} />
+                                            </div>
                                         </div>
                                     }
 
@@ -335,3 +523,176 @@ pub fn add_account_modal() -> Html {
         </div>
     }
 }
+
+#[derive(Properties, PartialEq)]
+pub struct RpcEndpointsModalProps {
+    pub onchange: Callback<RpcEndpoints>,
+}
+
+#[function_component(RpcEndpointsModal)]
+pub fn rpc_endpoints_modal(props: &RpcEndpointsModalProps) -> Html {
+    let is_visible = use_state(|| false);
+    let err = use_state(|| "".to_string());
+    let state = use_context::<StateContext>().unwrap();
+    let navigator = use_navigator().unwrap();
+    let location = use_location().unwrap();
+    let relay_ref = use_node_ref();
+    let people_ref = use_node_ref();
+    let asset_hub_ref = use_node_ref();
+
+    use_effect_with(state.layout.clone(), {
+        let is_visible = is_visible.clone();
+
+        move |layout| {
+            is_visible.set(layout.is_rpc_endpoints_modal_visible);
+        }
+    });
+
+    let onchange = {
+        let state = state.clone();
+        let onchange = props.onchange.clone();
+        let query = location.query::<Query>().unwrap();
+
+        Callback::from(move |endpoints: RpcEndpoints| {
+            // Drop any custom endpoints from the query, the persisted ones are used from now on
+            navigator
+                .push_with_query(
+                    &Routes::Index,
+                    &Query {
+                        lc: false,
+                        bounties: query.bounties.clone(),
+                        rpc: None,
+                        people_rpc: None,
+                        asset_hub_rpc: None,
+                        ..query
+                    },
+                )
+                .unwrap();
+            state.dispatch(Action::ToggleLayoutRpcEndpointsModal);
+            onchange.emit(endpoints);
+        })
+    };
+
+    let onsave = {
+        let err = err.clone();
+        let onchange = onchange.clone();
+        let relay_ref = relay_ref.clone();
+        let people_ref = people_ref.clone();
+        let asset_hub_ref = asset_hub_ref.clone();
+
+        Callback::from(move |_| {
+            let mut invalid = Vec::new();
+            let mut value = |node_ref: &NodeRef| -> Option<String> {
+                let url = node_ref
+                    .cast::<HtmlInputElement>()
+                    .map(|input| input.value().trim().to_string())
+                    .unwrap_or_default();
+                if url.is_empty() {
+                    None
+                } else if is_valid_rpc_url(&url) {
+                    Some(url)
+                } else {
+                    invalid.push(url);
+                    None
+                }
+            };
+            let endpoints = RpcEndpoints {
+                relay: value(&relay_ref),
+                people: value(&people_ref),
+                asset_hub: value(&asset_hub_ref),
+            };
+            if invalid.is_empty() {
+                err.set("".to_string());
+                onchange.emit(endpoints);
+            } else {
+                err.set(format!(
+                    "Invalid endpoint {}, expected ws:// or wss://",
+                    invalid.join(", ")
+                ));
+            }
+        })
+    };
+
+    let onreset = {
+        let err = err.clone();
+        let onchange = onchange.clone();
+        Callback::from(move |_| {
+            err.set("".to_string());
+            onchange.emit(RpcEndpoints::default());
+        })
+    };
+
+    let oncancel = {
+        let state = state.clone();
+        let err = err.clone();
+        Callback::from(move |_| {
+            err.set("".to_string());
+            state.dispatch(Action::ToggleLayoutRpcEndpointsModal);
+        })
+    };
+
+    let visibility = if *is_visible {
+        Some("flex")
+    } else {
+        Some("hidden")
+    };
+
+    let runtime = state.network.runtime;
+    let endpoints = state.network.endpoints.clone();
+    let fields = [
+        (
+            "Relay chain",
+            relay_ref,
+            endpoints.relay,
+            runtime.default_rpc_url(),
+        ),
+        (
+            "People chain",
+            people_ref,
+            endpoints.people,
+            runtime.default_people_rpc_url(),
+        ),
+        (
+            "Asset Hub",
+            asset_hub_ref,
+            endpoints.asset_hub,
+            runtime.default_asset_hub_rpc_url(),
+        ),
+    ];
+
+    html! {
+        <div class={classes!("modal__add_account", visibility)}>
+            <div class="relative p-4 w-full max-w-2xl max-h-full">
+                <div class="relative bg-gray-200 rounded-lg shadow dark:bg-gray-700 z-60">
+                    <div class="flex items-center justify-between px-4 pt-4 md:px-5 md:pt-5 rounded-t-lg">
+                        <h3 class="text-lg font-semibold text-gray-900 dark:text-white">
+                            {format!("{runtime} RPC Endpoints")}
+                        </h3>
+                        <button type="button" class="btn btn__icon btn__white" onclick={&oncancel} >
+                            <svg class="w-4 h-4 text-gray-600 dark:text-white" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" width="24" height="24" fill="none" viewBox="0 0 24 24">
+                                <path stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M6 18 17.94 6M18 18 6.06 6"/>
+                            </svg>
+                            <span class="sr-only">{"Close"}</span>
+                        </button>
+                    </div>
+                    <div class="p-4 md:p-5 space-y-4">
+                        <p class="text-sm text-gray-800">{"Leave an endpoint empty to use the default ones."}</p>
+                        { for fields.into_iter().map(|(label, node_ref, value, placeholder)| html! {
+                            <label class="block text-sm text-gray-900">
+                                {label}
+                                <input ref={node_ref} type="text" class="account__input mt-1" {placeholder}
+                                    value={value.unwrap_or_default()} />
+                            </label>
+                        })}
+                        <div class="ps-1 text-xs text-red">{err.to_string()}</div>
+                    </div>
+
+                    <div class="flex items-center justify-end space-x-2 p-4 md:p-5 rounded-b-lg">
+                        <button type="button" class="btn btn__gray" onclick={&onreset}>{"Reset"}</button>
+                        <button type="button" class={classes!("btn", "btn__primary", runtime.class())} onclick={&onsave}>{"Save"}</button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,5 +1,5 @@
 use crate::components::{
-    buttons::{NetworkProviderIconButton, NetworkSubscriber},
+    buttons::{NetworkProviderIconButton, NetworkSubscriber, RpcEndpointsIconButton},
     spinners::Spinner,
 };
 use crate::state::StateContext;
@@ -68,11 +68,24 @@ pub fn navbar(props: &NavbarProps) -> Html {
                                         <span>{"Paseo"}</span>
                                     </>
                                 },
+                                SupportedRelayRuntime::Westend => html! {
+                                    <>
+                                        <img class="h-8" src="/images/westend_icon.svg" alt="westend logo" />
+                                        <span>{"Westend"}</span>
+                                    </>
+                                },
+                                SupportedRelayRuntime::Local => html! {
+                                    <>
+                                        <img class="h-8" src="/images/local_icon.svg" alt="local logo" />
+                                        <span>{"Local"}</span>
+                                    </>
+                                },
                             }
                         }
                     </div>
 
-                    <NetworkProviderIconButton class="ms-4" onclick={props.ontoggle_provider.clone()} />
+                    <NetworkProviderIconButton class="ms-4" disabled={!props.runtime.supports_light_client()} onclick={props.ontoggle_provider.clone()} />
+                    <RpcEndpointsIconButton class="ms-2" />
 
                 </div>
             </div>
@@ -0,0 +1,69 @@
+use crate::components::{chips::AccountChip, items::PayoutItem};
+use crate::state::StateContext;
+use claimit_common::runtimes::utils::amount_human;
+use std::str::FromStr;
+use subxt::utils::AccountId32;
+use yew::{function_component, html, use_context, Html};
+
+#[function_component(PayoutsHistoryCard)]
+pub fn payouts_history_card() -> Html {
+    let state = use_context::<StateContext>().unwrap();
+    let runtime = state.network.runtime;
+
+    let accounts = state
+        .accounts
+        .iter()
+        .filter_map(|account| AccountId32::from_str(&account.address).ok())
+        .map(|account| {
+            let payouts = state.payouts_history.by_account(&account);
+            (account, payouts)
+        })
+        .filter(|(_, payouts)| !payouts.is_empty())
+        .collect::<Vec<_>>();
+
+    if accounts.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="flex flex-col p-4 md:p-6 mt-4 bg-gray-50 max-w-[375px] sm:max-w-[828px] rounded-lg w-full">
+            <div class="flex flex-none justify-between items-center mb-4 ms-1">
+                <h3 class="md:text-lg font-bold text-gray-900 dark:text-gray-100">{"Payout History"}</h3>
+                {
+                    if let Some((block_number, limit)) = state.payouts_history.limit {
+                        html! { <span class="text-xs">{format!("Indexed since block # {block_number}, {limit}")}</span> }
+                    } else { html! {} }
+                }
+            </div>
+            { for accounts.into_iter().map(|(account, payouts)| {
+                let total = payouts
+                    .iter()
+                    .filter_map(|payout| payout.amount)
+                    .sum::<u128>();
+
+                html! {
+                    <div class="mb-4">
+                        <div class="flex items-center justify-between mb-2 mx-1">
+                            <div class="flex flex-col">
+                                <AccountChip account={account.clone()} />
+                                {
+                                    if let Some(block_number) = state.payouts_history.indexed_since(&account) {
+                                        html! { <span class="text-xs text-gray-500 ms-1">{format!("Indexed since block # {block_number}")}</span> }
+                                    } else { html! {} }
+                                }
+                            </div>
+                            <span class="text-sm text-gray-800">
+                                {format!("Total claimed {} {}", amount_human(total, runtime.decimals().into()), runtime.unit())}
+                            </span>
+                        </div>
+                        <ul class="flex-col w-full space-y-2 text-sm font-medium text-gray-500 dark:text-gray-400">
+                            { for payouts.into_iter().map(|payout| html! {
+                                <PayoutItem {payout} />
+                            })}
+                        </ul>
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
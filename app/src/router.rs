@@ -1,6 +1,7 @@
 use crate::app::App;
 use crate::pages::page_not_found::PageNotFound;
 use claimit_common::runtimes::support::SupportedRelayRuntime;
+use claimit_common::types::network::{is_valid_rpc_url, RpcEndpoints};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeSet;
 use yew::{function_component, html, Html};
@@ -17,7 +18,7 @@ pub enum Routes {
 
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct Query {
-    /// Specifies to which network [polkadot, kusama, paseo] the api will try to connect to
+    /// Specifies to which network [polkadot, kusama, paseo, westend, local] the api will try to connect to
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_from_any_case")]
     pub chain: SupportedRelayRuntime,
@@ -32,6 +33,52 @@ pub struct Query {
         deserialize_with = "deserialize_from_csv"
     )]
     pub bounties: BTreeSet<u32>,
+    // Custom relay chain RPC endpoint, e.g. ws://127.0.0.1:9944
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc: Option<String>,
+    // Custom people chain RPC endpoint
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub people_rpc: Option<String>,
+    // Custom asset hub chain RPC endpoint
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_hub_rpc: Option<String>,
+    // Number of blocks a claim transaction stays valid for, e.g. 128
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mortality: Option<u64>,
+    // Custom block explorer with subscan-like paths, e.g. https://assethub-polkadot.subscan.io
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer: Option<String>,
+}
+
+impl Query {
+    /// Returns the custom RPC endpoints defined in the query, if any valid
+    pub fn endpoints(&self) -> Option<RpcEndpoints> {
+        let valid = |url: &Option<String>| url.clone().filter(|url| is_valid_rpc_url(url));
+        let endpoints = RpcEndpoints {
+            relay: valid(&self.rpc),
+            people: valid(&self.people_rpc),
+            asset_hub: valid(&self.asset_hub_rpc),
+        };
+        if endpoints.is_empty() {
+            None
+        } else {
+            Some(endpoints)
+        }
+    }
+
+    /// Returns the block explorer defined in the query if valid, otherwise the default one of the runtime
+    pub fn explorer_url(&self, runtime: SupportedRelayRuntime) -> Option<String> {
+        self.explorer
+            .clone()
+            .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+            .map(|url| url.trim_end_matches('/').to_string())
+            .or_else(|| runtime.default_asset_hub_explorer_url().map(String::from))
+    }
 }
 
 fn deserialize_from_any_case<'de, D>(deserializer: D) -> Result<SupportedRelayRuntime, D::Error>
@@ -43,6 +90,8 @@ where
         "polkadot" | "dot" => Ok(SupportedRelayRuntime::Polkadot),
         "kusama" | "ksm" => Ok(SupportedRelayRuntime::Kusama),
         "paseo" | "pas" => Ok(SupportedRelayRuntime::Paseo),
+        "westend" | "wnd" => Ok(SupportedRelayRuntime::Westend),
+        "local" => Ok(SupportedRelayRuntime::Local),
         &_ => Ok(SupportedRelayRuntime::Polkadot),
     }
 }
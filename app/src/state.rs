@@ -1,14 +1,27 @@
+use claimit_common::errors::WorkerError;
 use claimit_common::runtimes::support::SupportedRelayRuntime;
 use claimit_common::types::{
     accounts::{Account, Balance},
-    child_bounties::ChildBountyId,
+    batches::BatchMode,
+    bounties::ParentBounties,
     child_bounties::{ChildBounties, ChildBountiesIds, Filter},
-    claims::{ClaimState, ClaimStatus},
-    extensions::{ExtensionAccount, ExtensionState, ExtensionStatus},
+    child_bounties::{ChildBounty, ChildBountyId},
+    claims::{ClaimOutcome, ClaimState, ClaimStatus, TxProgress},
+    compatibility::RuntimeCompatibility,
+    extensions::{
+        ExtensionAccount, ExtensionState, ExtensionStatus, SigningMethod, SigningParams,
+        SigningPayload,
+    },
+    fees::{FeeAsset, FeeEstimate},
     layout::{BalanceMode, LayoutState},
-    network::{NetworkState, NetworkStatus},
+    multisigs::{Multisig, MultisigStatus},
+    network::{ConnectionEvent, NetworkState, NetworkStatus, RpcEndpoints},
+    payouts::{Payouts, PayoutsHistory, PayoutsHistoryLimit, PAYOUTS_HISTORY_CHUNK_BLOCKS},
+    proxies::Proxy,
+    worker::{BlockNumber, RequestId, RequestKind},
 };
 use gloo::storage::{LocalStorage, Storage};
+use log::{error, warn};
 use serde::{Deserialize, Serialize};
 use std::{
     env,
@@ -21,12 +34,16 @@ use yew::{Reducible, UseReducerHandle};
 const ACCOUNTS_KEY: &str = "accounts";
 const SIGNER_KEY: &str = "signer";
 const ONBOARDED_KEY: &str = "onboarded";
+const PAYOUTS_HISTORY_KEY: &str = "payouts_history";
+const RPC_ENDPOINTS_KEY: &str = "rpc_endpoints";
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct State {
     pub accounts: Vec<Account>,
     pub network: NetworkState,
     pub child_bounties_raw: Option<ChildBounties>,
+    pub parent_bounties_raw: Option<ParentBounties>,
+    pub payouts_history: PayoutsHistory,
     pub filter: Filter,
     pub extension: ExtensionState,
     pub claim: Option<ClaimState>,
@@ -44,26 +61,53 @@ pub enum Action {
     UpdateAccountIdentity(AccountId32, Option<String>),
     /// Claim/Sign actions
     StartClaim(ChildBountiesIds),
+    ChangeBatchMode(BatchMode),
+    UpdateClaimProxies(AccountId32, Vec<Proxy>),
+    ChangeClaimProxy(Option<AccountId32>),
+    ChangeClaimMultisig(Option<Multisig>),
+    UpdateClaimFeeAssetBalances(AccountId32, Vec<(FeeAsset, u128)>),
+    ChangeClaimFeeAsset(Option<FeeAsset>),
     PreparePayload,
-    GetSignature(String),
+    UpdateClaimBatches(RequestId, Vec<ChildBountiesIds>),
+    UpdateClaimMultisigStatus(RequestId, MultisigStatus),
+    GetSignature(
+        RequestId,
+        SigningPayload,
+        SigningParams,
+        Option<FeeEstimate>,
+    ),
     SubmitWithSignature(Vec<u8>),
-    CompleteClaim(Vec<ChildBountyId>),
+    UpdateClaimProgress(RequestId, TxProgress),
+    CompleteClaim(RequestId, Vec<ClaimOutcome>),
     ResetClaim,
     // ErrorClaim(String),
     /// Extension actions
     ConnectExtension(String),
     ChangeExtensionStatus(ExtensionStatus),
+    UpdateSigningMethods(Vec<SigningMethod>),
     ChangeSigner(ExtensionAccount),
     /// Network actions
     ChangeNetworkStatus(NetworkStatus),
-    ResetNetwork(SupportedRelayRuntime, bool),
+    UpdateConnection(ConnectionEvent),
+    UpdateCompatibility(RuntimeCompatibility),
+    ResetNetwork(SupportedRelayRuntime, bool, RpcEndpoints),
     UpdateBlockNumber(u32),
     UpdateChildBountiesRaw(ChildBounties),
+    UpdateChildBounty(ChildBounty),
+    RemoveChildBounty(ChildBountyId),
+    UpdateParentBountiesRaw(ParentBounties),
+    UpdatePayoutsHistory(Payouts, BlockNumber, BlockNumber),
+    UpdatePayoutsHistoryLimit(BlockNumber, PayoutsHistoryLimit),
     IncreaseFetch,
+    /// Request actions
+    StartRequest(RequestId, RequestKind),
+    CompleteRequest(RequestId),
+    FailRequest(RequestId, WorkerError),
     /// Filter child bounties actions
     SetFilter(Filter),
     /// Layout actions
     ToggleLayoutAddAccountModal,
+    ToggleLayoutRpcEndpointsModal,
     ChangeBalanceMode(BalanceMode),
     StartOnboarding,
     FinishOnboarding,
@@ -89,7 +133,7 @@ impl Reducible for State {
                                 .into_iter()
                                 .filter(|(_, cb)| {
                                     let acc = AccountId32::from_str(&address).unwrap();
-                                    acc == cb.beneficiary
+                                    cb.is_beneficiary(&acc)
                                 })
                                 .map(|(id, _)| id)
                                 .collect::<BTreeSet<u32>>()
@@ -123,6 +167,8 @@ impl Reducible for State {
                     accounts,
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter,
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -151,6 +197,8 @@ impl Reducible for State {
                     accounts,
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter,
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -168,6 +216,8 @@ impl Reducible for State {
                     accounts,
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -185,6 +235,8 @@ impl Reducible for State {
             //         accounts,
             //         network: self.network.clone(),
             //         child_bounties_raw: self.child_bounties_raw.clone(),
+            //         parent_bounties_raw: self.parent_bounties_raw.clone(),
+            //         payouts_history: self.payouts_history.clone(),
             //         filter: self.filter.clone(),
             //         extension: self.extension.clone(),
             //         claim: self.claim.clone(),
@@ -203,6 +255,8 @@ impl Reducible for State {
             //         accounts,
             //         network: self.network.clone(),
             //         child_bounties_raw: self.child_bounties_raw.clone(),
+            //         parent_bounties_raw: self.parent_bounties_raw.clone(),
+            //         payouts_history: self.payouts_history.clone(),
             //         filter: self.filter.clone(),
             //         extension: self.extension.clone(),
             //         claim: self.claim.clone(),
@@ -223,6 +277,8 @@ impl Reducible for State {
                     accounts,
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -243,6 +299,8 @@ impl Reducible for State {
                     accounts,
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -256,6 +314,148 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::ChangeBatchMode(mode) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                claim.mode = mode;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateClaimProxies(delegate, proxies) => {
+                let Some(mut claim) = self.claim.clone() else {
+                    return self;
+                };
+                // Ignore proxies of a previous signer, or once the claim is on its way
+                let signer = self
+                    .extension
+                    .signer
+                    .as_ref()
+                    .and_then(|signer| AccountId32::from_str(&signer.address).ok());
+                if !claim.is_initializing() || signer != Some(delegate) {
+                    return self;
+                }
+                claim.proxies = proxies;
+                // Note: keep the account selected only if the signer can still claim on its behalf
+                if !claim
+                    .proxies
+                    .iter()
+                    .any(|proxy| Some(&proxy.real) == claim.proxy.as_ref() && proxy.can_claim())
+                {
+                    claim.proxy = None;
+                }
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::ChangeClaimProxy(proxy) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                // Note: a claim is dispatched either via a proxy or a multisig, not both
+                if proxy.is_some() {
+                    claim.multisig = None;
+                }
+                claim.proxy = proxy;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::ChangeClaimMultisig(multisig) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                if multisig.is_some() {
+                    claim.proxy = None;
+                }
+                claim.multisig = multisig;
+                claim.multisig_status = None;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateClaimFeeAssetBalances(account, balances) => {
+                let Some(mut claim) = self.claim.clone() else {
+                    return self;
+                };
+                // Ignore balances of a previous signer, or once the claim is on its way
+                let signer = self
+                    .extension
+                    .signer
+                    .as_ref()
+                    .and_then(|signer| AccountId32::from_str(&signer.address).ok());
+                if !claim.is_initializing() || signer != Some(account) {
+                    return self;
+                }
+                claim.fee_asset_balances = balances;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::ChangeClaimFeeAsset(fee_asset) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                claim.fee_asset = fee_asset;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: Some(claim),
@@ -267,6 +467,8 @@ impl Reducible for State {
                 accounts: self.accounts.clone(),
                 network: self.network.clone(),
                 child_bounties_raw: self.child_bounties_raw.clone(),
+                parent_bounties_raw: self.parent_bounties_raw.clone(),
+                payouts_history: self.payouts_history.clone(),
                 filter: self.filter.clone(),
                 extension: self.extension.clone(),
                 claim: None,
@@ -281,6 +483,68 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateClaimBatches(request_id, batches) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                // Ignore batches from requests not related to the current claim
+                if claim.request_id != Some(request_id) {
+                    return self;
+                }
+                // Note: the claim stays in preparing, now for the payload of the first batch
+                if batches.is_empty() {
+                    claim.status = ClaimStatus::Error("There is nothing to claim.".to_string());
+                }
+                claim.batches = batches;
+                claim.batch_index = 0;
+                claim.multisig_status = None;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateClaimMultisigStatus(request_id, status) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                // Ignore approvals from requests not related to the current claim
+                if claim.request_id != Some(request_id) {
+                    return self;
+                }
+                // Note: the claim stays in preparing, now for the payload wrapped by the multisig
+                let signer = self
+                    .extension
+                    .signer
+                    .as_ref()
+                    .and_then(|signer| AccountId32::from_str(&signer.address).ok());
+                if signer.is_some_and(|signer| status.is_approved_by(&signer)) {
+                    claim.status = ClaimStatus::Error(
+                        "The signer has already approved this claim, waiting for the other signatories."
+                            .to_string(),
+                    );
+                }
+                claim.multisig_status = Some(status);
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: Some(claim),
@@ -288,13 +552,21 @@ impl Reducible for State {
                 }
                 .into()
             }
-            Action::GetSignature(payload) => {
+            Action::GetSignature(request_id, payload, params, fee) => {
                 let mut claim = self.claim.as_ref().unwrap().clone();
+                // Ignore payloads from requests not related to the current claim
+                if claim.request_id != Some(request_id) {
+                    return self;
+                }
                 claim.status = ClaimStatus::Signing(payload);
+                claim.params = Some(params);
+                claim.fee = fee;
                 State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: Some(claim),
@@ -305,11 +577,14 @@ impl Reducible for State {
             Action::SubmitWithSignature(signature) => {
                 let mut claim = self.claim.as_ref().unwrap().clone();
                 claim.status = ClaimStatus::Submitting(signature);
+                claim.progress = None;
 
                 State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: Some(claim),
@@ -317,9 +592,49 @@ impl Reducible for State {
                 }
                 .into()
             }
-            Action::CompleteClaim(claimed) => {
+            Action::UpdateClaimProgress(request_id, progress) => {
                 let mut claim = self.claim.as_ref().unwrap().clone();
-                claim.status = ClaimStatus::Completed;
+                // Ignore progress from requests not related to the current claim
+                if claim.request_id != Some(request_id) {
+                    return self;
+                }
+                claim.progress = Some(progress);
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: Some(claim),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::CompleteClaim(request_id, outcomes) => {
+                let mut claim = self.claim.as_ref().unwrap().clone();
+                // Ignore completions from requests not related to the current claim
+                if claim.request_id != Some(request_id) {
+                    return self;
+                }
+                let claimed = outcomes
+                    .iter()
+                    .filter(|outcome| outcome.is_claimed())
+                    .map(|outcome| outcome.child_id)
+                    .collect::<Vec<ChildBountyId>>();
+                claim.outcomes.extend(outcomes);
+                // Move on to prepare the next batch, if any
+                if claim.has_next_batch() {
+                    claim.batch_index += 1;
+                    claim.status = ClaimStatus::Preparing;
+                    claim.fee = None;
+                    claim.params = None;
+                    claim.multisig_status = None;
+                } else {
+                    claim.status = ClaimStatus::Completed;
+                }
 
                 let accounts = self
                     .accounts
@@ -352,6 +667,8 @@ impl Reducible for State {
                     accounts,
                     network: self.network.clone(),
                     child_bounties_raw: Some(child_bounties_raw),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: Some(claim),
@@ -367,6 +684,8 @@ impl Reducible for State {
             //         accounts: self.accounts.clone(),
             //         network: self.network.clone(),
             //         child_bounties_raw: self.child_bounties_raw.clone(),
+            //         parent_bounties_raw: self.parent_bounties_raw.clone(),
+            //         payouts_history: self.payouts_history.clone(),
             //         filter: self.filter.clone(),
             //         extension: self.extension.clone(),
             //         claim: Some(claim),
@@ -377,11 +696,14 @@ impl Reducible for State {
             Action::ConnectExtension(source) => {
                 let mut extension = self.extension.clone();
                 extension.status = ExtensionStatus::Connecting(source);
+                extension.signing_methods = Vec::new();
 
                 State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension,
                     claim: self.claim.clone(),
@@ -397,6 +719,25 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension,
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateSigningMethods(signing_methods) => {
+                let mut extension = self.extension.clone();
+                extension.signing_methods = signing_methods;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension,
                     claim: self.claim.clone(),
@@ -411,13 +752,27 @@ impl Reducible for State {
                 extension.signer = Some(account.clone());
                 extension.status = ExtensionStatus::Ready;
 
+                // Proxies, multisigs and fee asset balances depend on the signer, they are fetched
+                // again for the new one
+                let claim = self.claim.clone().map(|mut claim| {
+                    claim.proxies = Vec::new();
+                    claim.proxy = None;
+                    claim.multisig = None;
+                    claim.multisig_status = None;
+                    claim.fee_asset_balances = Vec::new();
+                    claim.fee_asset = None;
+                    claim
+                });
+
                 State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension,
-                    claim: self.claim.clone(),
+                    claim,
                     layout: self.layout.clone(),
                 }
                 .into()
@@ -430,6 +785,8 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network,
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -439,11 +796,50 @@ impl Reducible for State {
             }
             Action::ChangeNetworkStatus(new_status) => {
                 let mut network = self.network.clone();
+                if new_status == NetworkStatus::Reconnecting {
+                    // Pending requests are dropped by the worker, they are sent again once active
+                    network.requests.clear();
+                    network.fetches_counter = 0;
+                }
                 network.status = new_status;
                 State {
                     accounts: self.accounts.clone(),
                     network,
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateConnection(event) => {
+                let mut network = self.network.clone();
+                network.connection = Some(event);
+                State {
+                    accounts: self.accounts.clone(),
+                    network,
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateCompatibility(compatibility) => {
+                let mut network = self.network.clone();
+                network.update_compatibility(compatibility);
+                State {
+                    accounts: self.accounts.clone(),
+                    network,
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -451,8 +847,8 @@ impl Reducible for State {
                 }
                 .into()
             }
-            Action::ResetNetwork(runtime, use_light_client) => {
-                let network = NetworkState::new(runtime.clone(), use_light_client);
+            Action::ResetNetwork(runtime, use_light_client, endpoints) => {
+                let network = NetworkState::new(runtime, use_light_client, endpoints);
                 let accounts: Vec<Account> =
                     LocalStorage::get(account_key(runtime.clone())).unwrap_or_else(|_| vec![]);
 
@@ -474,10 +870,15 @@ impl Reducible for State {
                 layout.balance_mode = BalanceMode::TotalBalance;
                 layout.is_onboarding = is_onboarding;
 
+                let payouts_history: PayoutsHistory =
+                    LocalStorage::get(payouts_history_key(runtime)).unwrap_or_default();
+
                 State {
                     accounts,
                     network,
                     child_bounties_raw: None,
+                    parent_bounties_raw: None,
+                    payouts_history,
                     filter,
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -492,6 +893,8 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network,
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -499,13 +902,87 @@ impl Reducible for State {
                 }
                 .into()
             }
-            Action::UpdateChildBountiesRaw(mut data) => {
+            Action::StartRequest(request_id, kind) => {
+                let mut network = self.network.clone();
+                network.requests.insert(request_id, kind.clone());
+
+                // Keep track of the requests sent on behalf of the claim
+                let claim = self.claim.clone().map(|mut claim| {
+                    if kind == RequestKind::ClaimBatches
+                        || kind == RequestKind::MultisigStatus
+                        || kind == RequestKind::TxPayload
+                        || kind == RequestKind::TxSubmission
+                    {
+                        claim.request_id = Some(request_id);
+                    }
+                    claim
+                });
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network,
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim,
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::CompleteRequest(request_id) => {
                 let mut network = self.network.clone();
+                if network.requests.remove(&request_id) == Some(RequestKind::ChildBounties) {
+                    network.fetches_counter = network.fetches_counter.saturating_sub(1);
+                }
 
-                if data.len() < 2 {
-                    network.fetches_counter -= 1;
+                State {
+                    accounts: self.accounts.clone(),
+                    network,
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
                 }
+                .into()
+            }
+            Action::FailRequest(request_id, err) => {
+                let mut network = self.network.clone();
+                // Note: requests from a previous network are no longer tracked
+                let Some(kind) = network.requests.remove(&request_id) else {
+                    return self;
+                };
+
+                if kind == RequestKind::ChildBounties {
+                    network.fetches_counter = network.fetches_counter.saturating_sub(1);
+                }
+                network.last_error = Some((kind, err.clone()));
+
+                let claim = self.claim.clone().map(|mut claim| {
+                    if claim.request_id == Some(request_id) {
+                        claim.status = ClaimStatus::Error(err.to_string());
+                    }
+                    claim
+                });
 
+                State {
+                    accounts: self.accounts.clone(),
+                    network,
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim,
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateChildBountiesRaw(mut data) => {
                 // Filter and Map the child bounties against the accounts being tracked
                 let mut accounts = self.accounts.clone();
                 for account in accounts.iter_mut() {
@@ -516,7 +993,7 @@ impl Reducible for State {
                         .into_iter()
                         .filter(|(_, cb)| {
                             let acc = AccountId32::from_str(&account.address).unwrap();
-                            acc == cb.beneficiary
+                            cb.is_beneficiary(&acc)
                         })
                         .map(|(id, _)| id)
                         .collect::<BTreeSet<u32>>();
@@ -546,8 +1023,10 @@ impl Reducible for State {
 
                 State {
                     accounts,
-                    network,
+                    network: self.network.clone(),
                     child_bounties_raw: Some(child_bounties_raw),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter,
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -555,10 +1034,127 @@ impl Reducible for State {
                 }
                 .into()
             }
+            Action::UpdateChildBounty(child_bounty) => {
+                let id = child_bounty.id;
+
+                // Link or unlink the child bounty against the accounts being tracked
+                let mut accounts = self.accounts.clone();
+                for account in accounts.iter_mut() {
+                    let acc = AccountId32::from_str(&account.address).unwrap();
+                    if child_bounty.is_beneficiary(&acc) {
+                        account.child_bounty_ids.insert(id);
+                    } else {
+                        account.child_bounty_ids.remove(&id);
+                    }
+                }
+                LocalStorage::set(self.account_key(), accounts.clone()).expect("failed to set");
+
+                let child_bounties_raw =
+                    self.child_bounties_raw.clone().map(|mut child_bounties| {
+                        child_bounties.insert(id, child_bounty);
+                        child_bounties
+                    });
+
+                State {
+                    accounts,
+                    network: self.network.clone(),
+                    child_bounties_raw,
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::RemoveChildBounty(id) => {
+                let mut accounts = self.accounts.clone();
+                for account in accounts.iter_mut() {
+                    account.child_bounty_ids.remove(&id);
+                }
+                LocalStorage::set(self.account_key(), accounts.clone()).expect("failed to set");
+
+                let child_bounties_raw =
+                    self.child_bounties_raw.clone().map(|mut child_bounties| {
+                        child_bounties.remove(&id);
+                        child_bounties
+                    });
+
+                State {
+                    accounts,
+                    network: self.network.clone(),
+                    child_bounties_raw,
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdateParentBountiesRaw(data) => State {
+                accounts: self.accounts.clone(),
+                network: self.network.clone(),
+                child_bounties_raw: self.child_bounties_raw.clone(),
+                parent_bounties_raw: Some(data),
+                payouts_history: self.payouts_history.clone(),
+                filter: self.filter.clone(),
+                extension: self.extension.clone(),
+                claim: self.claim.clone(),
+                layout: self.layout.clone(),
+            }
+            .into(),
+            Action::UpdatePayoutsHistory(payouts, from, to) => {
+                // Note: empty finalized blocks are only persisted once in a while
+                let persist =
+                    from != to || !payouts.is_empty() || to % PAYOUTS_HISTORY_CHUNK_BLOCKS == 0;
+
+                let mut payouts_history = self.payouts_history.clone();
+                payouts_history.merge(payouts, from, to, &self.following());
+
+                if persist {
+                    save_payouts_history(self.payouts_history_key(), &mut payouts_history);
+                }
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history,
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
+            Action::UpdatePayoutsHistoryLimit(block_number, limit) => {
+                let mut payouts_history = self.payouts_history.clone();
+                payouts_history.limit = Some((block_number, limit));
+                save_payouts_history(self.payouts_history_key(), &mut payouts_history);
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history,
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout: self.layout.clone(),
+                }
+                .into()
+            }
             Action::SetFilter(filter) => State {
                 accounts: self.accounts.clone(),
                 network: self.network.clone(),
                 child_bounties_raw: self.child_bounties_raw.clone(),
+                parent_bounties_raw: self.parent_bounties_raw.clone(),
+                payouts_history: self.payouts_history.clone(),
                 filter,
                 extension: self.extension.clone(),
                 claim: self.claim.clone(),
@@ -573,6 +1169,25 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
+                    filter: self.filter.clone(),
+                    extension: self.extension.clone(),
+                    claim: self.claim.clone(),
+                    layout,
+                }
+                .into()
+            }
+            Action::ToggleLayoutRpcEndpointsModal => {
+                let mut layout = self.layout.clone();
+                layout.is_rpc_endpoints_modal_visible = !layout.is_rpc_endpoints_modal_visible;
+
+                State {
+                    accounts: self.accounts.clone(),
+                    network: self.network.clone(),
+                    child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -588,6 +1203,8 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -605,6 +1222,8 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter: self.filter.clone(),
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -630,6 +1249,8 @@ impl Reducible for State {
                     accounts: self.accounts.clone(),
                     network: self.network.clone(),
                     child_bounties_raw: self.child_bounties_raw.clone(),
+                    parent_bounties_raw: self.parent_bounties_raw.clone(),
+                    payouts_history: self.payouts_history.clone(),
                     filter,
                     extension: self.extension.clone(),
                     claim: self.claim.clone(),
@@ -642,6 +1263,14 @@ impl Reducible for State {
 }
 
 impl State {
+    /// Accounts being followed
+    pub fn following(&self) -> Vec<AccountId32> {
+        self.accounts
+            .iter()
+            .map(|a| AccountId32::from_str(&a.address).unwrap())
+            .collect()
+    }
+
     pub fn account_key(&self) -> String {
         account_key(self.network.runtime)
     }
@@ -653,6 +1282,14 @@ impl State {
     pub fn onboarded_key(&self) -> String {
         onboarded_key(self.network.runtime)
     }
+
+    pub fn payouts_history_key(&self) -> String {
+        payouts_history_key(self.network.runtime)
+    }
+
+    pub fn rpc_endpoints_key(&self) -> String {
+        rpc_endpoints_key(self.network.runtime)
+    }
 }
 
 pub fn account_key(runtime: SupportedRelayRuntime) -> String {
@@ -682,4 +1319,33 @@ pub fn onboarded_key(runtime: SupportedRelayRuntime) -> String {
     )
 }
 
+/// Persist the payouts history, dropping its oldest half when it does not fit in the storage
+fn save_payouts_history(key: String, payouts_history: &mut PayoutsHistory) {
+    if let Err(e) = LocalStorage::set(&key, &*payouts_history) {
+        warn!("Payouts history could not be persisted, dropping its oldest payouts: {e:?}");
+        payouts_history.trim(payouts_history.payouts.len() / 2);
+        if let Err(e) = LocalStorage::set(&key, &*payouts_history) {
+            error!("Payouts history could not be persisted: {e:?}");
+        }
+    }
+}
+
+pub fn payouts_history_key(runtime: SupportedRelayRuntime) -> String {
+    format!(
+        "{}::{}::{}",
+        env!("CARGO_PKG_NAME"),
+        runtime.to_string().to_lowercase(),
+        PAYOUTS_HISTORY_KEY
+    )
+}
+
+pub fn rpc_endpoints_key(runtime: SupportedRelayRuntime) -> String {
+    format!(
+        "{}::{}::{}",
+        env!("CARGO_PKG_NAME"),
+        runtime.to_string().to_lowercase(),
+        RPC_ENDPOINTS_KEY
+    )
+}
+
 pub type StateContext = UseReducerHandle<State>;
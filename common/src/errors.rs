@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::string::String;
 use subxt::{
     error::{DispatchError, MetadataError, RpcError},
@@ -31,3 +32,35 @@ impl From<&str> for ClaimitError {
         ClaimitError::Other(error.into())
     }
 }
+
+/// Serializable form of ClaimitError, so it can be sent from the worker to the app
+#[derive(Error, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkerError {
+    #[error("Subxt error: {0}")]
+    SubxtError(String),
+    #[error("LightClient error: {0}")]
+    LightClientError(String),
+    #[error("Metadata error: {0}")]
+    MetadataError(String),
+    #[error("Dispatch error: {0}")]
+    DispatchError(String),
+    #[error("{0}")]
+    RpcError(String),
+    #[error("Other error: {0}")]
+    Other(String),
+}
+
+/// Convert ClaimitError to WorkerError
+impl From<ClaimitError> for WorkerError {
+    fn from(error: ClaimitError) -> Self {
+        match error {
+            ClaimitError::SubxtError(e) => WorkerError::SubxtError(e.to_string()),
+            ClaimitError::SubxtCoreError(e) => WorkerError::SubxtError(e.to_string()),
+            ClaimitError::LightClientError(e) => WorkerError::LightClientError(e.to_string()),
+            ClaimitError::MetadataError(e) => WorkerError::MetadataError(e.to_string()),
+            ClaimitError::DispatchError(e) => WorkerError::DispatchError(e.to_string()),
+            ClaimitError::RpcError(e) => WorkerError::RpcError(e.to_string()),
+            ClaimitError::Other(e) => WorkerError::Other(e),
+        }
+    }
+}
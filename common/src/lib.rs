@@ -1,3 +1,5 @@
 pub mod errors;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod runtimes;
 pub mod types;
@@ -0,0 +1,372 @@
+//! In-process stand-in for an RPC node, so the runtimes can be tested natively without a network.
+//!
+//! It answers the legacy RPC methods used by subxt from recorded fixtures: storage entries (as SCALE
+//! encoded hex), block bodies, the finalized heads streamed to subscribers, and the statuses a
+//! submitted extrinsic goes through. Fixture files are JSON and can be layered, e.g. a chain state
+//! plus the outcome of a claim.
+//!
+//! ```ignore
+//! let api = MockRpcClient::new(METADATA)
+//!     .with_fixture(include_str!("../fixtures/asset_hub.json"))
+//!     .client()
+//!     .await?;
+//! ```
+
+use crate::errors::ClaimitError;
+use futures::stream;
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use sp_crypto_hashing::blake2_256;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use subxt::{
+    backend::rpc::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClient, RpcClientT},
+    ext::codec::{Compact, Encode},
+    ext::subxt_rpcs::{Error as RpcError, UserError},
+    utils::AccountId32,
+    OnlineClient, PolkadotConfig,
+};
+
+/// Placeholder in a block body replaced by the last extrinsic submitted
+pub const SUBMITTED_EXTRINSIC: &str = "submitted";
+
+/// Recorded chain data served by the mock. All hashes, keys and values are hex encoded.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Fixture {
+    pub genesis_hash: Option<String>,
+    pub finalized_head: Option<String>,
+    pub spec_version: Option<u32>,
+    pub transaction_version: Option<u32>,
+    /// Next nonce of every account, by SS58 address
+    pub nonces: BTreeMap<String, u32>,
+    /// Storage entries at the finalized head
+    pub storage: BTreeMap<String, String>,
+    /// SCALE encoded results of runtime API calls, by function name, e.g. `TransactionPaymentApi_query_info`
+    pub runtime_calls: BTreeMap<String, String>,
+    pub blocks: BTreeMap<String, FixtureBlock>,
+    /// Blocks streamed by the heads subscriptions, which end after the last one as if the
+    /// connection dropped
+    pub finalized_heads: Vec<String>,
+    /// Statuses sent back when an extrinsic is submitted, e.g. `"ready"` or `{ "finalized": "0x.." }`
+    pub submission: Option<Vec<JsonValue>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FixtureBlock {
+    pub number: u32,
+    pub parent_hash: String,
+    pub extrinsics: Vec<String>,
+    /// Storage entries that differ from the finalized head at this block, e.g. `System::Events`,
+    /// or null if removed at this block
+    pub storage: BTreeMap<String, Option<String>>,
+    /// Whether the node discarded the state of this block, so storage queries at it fail as on
+    /// a non-archive node
+    pub pruned: bool,
+}
+
+impl FixtureBlock {
+    /// Hash of the header served for this block, as computed by subxt for the blocks streamed
+    /// by a subscription
+    fn header_hash(&self) -> String {
+        let parent_hash = hex::decode(self.parent_hash.trim_start_matches("0x"))
+            .ok()
+            .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+            .unwrap_or_default();
+        // Header { parent_hash, number, state_root, extrinsics_root, digest: [] }
+        let header = (
+            parent_hash,
+            Compact(self.number),
+            parent_hash,
+            parent_hash,
+            Vec::<u8>::new(),
+        )
+            .encode();
+        format!("0x{}", hex::encode(blake2_256(&header)))
+    }
+}
+
+impl Fixture {
+    /// Entries of `other` take precedence over the ones already present
+    fn merge(&mut self, other: Fixture) {
+        self.genesis_hash = other.genesis_hash.or(self.genesis_hash.take());
+        self.finalized_head = other.finalized_head.or(self.finalized_head.take());
+        self.spec_version = other.spec_version.or(self.spec_version);
+        self.transaction_version = other.transaction_version.or(self.transaction_version);
+        self.nonces.extend(other.nonces);
+        self.storage.extend(other.storage);
+        self.runtime_calls.extend(other.runtime_calls);
+        self.blocks.extend(other.blocks);
+        if !other.finalized_heads.is_empty() {
+            self.finalized_heads = other.finalized_heads;
+        }
+        self.submission = other.submission.or(self.submission.take());
+    }
+}
+
+pub struct MockRpcClient {
+    metadata: Vec<u8>,
+    fixture: Fixture,
+    submitted: Mutex<Option<String>>,
+}
+
+impl MockRpcClient {
+    /// Create a mock serving the given SCALE encoded metadata, e.g. the one a runtime was generated from
+    pub fn new(metadata: &[u8]) -> Self {
+        Self {
+            metadata: metadata.to_vec(),
+            fixture: Fixture::default(),
+            submitted: Mutex::new(None),
+        }
+    }
+
+    /// Layer a JSON fixture on top of the ones already loaded
+    pub fn with_fixture(mut self, json: &str) -> Self {
+        let fixture: Fixture = serde_json::from_str(json).expect("valid fixture");
+        self.fixture.merge(fixture);
+        self
+    }
+
+    /// Create an online client backed by the mock
+    pub async fn client(self) -> Result<OnlineClient<PolkadotConfig>, ClaimitError> {
+        Ok(OnlineClient::from_rpc_client(RpcClient::new(self)).await?)
+    }
+
+    fn finalized_head(&self) -> String {
+        self.fixture.finalized_head.clone().unwrap_or_default()
+    }
+
+    /// Block with the hash given, either the one it is recorded with or the hash of its header
+    fn fixture_block(&self, hash: &str) -> Option<&FixtureBlock> {
+        self.fixture.blocks.get(hash).or_else(|| {
+            self.fixture
+                .blocks
+                .values()
+                .find(|block| block.header_hash() == hash)
+        })
+    }
+
+    /// Fails if the state at the block given was discarded
+    fn check_state(&self, at: Option<&str>) -> Result<(), RpcError> {
+        match at {
+            Some(hash) if self.fixture_block(hash).is_some_and(|block| block.pruned) => {
+                Err(UserError {
+                    code: 4003,
+                    message: format!("State already discarded for {hash}"),
+                    data: None,
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Storage value at the block given, falling back to the finalized head
+    fn storage(&self, key: &str, at: Option<&str>) -> Option<String> {
+        match at
+            .and_then(|hash| self.fixture_block(hash))
+            .and_then(|block| block.storage.get(key))
+        {
+            Some(value) => value.clone(),
+            None => self.fixture.storage.get(key).cloned(),
+        }
+    }
+
+    fn keys(&self, at: Option<&str>) -> Vec<String> {
+        let mut keys: Vec<String> = self.fixture.storage.keys().cloned().collect();
+        if let Some(block) = at.and_then(|hash| self.fixture_block(hash)) {
+            keys.extend(block.storage.keys().cloned());
+            keys.retain(|key| !matches!(block.storage.get(key), Some(None)));
+        }
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    fn block(&self, hash: &str) -> Option<JsonValue> {
+        let block = self.fixture_block(hash)?;
+        let submitted = self.submitted.lock().unwrap().clone().unwrap_or_default();
+        let extrinsics: Vec<String> = block
+            .extrinsics
+            .iter()
+            .map(|extrinsic| match extrinsic.as_str() {
+                SUBMITTED_EXTRINSIC => submitted.clone(),
+                _ => extrinsic.clone(),
+            })
+            .collect();
+        Some(json!({
+            "block": {
+                "header": {
+                    "parentHash": block.parent_hash,
+                    "number": format!("0x{:x}", block.number),
+                    "stateRoot": block.parent_hash,
+                    "extrinsicsRoot": block.parent_hash,
+                    "digest": { "logs": [] }
+                },
+                "extrinsics": extrinsics
+            },
+            "justifications": null
+        }))
+    }
+
+    fn respond(&self, method: &str, params: &[JsonValue]) -> Result<JsonValue, RpcError> {
+        let param = |i: usize| params.get(i).and_then(|p| p.as_str());
+
+        match method {
+            "chain_getBlockHash" => match params.first().and_then(block_number) {
+                // Looked up by subxt to fill the gaps between the finalized heads streamed
+                Some(number) if number > 0 => Ok(json!(self
+                    .fixture
+                    .blocks
+                    .iter()
+                    .find(|(_, block)| block.number == number)
+                    .map(|(hash, _)| hash))),
+                _ => Ok(json!(self.fixture.genesis_hash)),
+            },
+            "chain_getFinalizedHead" => Ok(json!(self.finalized_head())),
+            "chain_getBlock" => Ok(json!(self.block(param(0).unwrap_or_default()))),
+            "chain_getHeader" => Ok(json!(self
+                .block(param(0).unwrap_or_default())
+                .map(|block| block["block"]["header"].clone()))),
+            "state_getRuntimeVersion" => Ok(json!({
+                "specVersion": self.fixture.spec_version.unwrap_or_default(),
+                "transactionVersion": self.fixture.transaction_version.unwrap_or_default(),
+            })),
+            "state_call" => match param(0).unwrap_or_default() {
+                // Served whatever the version asked for, as the fixtures are recorded against it
+                "Metadata_metadata_at_version" => Ok(json!(hex(&Some(self.metadata.clone())))),
+                "Metadata_metadata" => Ok(json!(hex(&self.metadata))),
+                "AccountNonceApi_account_nonce" => {
+                    let nonce = param(1)
+                        .and_then(|account| hex::decode(account.trim_start_matches("0x")).ok())
+                        .and_then(|account| <[u8; 32]>::try_from(account).ok())
+                        .and_then(|account| {
+                            self.fixture
+                                .nonces
+                                .get(&AccountId32::from(account).to_string())
+                        })
+                        .copied()
+                        .unwrap_or_default();
+                    Ok(json!(hex(&nonce)))
+                }
+                function => self
+                    .fixture
+                    .runtime_calls
+                    .get(function)
+                    .map(|result| json!(result))
+                    .ok_or(UserError::method_not_found().into()),
+            },
+            "state_getStorage" => {
+                self.check_state(param(1))?;
+                Ok(json!(self.storage(param(0).unwrap_or_default(), param(1))))
+            }
+            "state_getKeysPaged" => {
+                self.check_state(param(3))?;
+                let prefix = param(0).unwrap_or_default();
+                let count = params.get(1).and_then(|c| c.as_u64()).unwrap_or(u64::MAX);
+                let start_key = param(2).unwrap_or_default();
+                let keys: Vec<String> = self
+                    .keys(param(3))
+                    .into_iter()
+                    .filter(|key| key.starts_with(prefix) && key.as_str() > start_key)
+                    .take(count as usize)
+                    .collect();
+                Ok(json!(keys))
+            }
+            "state_queryStorageAt" => {
+                let at = param(1).map(String::from).unwrap_or(self.finalized_head());
+                self.check_state(Some(&at))?;
+                let changes: Vec<(String, Option<String>)> = params
+                    .first()
+                    .and_then(|keys| keys.as_array())
+                    .map(|keys| {
+                        keys.iter()
+                            .filter_map(|key| key.as_str())
+                            .map(|key| (key.to_string(), self.storage(key, Some(&at))))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Ok(json!([{ "block": at, "changes": changes }]))
+            }
+            _ => Err(UserError::method_not_found().into()),
+        }
+    }
+}
+
+fn hex(value: &impl Encode) -> String {
+    format!("0x{}", hex::encode(value.encode()))
+}
+
+/// Block number given either as a number or as hex
+fn block_number(value: &JsonValue) -> Option<u32> {
+    match value.as_str() {
+        Some(hex) => u32::from_str_radix(hex.trim_start_matches("0x"), 16).ok(),
+        None => value.as_u64().and_then(|number| u32::try_from(number).ok()),
+    }
+}
+
+fn params(params: Option<Box<RawValue>>) -> Vec<JsonValue> {
+    params
+        .and_then(|params| serde_json::from_str(params.get()).ok())
+        .unwrap_or_default()
+}
+
+fn raw(value: &JsonValue) -> Result<Box<RawValue>, RpcError> {
+    RawValue::from_string(value.to_string()).map_err(RpcError::Deserialization)
+}
+
+impl RpcClientT for MockRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        let response = self
+            .respond(method, &self::params(params))
+            .and_then(|value| raw(&value));
+        Box::pin(async move { response })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        _unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        let subscription = match (sub, &self.fixture.submission) {
+            ("chain_subscribeFinalizedHeads" | "chain_subscribeNewHeads", _)
+                if !self.fixture.finalized_heads.is_empty() =>
+            {
+                let headers: Vec<_> = self
+                    .fixture
+                    .finalized_heads
+                    .iter()
+                    .map(|hash| {
+                        let block = self.block(hash).expect("finalized head in fixture blocks");
+                        raw(&block["block"]["header"])
+                    })
+                    .collect();
+                Ok(RawRpcSubscription {
+                    stream: Box::pin(stream::iter(headers)),
+                    id: Some("heads".into()),
+                })
+            }
+            ("author_submitAndWatchExtrinsic", Some(statuses)) => {
+                let extrinsic = self::params(params)
+                    .first()
+                    .and_then(|extrinsic| extrinsic.as_str())
+                    .map(String::from);
+                *self.submitted.lock().unwrap() = extrinsic;
+
+                let statuses: Vec<_> = statuses.iter().map(raw).collect();
+                Ok(RawRpcSubscription {
+                    stream: Box::pin(stream::iter(statuses)),
+                    id: Some("submission".into()),
+                })
+            }
+            _ => Err(UserError::method_not_found().into()),
+        };
+        Box::pin(async move { subscription })
+    }
+}
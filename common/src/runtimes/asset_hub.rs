@@ -0,0 +1,664 @@
+/// Generates the Asset Hub implementation of a relay chain network from its subxt metadata.
+///
+/// The runtime specific bits are the `$name` of the unit struct that implements
+/// [`AssetHubRuntime`](crate::runtimes::traits::AssetHubRuntime), the path to the metadata
+/// and the name of the module under `runtime_types` where the `RuntimeCall` is defined, e.g.
+///
+/// ```ignore
+/// claimit_common::asset_hub_runtime!(
+///     PolkadotAssetHub,
+///     "artifacts/metadata/polkadot_asset_hub_metadata_small.scale",
+///     asset_hub_polkadot_runtime
+/// );
+/// ```
+#[macro_export]
+#[rustfmt::skip]
+macro_rules! asset_hub_runtime {
+    ($name:ident, $metadata_path:tt, $runtime_types:ident) => {
+        use node_runtime::{
+            child_bounties::events::{Awarded, Claimed},
+            runtime_types::{
+                bounded_collections::bounded_vec::BoundedVec, pallet_bounties::BountyStatus,
+                pallet_child_bounties::ChildBountyStatus,
+            },
+        };
+        use std::collections::BTreeMap;
+        use subxt::{
+            events::EventDetails,
+            ext::codec::{Decode, Encode},
+            storage::Storage,
+            tx::Payload,
+            utils::{AccountId32, MultiAddress},
+            Metadata, OnlineClient, PolkadotConfig,
+        };
+        use yew::platform::pinned::mpsc::UnboundedSender;
+        use $crate::errors::ClaimitError;
+        use $crate::runtimes::helpers;
+        use $crate::runtimes::support::SupportedRelayRuntime;
+        use $crate::runtimes::utils::get_child_bounty_id_from_storage_key;
+        use $crate::runtimes::utils::{
+            describe_dispatch_error, get_bounty_account_id, get_treasury_pallet_id, str,
+        };
+        use $crate::types::{
+            accounts::Balance,
+            batches::{split_in_batches, BatchMode},
+            bounties::{ParentBounties, ParentBounty, ParentBountyStatus},
+            child_bounties::{
+                ChildBounties, ChildBountiesIds, ChildBounty, ChildBountyId, ParentBountyId, Status,
+            },
+            claims::{ClaimOrigin, ClaimOutcome},
+            extensions::{SigningParams, SigningPayload},
+            fees::{FeeAsset, FeeEstimate},
+            multisigs::{Multisig, MultisigStatus},
+            payouts::{PayoutEvent, PayoutKind},
+            proxies::Proxy,
+            worker::{BlockNumber, Output, RequestId},
+        };
+
+        #[subxt::subxt(
+            runtime_metadata_path = $metadata_path,
+            derive_for_all_types = "PartialEq, Clone"
+        )]
+        mod node_runtime {}
+
+        /// Metadata the runtime code was generated from
+        const METADATA: &[u8] =
+            include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $metadata_path));
+        type Call = node_runtime::runtime_types::$runtime_types::RuntimeCall;
+        type ChildBountyCall = node_runtime::runtime_types::pallet_child_bounties::pallet::Call;
+        type UtilityCall = node_runtime::runtime_types::pallet_utility::pallet::Call;
+        type ProxyCall = node_runtime::runtime_types::pallet_proxy::pallet::Call;
+        type ProxyType = node_runtime::runtime_types::$runtime_types::ProxyType;
+        type MultisigCall = node_runtime::runtime_types::pallet_multisig::pallet::Call;
+        type MultisigEvent = node_runtime::runtime_types::pallet_multisig::pallet::Event;
+        type ProxyEvent = node_runtime::runtime_types::pallet_proxy::pallet::Event;
+        type Timepoint = node_runtime::runtime_types::pallet_multisig::Timepoint<u32>;
+        type Weight = node_runtime::runtime_types::sp_weights::weight_v2::Weight;
+
+        /// A `RuntimeCall` to be submitted as is, so every claim is built the same way whether it is
+        /// wrapped or not, including in the calls of `Proxy` and `Multisig`
+        struct RuntimeCallPayload(Call);
+
+        impl Payload for RuntimeCallPayload {
+            fn encode_call_data_to(
+                &self,
+                _metadata: &subxt::Metadata,
+                out: &mut Vec<u8>,
+            ) -> Result<(), subxt::ext::subxt_core::Error> {
+                self.0.encode_to(out);
+                Ok(())
+            }
+        }
+
+        async fn fetch_child_bounties(
+            api: &OnlineClient<PolkadotConfig>,
+            request_id: RequestId,
+            tx: UnboundedSender<Output>,
+        ) -> Result<(), ClaimitError> {
+            let mut out = ChildBounties::new();
+            let mut temp = BTreeMap::new();
+
+            // Fetch child bounties
+            let address = node_runtime::storage()
+                .child_bounties()
+                .child_bounties_iter();
+
+            let mut iter = api.storage().at_latest().await?.iter(address).await?;
+
+            while let Some(storage) = iter.next().await {
+                let storage = storage?;
+                temp.insert(
+                    get_child_bounty_id_from_storage_key(storage.key_bytes),
+                    storage.value,
+                );
+            }
+
+            // Fetch all child bounties descriptions
+            let address = node_runtime::storage()
+                .child_bounties()
+                .child_bounty_descriptions_v1_iter();
+
+            let mut iter = api.storage().at_latest().await?.iter(address).await?;
+
+            while let Some(Ok(storage)) = iter.next().await {
+                let id = get_child_bounty_id_from_storage_key(storage.key_bytes);
+
+                if let Some(cb_storage) = temp.get(&id) {
+                    let BoundedVec(description) = storage.value;
+
+                    let cb = to_child_bounty(id, cb_storage, str(description));
+                    out.insert(id, cb);
+                    if out.len() % 2 == 0 {
+                        let _ = tx.send_now(Output::ChildBounties(request_id, out));
+                        out = ChildBounties::new();
+                    }
+                }
+            }
+
+            // Send whatever is left
+            if !out.is_empty() {
+                let _ = tx.send_now(Output::ChildBounties(request_id, out));
+            }
+            //
+            return Ok(());
+        }
+
+        type ChildBountyStorage =
+            node_runtime::runtime_types::pallet_child_bounties::ChildBounty<AccountId32, u128, u32>;
+
+        fn to_child_bounty(
+            id: ChildBountyId,
+            cb_storage: &ChildBountyStorage,
+            description: String,
+        ) -> ChildBounty {
+            let (status, curator, beneficiary, unlock_at) = match &cb_storage.status {
+                ChildBountyStatus::Added => (Status::Added, None, None, None),
+                ChildBountyStatus::CuratorProposed { curator } => {
+                    (Status::CuratorProposed, Some(curator.clone()), None, None)
+                }
+                ChildBountyStatus::Active { curator } => {
+                    (Status::Active, Some(curator.clone()), None, None)
+                }
+                ChildBountyStatus::PendingPayout {
+                    curator,
+                    beneficiary,
+                    unlock_at,
+                } => (
+                    Status::Pending,
+                    Some(curator.clone()),
+                    Some(beneficiary.clone()),
+                    Some(*unlock_at),
+                ),
+            };
+
+            ChildBounty {
+                id,
+                parent_id: cb_storage.parent_bounty,
+                description,
+                value: cb_storage.value,
+                fee: cb_storage.fee,
+                status,
+                curator,
+                beneficiary,
+                beneficiary_identity: None,
+                unlock_at,
+            }
+        }
+
+        async fn fetch_parent_bounties(
+            api: &OnlineClient<PolkadotConfig>,
+        ) -> Result<ParentBounties, ClaimitError> {
+            let mut out = ParentBounties::new();
+
+            let storage = api.storage().at_latest().await?;
+
+            // Fetch parent bounties
+            let address = node_runtime::storage().bounties().bounties_iter();
+
+            let mut iter = storage.iter(address).await?;
+
+            while let Some(Ok(kv)) = iter.next().await {
+                // Note: parent and child bounty ids are both the trailing u32 of the storage key
+                let id = get_child_bounty_id_from_storage_key(kv.key_bytes);
+
+                let (status, curator) = match kv.value.status {
+                    BountyStatus::Proposed => (ParentBountyStatus::Proposed, None),
+                    BountyStatus::Approved => (ParentBountyStatus::Approved, None),
+                    BountyStatus::Funded => (ParentBountyStatus::Funded, None),
+                    BountyStatus::CuratorProposed { curator } => {
+                        (ParentBountyStatus::CuratorProposed, Some(curator))
+                    }
+                    BountyStatus::Active { curator, .. } => {
+                        (ParentBountyStatus::Active, Some(curator))
+                    }
+                    BountyStatus::PendingPayout { curator, .. } => {
+                        (ParentBountyStatus::PendingPayout, Some(curator))
+                    }
+                    BountyStatus::ApprovedWithCurator { curator } => {
+                        (ParentBountyStatus::ApprovedWithCurator, Some(curator))
+                    }
+                };
+
+                out.insert(
+                    id,
+                    ParentBounty {
+                        id,
+                        description: String::new(),
+                        value: kv.value.value,
+                        remaining_value: 0,
+                        fee: kv.value.fee,
+                        status,
+                        curator,
+                    },
+                );
+            }
+
+            // Fetch all parent bounties descriptions
+            let address = node_runtime::storage()
+                .bounties()
+                .bounty_descriptions_iter();
+
+            let mut iter = storage.iter(address).await?;
+
+            while let Some(Ok(kv)) = iter.next().await {
+                let id = get_child_bounty_id_from_storage_key(kv.key_bytes);
+
+                if let Some(bounty) = out.get_mut(&id) {
+                    let BoundedVec(description) = kv.value;
+                    bounty.description = str(description);
+                }
+            }
+
+            // The remaining value is whatever is still free in each bounty account
+            let pallet_id = get_treasury_pallet_id(&api.metadata());
+
+            for (id, bounty) in out.iter_mut() {
+                let address = node_runtime::storage()
+                    .system()
+                    .account(get_bounty_account_id(pallet_id, *id));
+
+                if let Some(result) = storage.fetch(&address).await? {
+                    bounty.remaining_value = result.data.free;
+                }
+            }
+
+            Ok(out)
+        }
+
+        async fn fetch_account_balance(
+            api: &OnlineClient<PolkadotConfig>,
+            account: AccountId32,
+        ) -> Result<Balance, ClaimitError> {
+            let address = node_runtime::storage().system().account(account.clone());
+
+            if let Some(result) = api.storage().at_latest().await?.fetch(&address).await? {
+                return Ok(Balance {
+                    free: result.data.free,
+                    reserved: result.data.reserved,
+                });
+            }
+
+            return Err(ClaimitError::Other(
+                "An unexpected error occurred, balance couldn't be retrieved.".into(),
+            ));
+        }
+
+        /// Create the call claiming a single child bounty
+        fn claim_call(parent_bounty_id: u32, child_bounty_id: u32) -> Call {
+            Call::ChildBounties(ChildBountyCall::claim_child_bounty {
+                parent_bounty_id,
+                child_bounty_id,
+            })
+        }
+
+        /// Create the call claiming the child bounties, as a `RuntimeCall`. A single call only claims
+        /// one child bounty, otherwise they are batched as the mode given.
+        fn batch_call(child_bounties_ids: &ChildBountiesIds, mode: BatchMode) -> Call {
+            let mut calls: Vec<Call> = child_bounties_ids
+                .iter()
+                .map(|(parent_bounty_id, child_bounty_id)| {
+                    claim_call(*parent_bounty_id, *child_bounty_id)
+                })
+                .collect();
+
+            match mode {
+                BatchMode::Single if calls.len() == 1 => calls.remove(0),
+                BatchMode::BatchAll => Call::Utility(UtilityCall::batch_all { calls }),
+                _ => Call::Utility(UtilityCall::force_batch { calls }),
+            }
+        }
+
+        /// Wrap the call in `Proxy::proxy` or in a multisig approval, to be dispatched from the origin given
+        fn origin_call(origin: ClaimOrigin, call: Call) -> RuntimeCallPayload {
+            RuntimeCallPayload(match origin {
+                ClaimOrigin::Signer => call,
+                ClaimOrigin::Proxy(real) => Call::Proxy(ProxyCall::proxy {
+                    real: MultiAddress::Id(real),
+                    force_proxy_type: None,
+                    call: Box::new(call),
+                }),
+                ClaimOrigin::Multisig(approval) => {
+                    let maybe_timepoint = approval.timepoint.map(|timepoint| Timepoint {
+                        height: timepoint.height,
+                        index: timepoint.index,
+                    });
+                    let (ref_time, proof_size) = approval.max_weight;
+                    let max_weight = Weight { ref_time, proof_size };
+
+                    if approval.execute {
+                        Call::Multisig(MultisigCall::as_multi {
+                            threshold: approval.threshold,
+                            other_signatories: approval.other_signatories,
+                            maybe_timepoint,
+                            call: Box::new(call),
+                            max_weight,
+                        })
+                    } else {
+                        Call::Multisig(MultisigCall::approve_as_multi {
+                            threshold: approval.threshold,
+                            other_signatories: approval.other_signatories,
+                            maybe_timepoint,
+                            call_hash: approval.call_hash,
+                            max_weight,
+                        })
+                    }
+                }
+            })
+        }
+
+        async fn fetch_proxies(
+            api: &OnlineClient<PolkadotConfig>,
+            delegate: &AccountId32,
+            reals: Vec<AccountId32>,
+        ) -> Result<Vec<Proxy>, ClaimitError> {
+            $crate::types::proxies::fetch_proxies(api, delegate, reals, |index| {
+                ProxyType::decode(&mut &[index][..])
+                    .ok()
+                    .map(|proxy_type| format!("{proxy_type:?}"))
+            })
+            .await
+        }
+
+        async fn fetch_multisig_status(
+            api: &OnlineClient<PolkadotConfig>,
+            child_bounties_ids: ChildBountiesIds,
+            mode: BatchMode,
+            multisig: &Multisig,
+        ) -> Result<MultisigStatus, ClaimitError> {
+            let call_data = batch_call(&child_bounties_ids, mode).encode();
+            $crate::types::multisigs::fetch_multisig_status(api, multisig, call_data).await
+        }
+
+        async fn split_claim(
+            api: &OnlineClient<PolkadotConfig>,
+            child_bounties_ids: ChildBountiesIds,
+            mode: BatchMode,
+        ) -> Result<Vec<ChildBountiesIds>, ClaimitError> {
+            split_in_batches(api, child_bounties_ids, mode, |parent_bounty_id, child_bounty_id| {
+                node_runtime::tx()
+                    .child_bounties()
+                    .claim_child_bounty(parent_bounty_id, child_bounty_id)
+            })
+            .await
+        }
+
+        async fn create_payload_tx(
+            api: &OnlineClient<PolkadotConfig>,
+            runtime: SupportedRelayRuntime,
+            child_bounties_ids: ChildBountiesIds,
+            mode: BatchMode,
+            origin: ClaimOrigin,
+            signer_address: String,
+            mortality_period: u64,
+            fee_asset: Option<FeeAsset>,
+        ) -> Result<(SigningPayload, SigningParams, Option<FeeEstimate>), ClaimitError> {
+            let call = origin_call(origin, batch_call(&child_bounties_ids, mode));
+            helpers::create_payload::<$name, _>(
+                api,
+                runtime,
+                &call,
+                signer_address,
+                mortality_period,
+                fee_asset,
+            )
+            .await
+        }
+
+        async fn sign_and_submit_tx(
+            api: &OnlineClient<PolkadotConfig>,
+            request_id: RequestId,
+            child_bounties_ids: ChildBountiesIds,
+            mode: BatchMode,
+            origin: ClaimOrigin,
+            signer_address: String,
+            signature: Vec<u8>,
+            params: SigningParams,
+            fee_asset: Option<FeeAsset>,
+            tx: UnboundedSender<Output>,
+        ) -> Result<Vec<ClaimOutcome>, ClaimitError> {
+            // Note: the call must be built as in `create_payload_tx`, otherwise the signature does not match
+            let call = origin_call(origin, batch_call(&child_bounties_ids, mode));
+            let tx_bytes =
+                helpers::sign_call(api, &call, signer_address, signature, params, fee_asset)
+                    .await?;
+
+            // Submit and watch transaction
+            helpers::submit_and_watch_tx::<Self>(api, tx_bytes, &child_bounties_ids, request_id, tx)
+                .await
+        }
+
+        /// Asset Hub runtime, dispatching to the functions generated above
+        pub struct $name;
+
+        impl $crate::runtimes::traits::AssetHubRuntime for $name {
+            fn compatibility(
+                api: &OnlineClient<PolkadotConfig>,
+            ) -> Result<$crate::types::compatibility::RuntimeCompatibility, ClaimitError> {
+                $crate::types::compatibility::RuntimeCompatibility::check(
+                    "Asset Hub",
+                    METADATA,
+                    &$crate::types::compatibility::ASSET_HUB_PALLETS,
+                    api,
+                )
+            }
+
+            async fn fetch_child_bounties(
+                api: &OnlineClient<PolkadotConfig>,
+                request_id: RequestId,
+                tx: UnboundedSender<Output>,
+            ) -> Result<(), ClaimitError> {
+                fetch_child_bounties(api, request_id, tx).await
+            }
+
+            async fn subscribe_child_bounties(
+                api: &OnlineClient<PolkadotConfig>,
+                request_id: RequestId,
+                tx: UnboundedSender<Output>,
+            ) -> Result<(), ClaimitError> {
+                helpers::subscribe_child_bounties::<Self>(api, request_id, tx).await
+            }
+
+            async fn fetch_parent_bounties(
+                api: &OnlineClient<PolkadotConfig>,
+            ) -> Result<ParentBounties, ClaimitError> {
+                fetch_parent_bounties(api).await
+            }
+
+            async fn fetch_payouts_history(
+                api: &OnlineClient<PolkadotConfig>,
+                request_id: RequestId,
+                tx: UnboundedSender<Output>,
+            ) -> Result<(), ClaimitError> {
+                helpers::fetch_payouts_history::<Self>(api, request_id, tx).await
+            }
+
+            async fn subscribe_payouts_history(
+                api: &OnlineClient<PolkadotConfig>,
+                request_id: RequestId,
+                from: Option<BlockNumber>,
+                tx: UnboundedSender<Output>,
+            ) -> Result<(), ClaimitError> {
+                helpers::subscribe_payouts_history::<Self>(api, request_id, from, tx).await
+            }
+
+            async fn fetch_account_balance(
+                api: &OnlineClient<PolkadotConfig>,
+                account: AccountId32,
+            ) -> Result<Balance, ClaimitError> {
+                fetch_account_balance(api, account).await
+            }
+
+            async fn fetch_proxies(
+                api: &OnlineClient<PolkadotConfig>,
+                delegate: &AccountId32,
+                reals: Vec<AccountId32>,
+            ) -> Result<Vec<Proxy>, ClaimitError> {
+                fetch_proxies(api, delegate, reals).await
+            }
+
+            async fn fetch_multisig_status(
+                api: &OnlineClient<PolkadotConfig>,
+                child_bounties_ids: ChildBountiesIds,
+                mode: BatchMode,
+                multisig: &Multisig,
+            ) -> Result<MultisigStatus, ClaimitError> {
+                fetch_multisig_status(api, child_bounties_ids, mode, multisig).await
+            }
+
+            async fn split_claim(
+                api: &OnlineClient<PolkadotConfig>,
+                child_bounties_ids: ChildBountiesIds,
+                mode: BatchMode,
+            ) -> Result<Vec<ChildBountiesIds>, ClaimitError> {
+                split_claim(api, child_bounties_ids, mode).await
+            }
+
+            async fn create_payload_tx(
+                api: &OnlineClient<PolkadotConfig>,
+                runtime: SupportedRelayRuntime,
+                child_bounties_ids: ChildBountiesIds,
+                mode: BatchMode,
+                origin: ClaimOrigin,
+                signer_address: String,
+                mortality_period: u64,
+                fee_asset: Option<FeeAsset>,
+            ) -> Result<(SigningPayload, SigningParams, Option<FeeEstimate>), ClaimitError> {
+                create_payload_tx(
+                    api,
+                    runtime,
+                    child_bounties_ids,
+                    mode,
+                    origin,
+                    signer_address,
+                    mortality_period,
+                    fee_asset,
+                )
+                .await
+            }
+
+            async fn sign_and_submit_tx(
+                api: &OnlineClient<PolkadotConfig>,
+                request_id: RequestId,
+                child_bounties_ids: ChildBountiesIds,
+                mode: BatchMode,
+                origin: ClaimOrigin,
+                signer_address: String,
+                signature: Vec<u8>,
+                params: SigningParams,
+                fee_asset: Option<FeeAsset>,
+                tx: UnboundedSender<Output>,
+            ) -> Result<Vec<ClaimOutcome>, ClaimitError> {
+                sign_and_submit_tx(
+                    api,
+                    request_id,
+                    child_bounties_ids,
+                    mode,
+                    origin,
+                    signer_address,
+                    signature,
+                    params,
+                    fee_asset,
+                    tx,
+                )
+                .await
+            }
+        }
+
+        impl $crate::runtimes::traits::AssetHubDecoder for $name {
+            type ChildBountyValue = ChildBountyStorage;
+
+            async fn fetch_child_bounties_values(
+                storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+            ) -> Result<BTreeMap<ChildBountyId, ChildBountyStorage>, ClaimitError> {
+                let mut out = BTreeMap::new();
+
+                let address = node_runtime::storage()
+                    .child_bounties()
+                    .child_bounties_iter();
+
+                let mut iter = storage.iter(address).await?;
+
+                // Note: an incomplete map would report the child bounties missing from it as removed
+                while let Some(kv) = iter.next().await {
+                    let kv = kv?;
+                    out.insert(get_child_bounty_id_from_storage_key(kv.key_bytes), kv.value);
+                }
+
+                Ok(out)
+            }
+
+            async fn fetch_child_bounty_description(
+                storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+                parent_id: ParentBountyId,
+                child_id: ChildBountyId,
+            ) -> Result<String, ClaimitError> {
+                let address = node_runtime::storage()
+                    .child_bounties()
+                    .child_bounty_descriptions_v1(parent_id, child_id);
+
+                match storage.fetch(&address).await? {
+                    Some(BoundedVec(description)) => Ok(str(description)),
+                    None => Ok(String::new()),
+                }
+            }
+
+            fn to_child_bounty(
+                id: ChildBountyId,
+                value: &ChildBountyStorage,
+                description: String,
+            ) -> Result<ChildBounty, ClaimitError> {
+                Ok(to_child_bounty(id, value, description))
+            }
+
+            fn payout_event(
+                event: &EventDetails<PolkadotConfig>,
+            ) -> Result<Option<PayoutEvent>, ClaimitError> {
+                if let Some(ev) = event.as_event::<Claimed>()? {
+                    return Ok(Some((
+                        PayoutKind::Claimed,
+                        ev.index,
+                        ev.child_index,
+                        ev.beneficiary,
+                        Some(ev.payout),
+                    )));
+                }
+                if let Some(ev) = event.as_event::<Awarded>()? {
+                    return Ok(Some((
+                        PayoutKind::Awarded,
+                        ev.index,
+                        ev.child_index,
+                        ev.beneficiary,
+                        None,
+                    )));
+                }
+                Ok(None)
+            }
+
+            fn dispatch_failure(
+                event: &EventDetails<PolkadotConfig>,
+                metadata: Metadata,
+            ) -> Result<Option<String>, ClaimitError> {
+                // Note: the event is decoded as the pallet event enum, variant index included
+                let bytes = [&[event.variant_index()][..], event.field_bytes()].concat();
+                let result = match event.pallet_name() {
+                    "Proxy" => match ProxyEvent::decode(&mut &bytes[..])
+                        .map_err(subxt::Error::from)?
+                    {
+                        ProxyEvent::ProxyExecuted { result } => result,
+                        _ => return Ok(None),
+                    },
+                    "Multisig" => match MultisigEvent::decode(&mut &bytes[..])
+                        .map_err(subxt::Error::from)?
+                    {
+                        MultisigEvent::MultisigExecuted { result, .. } => result,
+                        _ => return Ok(None),
+                    },
+                    _ => return Ok(None),
+                };
+                Ok(result
+                    .err()
+                    .map(|e| describe_dispatch_error(&e.encode(), metadata)))
+            }
+        }
+    };
+}
@@ -0,0 +1,667 @@
+use crate::errors::ClaimitError;
+use crate::runtimes::helpers;
+use crate::runtimes::support::SupportedRelayRuntime;
+use crate::runtimes::traits::{AssetHubDecoder, AssetHubRuntime, PeopleRuntime};
+use crate::runtimes::utils::{
+    describe_dispatch_result, get_bounty_account_id, get_child_bounty_id_from_storage_key,
+    get_treasury_pallet_id, str,
+};
+use crate::types::{
+    accounts::Balance,
+    batches::{split_in_batches, BatchMode},
+    bounties::{ParentBounties, ParentBounty, ParentBountyStatus},
+    child_bounties::{
+        ChildBounties, ChildBountiesIds, ChildBounty, ChildBountyId, ParentBountyId, Status,
+    },
+    claims::{ClaimOrigin, ClaimOutcome},
+    compatibility::{RuntimeCompatibility, ASSET_HUB_PALLETS, PEOPLE_PALLETS},
+    extensions::{SigningParams, SigningPayload},
+    fees::{FeeAsset, FeeEstimate},
+    multisigs::{self, Multisig, MultisigStatus},
+    payouts::{PayoutEvent, PayoutKind},
+    proxies::{self, Proxy},
+    worker::{BlockNumber, Output, RequestId},
+};
+use std::collections::BTreeMap;
+use subxt::{
+    dynamic::{At, DecodedValue, DecodedValueThunk, Value},
+    events::EventDetails,
+    ext::scale_value::{self, Primitive, ValueDef},
+    storage::Storage,
+    tx::DynamicPayload,
+    utils::AccountId32,
+    Metadata, OnlineClient, PolkadotConfig,
+};
+use yew::platform::pinned::mpsc::UnboundedSender;
+
+/// Asset Hub runtime decoded from the live metadata, used when the generated code no longer matches it.
+pub struct DynamicAssetHub;
+
+/// People runtime decoded from the live metadata, used when the generated code no longer matches it.
+pub struct DynamicPeople;
+
+impl AssetHubRuntime for DynamicAssetHub {
+    fn compatibility(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<RuntimeCompatibility, ClaimitError> {
+        Ok(RuntimeCompatibility::dynamic(
+            "Asset Hub",
+            &ASSET_HUB_PALLETS,
+            api,
+        ))
+    }
+
+    async fn fetch_child_bounties(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError> {
+        let mut out = ChildBounties::new();
+        let storage = api.storage().at_latest().await?;
+
+        let current = Self::fetch_child_bounties_values(&storage).await?;
+
+        // Fetch all child bounties descriptions
+        let address = subxt::dynamic::storage("ChildBounties", "ChildBountyDescriptionsV1", ());
+
+        let mut iter = storage.iter(address).await?;
+
+        while let Some(Ok(kv)) = iter.next().await {
+            let id = get_child_bounty_id_from_storage_key(kv.key_bytes);
+
+            if let Some(value) = current.get(&id) {
+                let description = str(bytes(&decode(&kv.value)?));
+                out.insert(id, Self::to_child_bounty(id, value, description)?);
+                if out.len() % 2 == 0 {
+                    let _ = tx.send_now(Output::ChildBounties(request_id, out));
+                    out = ChildBounties::new();
+                }
+            }
+        }
+
+        // Send whatever is left
+        if !out.is_empty() {
+            let _ = tx.send_now(Output::ChildBounties(request_id, out));
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe_child_bounties(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError> {
+        helpers::subscribe_child_bounties::<Self>(api, request_id, tx).await
+    }
+
+    async fn fetch_parent_bounties(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<ParentBounties, ClaimitError> {
+        let mut out = ParentBounties::new();
+
+        let storage = api.storage().at_latest().await?;
+
+        // Fetch parent bounties
+        let address = subxt::dynamic::storage("Bounties", "Bounties", ());
+
+        let mut iter = storage.iter(address).await?;
+
+        while let Some(Ok(kv)) = iter.next().await {
+            // Note: parent and child bounty ids are both the trailing u32 of the storage key
+            let id = get_child_bounty_id_from_storage_key(kv.key_bytes);
+            let value = decode(&kv.value)?;
+
+            let status_value = field(&value, "status")?;
+            let status = match variant_name(status_value)? {
+                "Proposed" => ParentBountyStatus::Proposed,
+                "Approved" => ParentBountyStatus::Approved,
+                "Funded" => ParentBountyStatus::Funded,
+                "CuratorProposed" => ParentBountyStatus::CuratorProposed,
+                "Active" => ParentBountyStatus::Active,
+                "PendingPayout" => ParentBountyStatus::PendingPayout,
+                "ApprovedWithCurator" => ParentBountyStatus::ApprovedWithCurator,
+                name => return Err(ClaimitError::Other(format!("Unknown bounty status {name}"))),
+            };
+
+            out.insert(
+                id,
+                ParentBounty {
+                    id,
+                    description: String::new(),
+                    value: u128_at(&value, "value")?,
+                    remaining_value: 0,
+                    fee: u128_at(&value, "fee")?,
+                    status,
+                    curator: account_at(status_value, "curator").ok(),
+                },
+            );
+        }
+
+        // Fetch all parent bounties descriptions
+        let address = subxt::dynamic::storage("Bounties", "BountyDescriptions", ());
+
+        let mut iter = storage.iter(address).await?;
+
+        while let Some(Ok(kv)) = iter.next().await {
+            let id = get_child_bounty_id_from_storage_key(kv.key_bytes);
+
+            if let Some(bounty) = out.get_mut(&id) {
+                bounty.description = str(bytes(&decode(&kv.value)?));
+            }
+        }
+
+        // The remaining value is whatever is still free in each bounty account
+        let pallet_id = get_treasury_pallet_id(&api.metadata());
+
+        for (id, bounty) in out.iter_mut() {
+            let account = get_bounty_account_id(pallet_id, *id);
+            if let Some(value) = fetch_account(&storage, &account).await? {
+                bounty.remaining_value = u128_at(field(&value, "data")?, "free")?;
+            }
+        }
+
+        Ok(out)
+    }
+
+    async fn fetch_payouts_history(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError> {
+        helpers::fetch_payouts_history::<Self>(api, request_id, tx).await
+    }
+
+    async fn subscribe_payouts_history(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        from: Option<BlockNumber>,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError> {
+        helpers::subscribe_payouts_history::<Self>(api, request_id, from, tx).await
+    }
+
+    async fn fetch_account_balance(
+        api: &OnlineClient<PolkadotConfig>,
+        account: AccountId32,
+    ) -> Result<Balance, ClaimitError> {
+        let storage = api.storage().at_latest().await?;
+
+        if let Some(value) = fetch_account(&storage, &account).await? {
+            let data = field(&value, "data")?;
+            return Ok(Balance {
+                free: u128_at(data, "free")?,
+                reserved: u128_at(data, "reserved")?,
+            });
+        }
+
+        Err(ClaimitError::Other(
+            "An unexpected error occurred, balance couldn't be retrieved.".into(),
+        ))
+    }
+
+    async fn fetch_proxies(
+        api: &OnlineClient<PolkadotConfig>,
+        delegate: &AccountId32,
+        reals: Vec<AccountId32>,
+    ) -> Result<Vec<Proxy>, ClaimitError> {
+        let metadata = api.metadata();
+        proxies::fetch_proxies(api, delegate, reals, |index| {
+            proxy_type_name(&metadata, index)
+        })
+        .await
+    }
+
+    async fn fetch_multisig_status(
+        api: &OnlineClient<PolkadotConfig>,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+        multisig: &Multisig,
+    ) -> Result<MultisigStatus, ClaimitError> {
+        let call_data = api.tx().call_data(&batch_call(child_bounties_ids, mode))?;
+        multisigs::fetch_multisig_status(api, multisig, call_data).await
+    }
+
+    async fn split_claim(
+        api: &OnlineClient<PolkadotConfig>,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+    ) -> Result<Vec<ChildBountiesIds>, ClaimitError> {
+        split_in_batches(api, child_bounties_ids, mode, claim_call).await
+    }
+
+    async fn create_payload_tx(
+        api: &OnlineClient<PolkadotConfig>,
+        runtime: SupportedRelayRuntime,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+        origin: ClaimOrigin,
+        signer_address: String,
+        mortality_period: u64,
+        fee_asset: Option<FeeAsset>,
+    ) -> Result<(SigningPayload, SigningParams, Option<FeeEstimate>), ClaimitError> {
+        let call = claim_tx(child_bounties_ids, mode, origin);
+        helpers::create_payload::<Self, _>(
+            api,
+            runtime,
+            &call,
+            signer_address,
+            mortality_period,
+            fee_asset,
+        )
+        .await
+    }
+
+    async fn sign_and_submit_tx(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+        origin: ClaimOrigin,
+        signer_address: String,
+        signature: Vec<u8>,
+        params: SigningParams,
+        fee_asset: Option<FeeAsset>,
+        tx: UnboundedSender<Output>,
+    ) -> Result<Vec<ClaimOutcome>, ClaimitError> {
+        // Note: the call must be built as in `create_payload_tx`, otherwise the signature does not match
+        let call = claim_tx(child_bounties_ids.clone(), mode, origin);
+        let tx_bytes =
+            helpers::sign_call(api, &call, signer_address, signature, params, fee_asset).await?;
+
+        helpers::submit_and_watch_tx::<Self>(api, tx_bytes, &child_bounties_ids, request_id, tx)
+            .await
+    }
+}
+
+impl AssetHubDecoder for DynamicAssetHub {
+    type ChildBountyValue = DecodedValue;
+
+    async fn fetch_child_bounties_values(
+        storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    ) -> Result<BTreeMap<ChildBountyId, DecodedValue>, ClaimitError> {
+        let mut out = BTreeMap::new();
+
+        let address = subxt::dynamic::storage("ChildBounties", "ChildBounties", ());
+
+        let mut iter = storage.iter(address).await?;
+
+        // Note: an incomplete map would report the child bounties missing from it as removed
+        while let Some(kv) = iter.next().await {
+            let kv = kv?;
+            out.insert(
+                get_child_bounty_id_from_storage_key(kv.key_bytes),
+                decode(&kv.value)?,
+            );
+        }
+
+        Ok(out)
+    }
+
+    async fn fetch_child_bounty_description(
+        storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        parent_id: ParentBountyId,
+        child_id: ChildBountyId,
+    ) -> Result<String, ClaimitError> {
+        let address = subxt::dynamic::storage(
+            "ChildBounties",
+            "ChildBountyDescriptionsV1",
+            vec![Value::u128(parent_id.into()), Value::u128(child_id.into())],
+        );
+
+        match storage.fetch(&address).await? {
+            Some(thunk) => Ok(str(bytes(&decode(&thunk)?))),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn to_child_bounty(
+        id: ChildBountyId,
+        value: &DecodedValue,
+        description: String,
+    ) -> Result<ChildBounty, ClaimitError> {
+        let status_value = field(value, "status")?;
+        let status = match variant_name(status_value)? {
+            "Added" => Status::Added,
+            "CuratorProposed" => Status::CuratorProposed,
+            "Active" => Status::Active,
+            "PendingPayout" => Status::Pending,
+            name => {
+                return Err(ClaimitError::Other(format!(
+                    "Unknown child bounty status {name}"
+                )))
+            }
+        };
+
+        Ok(ChildBounty {
+            id,
+            parent_id: u32_at(value, "parent_bounty")?,
+            description,
+            value: u128_at(value, "value")?,
+            fee: u128_at(value, "fee")?,
+            status,
+            curator: account_at(status_value, "curator").ok(),
+            beneficiary: account_at(status_value, "beneficiary").ok(),
+            beneficiary_identity: None,
+            unlock_at: u32_at(status_value, "unlock_at").ok(),
+        })
+    }
+
+    fn payout_event(
+        event: &EventDetails<PolkadotConfig>,
+    ) -> Result<Option<PayoutEvent>, ClaimitError> {
+        let kind = match (event.pallet_name(), event.variant_name()) {
+            ("ChildBounties", "Claimed") => PayoutKind::Claimed,
+            ("ChildBounties", "Awarded") => PayoutKind::Awarded,
+            _ => return Ok(None),
+        };
+
+        let value = DecodedValue {
+            value: ValueDef::Composite(event.field_values()?),
+            context: 0,
+        };
+        Ok(Some((
+            kind,
+            u32_at(&value, "index")?,
+            u32_at(&value, "child_index")?,
+            account_at(&value, "beneficiary")?,
+            u128_at(&value, "payout").ok(),
+        )))
+    }
+
+    fn dispatch_failure(
+        event: &EventDetails<PolkadotConfig>,
+        metadata: Metadata,
+    ) -> Result<Option<String>, ClaimitError> {
+        match (event.pallet_name(), event.variant_name()) {
+            ("Proxy", "ProxyExecuted") | ("Multisig", "MultisigExecuted") => {}
+            _ => return Ok(None),
+        }
+
+        // Note: the result is encoded back with its type, to decode the dispatch error it holds
+        let value = DecodedValue {
+            value: ValueDef::Composite(event.field_values()?),
+            context: 0,
+        };
+        let result = field(&value, "result")?;
+        let mut bytes = Vec::new();
+        scale_value::scale::encode_as_type(result, result.context, metadata.types(), &mut bytes)
+            .map_err(|e| ClaimitError::Other(format!("Dispatch result encoding failed: {e}")))?;
+
+        Ok(describe_dispatch_result(&bytes, metadata))
+    }
+}
+
+impl PeopleRuntime for DynamicPeople {
+    fn compatibility(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<RuntimeCompatibility, ClaimitError> {
+        Ok(RuntimeCompatibility::dynamic(
+            "People",
+            &PEOPLE_PALLETS,
+            api,
+        ))
+    }
+
+    async fn fetch_display_name(
+        api: &OnlineClient<PolkadotConfig>,
+        address: &AccountId32,
+    ) -> Result<Option<String>, ClaimitError> {
+        let storage = api.storage().at_latest().await?;
+
+        // Note: a sub identity only looks up its parent once, as in the generated runtimes
+        let mut account = address.clone();
+        let mut sub_account_name: Option<String> = None;
+        loop {
+            let address = subxt::dynamic::storage(
+                "Identity",
+                "IdentityOf",
+                vec![Value::from_bytes(&account)],
+            );
+            if let Some(thunk) = storage.fetch(&address).await? {
+                let value = decode(&thunk)?;
+                // Older runtimes store the registration together with the username in a tuple
+                let registration = match value.at("info") {
+                    Some(_) => &value,
+                    None => item(&value, 0)?,
+                };
+                let display = identity_data(field(field(registration, "info")?, "display")?);
+                return Ok(Some(match sub_account_name {
+                    Some(child) => format!("{display}/{child}"),
+                    None => display,
+                }));
+            }
+
+            if sub_account_name.is_some() {
+                return Ok(None);
+            }
+
+            let address =
+                subxt::dynamic::storage("Identity", "SuperOf", vec![Value::from_bytes(&account)]);
+            let Some(thunk) = storage.fetch(&address).await? else {
+                return Ok(None);
+            };
+            let value = decode(&thunk)?;
+            account = account_from(item(&value, 0)?)?;
+            sub_account_name = Some(identity_data(item(&value, 1)?));
+        }
+    }
+}
+
+async fn fetch_account(
+    storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    account: &AccountId32,
+) -> Result<Option<DecodedValue>, ClaimitError> {
+    let address = subxt::dynamic::storage("System", "Account", vec![Value::from_bytes(account)]);
+
+    match storage.fetch(&address).await? {
+        Some(thunk) => Ok(Some(decode(&thunk)?)),
+        None => Ok(None),
+    }
+}
+
+/// Fields of a `ChildBounties::claim_child_bounty` call
+fn claim_call_fields(parent_bounty_id: u32, child_bounty_id: u32) -> Vec<(&'static str, Value)> {
+    vec![
+        ("parent_bounty_id", Value::u128(parent_bounty_id.into())),
+        ("child_bounty_id", Value::u128(child_bounty_id.into())),
+    ]
+}
+
+/// Create a `ChildBounties::claim_child_bounty` call
+fn claim_call(parent_bounty_id: u32, child_bounty_id: u32) -> DynamicPayload {
+    subxt::dynamic::tx(
+        "ChildBounties",
+        "claim_child_bounty",
+        claim_call_fields(parent_bounty_id, child_bounty_id),
+    )
+}
+
+/// Create a `Utility::batch_all` or `Utility::force_batch` with all the `ChildBounties::claim_child_bounty`
+/// calls, as the batch mode given. A single call only claims one child bounty, otherwise they are batched.
+fn batch_call(child_bounties_ids: ChildBountiesIds, mode: BatchMode) -> DynamicPayload {
+    if let (BatchMode::Single, [(parent_bounty_id, child_bounty_id)]) =
+        (mode, &child_bounties_ids[..])
+    {
+        return claim_call(*parent_bounty_id, *child_bounty_id);
+    }
+
+    let calls = child_bounties_ids
+        .into_iter()
+        .map(|(parent_bounty_id, child_bounty_id)| {
+            Value::unnamed_variant(
+                "ChildBounties",
+                [Value::named_variant(
+                    "claim_child_bounty",
+                    claim_call_fields(parent_bounty_id, child_bounty_id),
+                )],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let batch = match mode {
+        BatchMode::BatchAll => "batch_all",
+        _ => "force_batch",
+    };
+
+    subxt::dynamic::tx(
+        "Utility",
+        batch,
+        vec![("calls", Value::unnamed_composite(calls))],
+    )
+}
+
+/// Create the transaction claiming the child bounties, wrapped in `Proxy::proxy` or in a
+/// multisig approval to be dispatched from the origin given
+fn claim_tx(
+    child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+    origin: ClaimOrigin,
+) -> DynamicPayload {
+    let call = batch_call(child_bounties_ids, mode);
+
+    match origin {
+        ClaimOrigin::Signer => call,
+        ClaimOrigin::Proxy(real) => subxt::dynamic::tx(
+            "Proxy",
+            "proxy",
+            vec![
+                (
+                    "real",
+                    Value::unnamed_variant("Id", [Value::from_bytes(real.0)]),
+                ),
+                ("force_proxy_type", Value::unnamed_variant("None", [])),
+                ("call", call.into_value()),
+            ],
+        ),
+        ClaimOrigin::Multisig(approval) => {
+            let other_signatories = approval
+                .other_signatories
+                .iter()
+                .map(|account| Value::from_bytes(account.0))
+                .collect::<Vec<_>>();
+            let maybe_timepoint = match approval.timepoint {
+                Some(timepoint) => Value::unnamed_variant(
+                    "Some",
+                    [Value::named_composite([
+                        ("height", Value::u128(timepoint.height.into())),
+                        ("index", Value::u128(timepoint.index.into())),
+                    ])],
+                ),
+                None => Value::unnamed_variant("None", []),
+            };
+            let (ref_time, proof_size) = approval.max_weight;
+            let max_weight = Value::named_composite([
+                ("ref_time", Value::u128(ref_time.into())),
+                ("proof_size", Value::u128(proof_size.into())),
+            ]);
+
+            // Note: the last approval needed executes the call, the others only register its hash
+            let (name, call) = if approval.execute {
+                ("as_multi", ("call", call.into_value()))
+            } else {
+                (
+                    "approve_as_multi",
+                    ("call_hash", Value::from_bytes(approval.call_hash)),
+                )
+            };
+
+            subxt::dynamic::tx(
+                "Multisig",
+                name,
+                vec![
+                    ("threshold", Value::u128(approval.threshold.into())),
+                    (
+                        "other_signatories",
+                        Value::unnamed_composite(other_signatories),
+                    ),
+                    ("maybe_timepoint", maybe_timepoint),
+                    call,
+                    ("max_weight", max_weight),
+                ],
+            )
+        }
+    }
+}
+
+/// Name of the `ProxyType` variant at the index given, with the enum looked up by its path in the
+/// metadata types, e.g. `asset_hub_polkadot_runtime::ProxyType`
+fn proxy_type_name(metadata: &Metadata, index: u8) -> Option<String> {
+    let proxy_type = metadata.types().types.iter().find(|ty| {
+        matches!(
+            &ty.ty.path.segments[..],
+            [runtime, name] if runtime.ends_with("_runtime") && name == "ProxyType"
+        )
+    })?;
+
+    let value =
+        scale_value::scale::decode_as_type(&mut &[index][..], proxy_type.id, metadata.types())
+            .ok()?;
+
+    variant_name(&value).ok().map(String::from)
+}
+
+fn decode(thunk: &DecodedValueThunk) -> Result<DecodedValue, ClaimitError> {
+    thunk
+        .to_value()
+        .map_err(|e| ClaimitError::Other(format!("Value decoding failed: {e}")))
+}
+
+fn field<'a>(value: &'a DecodedValue, name: &str) -> Result<&'a DecodedValue, ClaimitError> {
+    value
+        .at(name)
+        .ok_or_else(|| ClaimitError::Other(format!("Missing field {name}")))
+}
+
+fn item(value: &DecodedValue, index: usize) -> Result<&DecodedValue, ClaimitError> {
+    value
+        .at(index)
+        .ok_or_else(|| ClaimitError::Other(format!("Missing item {index}")))
+}
+
+fn u128_at(value: &DecodedValue, name: &str) -> Result<u128, ClaimitError> {
+    field(value, name)?
+        .as_u128()
+        .ok_or_else(|| ClaimitError::Other(format!("Field {name} is not a number")))
+}
+
+fn u32_at(value: &DecodedValue, name: &str) -> Result<u32, ClaimitError> {
+    u32::try_from(u128_at(value, name)?)
+        .map_err(|_| ClaimitError::Other(format!("Field {name} out of range")))
+}
+
+fn account_at(value: &DecodedValue, name: &str) -> Result<AccountId32, ClaimitError> {
+    account_from(field(value, name)?)
+}
+
+fn account_from(value: &DecodedValue) -> Result<AccountId32, ClaimitError> {
+    let bytes: [u8; 32] = bytes(value)
+        .try_into()
+        .map_err(|_| ClaimitError::Other("Invalid account".into()))?;
+    Ok(AccountId32::from(bytes))
+}
+
+fn variant_name(value: &DecodedValue) -> Result<&str, ClaimitError> {
+    match &value.value {
+        ValueDef::Variant(variant) => Ok(variant.name.as_str()),
+        _ => Err(ClaimitError::Other("Value is not a variant".into())),
+    }
+}
+
+/// Collect all the bytes of a value, however nested they are (e.g. `BoundedVec<u8>` or `AccountId32`)
+fn bytes(value: &DecodedValue) -> Vec<u8> {
+    match &value.value {
+        ValueDef::Primitive(Primitive::U128(n)) => vec![*n as u8],
+        ValueDef::Composite(composite) => composite.values().flat_map(bytes).collect(),
+        ValueDef::Variant(variant) => variant.values.values().flat_map(bytes).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Identity `Data` is a variant of raw bytes, e.g. `Raw5([u8; 5])`
+fn identity_data(value: &DecodedValue) -> String {
+    match variant_name(value) {
+        Ok(name) if name.starts_with("Raw") => str(bytes(value)),
+        _ => "???".to_string(),
+    }
+}
@@ -0,0 +1,456 @@
+use crate::errors::ClaimitError;
+use crate::runtimes::support::SupportedRelayRuntime;
+use crate::runtimes::traits::{AssetHubDecoder, AssetHubRuntime};
+use crate::runtimes::utils::describe_dispatch_error;
+use crate::types::{
+    child_bounties::{ChildBountiesIds, ChildBountyId},
+    claims::{ClaimOutcome, ClaimResult, TxProgress, TxStage},
+    extensions::{Mortality, SigningParams, SigningPayload, TransactionExtensions},
+    fees::{estimate_fee, fetch_fee_asset_balance, FeeAsset, FeeEstimate},
+    payouts::{
+        Payout, Payouts, PayoutsHistoryLimit, PAYOUTS_HISTORY_CHUNK_BLOCKS,
+        PAYOUTS_HISTORY_MAX_BLOCKS,
+    },
+    worker::{BlockNumber, Output, RequestId},
+};
+use log::{error, info, warn};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use subxt::{
+    blocks::Block,
+    events::Phase,
+    ext::codec::Decode,
+    tx::{Payload, SubmittableTransaction, TxStatus},
+    utils::{AccountId32, MultiSignature},
+    OnlineClient, PolkadotConfig,
+};
+use yew::platform::pinned::mpsc::UnboundedSender;
+
+/// Watch child bounties changes on every finalized block, sending them over `tx`
+pub async fn subscribe_child_bounties<D: AssetHubDecoder>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
+) -> Result<(), ClaimitError> {
+    let mut blocks_sub = api.blocks().subscribe_finalized().await?;
+
+    // Note: the first finalized block only sets the reference, since all child bounties are fetched separately
+    let mut previous: Option<BTreeMap<ChildBountyId, D::ChildBountyValue>> = None;
+
+    while let Some(result) = blocks_sub.next().await {
+        let block = result?;
+        let storage = block.storage();
+
+        // Child bounties that could not all be read are skipped, diffing the next block against
+        // the last complete ones instead
+        let current = match D::fetch_child_bounties_values(&storage).await {
+            Ok(current) => current,
+            Err(e) => {
+                warn!(
+                    "Child bounties changes at block {} skipped: {e:?}",
+                    block.number()
+                );
+                continue;
+            }
+        };
+
+        if let Some(previous) = previous {
+            // Added or updated child bounties
+            for (id, value) in current.iter() {
+                let is_new = !previous.contains_key(id);
+                if !is_new && previous.get(id) == Some(value) {
+                    continue;
+                }
+
+                let mut cb = D::to_child_bounty(*id, value, String::new())?;
+                cb.description =
+                    D::fetch_child_bounty_description(&storage, cb.parent_id, *id).await?;
+
+                if is_new {
+                    let _ = tx.send_now(Output::ChildBountyAdded(request_id, cb));
+                } else {
+                    let _ = tx.send_now(Output::ChildBountyUpdated(request_id, cb));
+                }
+            }
+
+            // Claimed or canceled child bounties
+            for id in previous.keys() {
+                if !current.contains_key(id) {
+                    let _ = tx.send_now(Output::ChildBountyRemoved(request_id, *id));
+                }
+            }
+        }
+
+        previous = Some(current);
+    }
+
+    Err(ClaimitError::Other(
+        "Finalized blocks subscription ended".into(),
+    ))
+}
+
+/// Index child bounties payouts from the last finalized blocks, sending them over `tx`
+pub async fn fetch_payouts_history<D: AssetHubDecoder>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
+) -> Result<(), ClaimitError> {
+    let block = api.blocks().at_latest().await?;
+    index_payouts_back::<D>(api, block, None, request_id, &tx).await
+}
+
+/// Index child bounties payouts from finalized blocks, sending them over `tx`. The blocks
+/// finalized since `from` are indexed first, if any.
+pub async fn subscribe_payouts_history<D: AssetHubDecoder>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    from: Option<BlockNumber>,
+    tx: UnboundedSender<Output>,
+) -> Result<(), ClaimitError> {
+    let mut blocks_sub = api.blocks().subscribe_finalized().await?;
+    let mut is_first_block = true;
+
+    while let Some(result) = blocks_sub.next().await {
+        let block = result?;
+
+        // Catch up with the finalized blocks since the last indexed block
+        if is_first_block {
+            is_first_block = false;
+            if let Some(from) = from {
+                index_payouts_back::<D>(api, block, Some(from), request_id, &tx).await?;
+                continue;
+            }
+        }
+
+        let block_number = block.number();
+        let payouts = fetch_payouts::<D>(&block).await?;
+        let _ = tx.send_now(Output::PayoutsHistory(
+            request_id,
+            payouts,
+            block_number,
+            block_number,
+        ));
+    }
+
+    Err(ClaimitError::Other(
+        "Finalized blocks subscription ended".into(),
+    ))
+}
+
+/// Index the payouts of the block given and of its ancestors newer than `until`, walking back the
+/// chain by parent hash so it works with any backend. The walk stops at
+/// `PAYOUTS_HISTORY_MAX_BLOCKS` or at the first block whose events are no longer available,
+/// which is reported as the limit of the blocks indexed.
+async fn index_payouts_back<D: AssetHubDecoder>(
+    api: &OnlineClient<PolkadotConfig>,
+    block: Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    until: Option<BlockNumber>,
+    request_id: RequestId,
+    tx: &UnboundedSender<Output>,
+) -> Result<(), ClaimitError> {
+    let head = block.number();
+    let max_until = head.saturating_sub(PAYOUTS_HISTORY_MAX_BLOCKS);
+    let oldest = until.unwrap_or_default().max(max_until) + 1;
+    if oldest > head {
+        return Ok(());
+    }
+
+    // Blocks are indexed from the newest one, so progress can be sent (and persisted) along the way
+    let mut block = block;
+    let mut out = Payouts::new();
+    let mut to = head;
+    loop {
+        let number = block.number();
+        match fetch_payouts::<D>(&block).await {
+            Ok(mut payouts) => out.append(&mut payouts),
+            Err(e) => {
+                // Note: only archive nodes keep the state of every block
+                warn!("Payouts history stopped at block {number}: {e:?}");
+                if number < to {
+                    let _ = tx.send_now(Output::PayoutsHistory(request_id, out, number + 1, to));
+                }
+                let _ = tx.send_now(Output::PayoutsHistoryLimit(
+                    request_id,
+                    number + 1,
+                    PayoutsHistoryLimit::Pruned,
+                ));
+                return Ok(());
+            }
+        }
+
+        if number <= oldest {
+            let _ = tx.send_now(Output::PayoutsHistory(request_id, out, number, to));
+            if until.is_none_or(|until| until < max_until) && max_until > 0 {
+                let _ = tx.send_now(Output::PayoutsHistoryLimit(
+                    request_id,
+                    number,
+                    PayoutsHistoryLimit::MaxBlocks,
+                ));
+            }
+            return Ok(());
+        }
+
+        if (to - number + 1) % PAYOUTS_HISTORY_CHUNK_BLOCKS == 0 {
+            let _ = tx.send_now(Output::PayoutsHistory(request_id, out, number, to));
+            out = Payouts::new();
+            to = number - 1;
+        }
+
+        block = api.blocks().at(block.header().parent_hash).await?;
+    }
+}
+
+/// Collect child bounties `Awarded` and `Claimed` events emitted in the block
+async fn fetch_payouts<D: AssetHubDecoder>(
+    block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> Result<Payouts, ClaimitError> {
+    let mut out = Payouts::new();
+    let mut extrinsics = None;
+
+    let events = block.events().await?;
+
+    for event in events.iter() {
+        let event = event?;
+
+        let Some((kind, parent_id, child_id, beneficiary, amount)) = D::payout_event(&event)?
+        else {
+            continue;
+        };
+
+        // Note: block extrinsics are only fetched if there is an event of interest
+        let extrinsic_hash = match event.phase() {
+            Phase::ApplyExtrinsic(index) => {
+                if extrinsics.is_none() {
+                    extrinsics = Some(block.extrinsics().await?);
+                }
+                extrinsics
+                    .as_ref()
+                    .and_then(|exts| exts.iter().nth(index as usize))
+                    .map(|ext| format!("{:?}", ext.hash()))
+                    .unwrap_or_default()
+            }
+            _ => String::new(),
+        };
+
+        out.push(Payout {
+            kind,
+            parent_id,
+            child_id,
+            beneficiary,
+            amount,
+            block_number: block.number(),
+            extrinsic_hash,
+        });
+    }
+
+    Ok(out)
+}
+
+/// Create the payload of the call to be signed, along with the params it is signed with and the
+/// fee it is expected to be charged, if it could be estimated
+pub async fn create_payload<R: AssetHubRuntime, P: Payload>(
+    api: &OnlineClient<PolkadotConfig>,
+    runtime: SupportedRelayRuntime,
+    call: &P,
+    signer_address: String,
+    mortality_period: u64,
+    fee_asset: Option<FeeAsset>,
+) -> Result<(SigningPayload, SigningParams, Option<FeeEstimate>), ClaimitError> {
+    let account_id = AccountId32::from_str(&signer_address).unwrap();
+    let account_nonce = api.tx().account_nonce(&account_id).await?;
+    let mortality = Mortality::from_latest_finalized(api, mortality_period).await?;
+
+    let Ok(payload) = SigningPayload::new(
+        api,
+        runtime,
+        call,
+        account_nonce,
+        signer_address,
+        &mortality,
+        fee_asset,
+    )
+    .await
+    else {
+        return Err(ClaimitError::Other("Payload creation failed".to_string()));
+    };
+
+    // Accounts not found on chain have no balance to pay the fee with
+    let free_balance = match fee_asset {
+        Some(asset) => fetch_fee_asset_balance(api, asset, &account_id).await,
+        None => R::fetch_account_balance(api, account_id.clone())
+            .await
+            .map(|balance| balance.free),
+    }
+    .unwrap_or_default();
+
+    let fee = estimate_fee(
+        api,
+        call,
+        &account_id,
+        account_nonce,
+        &mortality,
+        fee_asset,
+        free_balance,
+    )
+    .await
+    .inspect_err(|e| warn!("Fee estimation failed: {e:?}"))
+    .ok();
+
+    let params = SigningParams {
+        mortality,
+        account_nonce,
+        metadata_hash: payload.metadata_hash,
+    };
+
+    Ok((payload, params, fee))
+}
+
+/// Apply the signature to the call, returning the encoded signed extrinsic
+pub async fn sign_call<P: Payload>(
+    api: &OnlineClient<PolkadotConfig>,
+    call: &P,
+    signer_address: String,
+    signature: Vec<u8>,
+    params: SigningParams,
+    fee_asset: Option<FeeAsset>,
+) -> Result<Vec<u8>, ClaimitError> {
+    let account_id = AccountId32::from_str(&signer_address).unwrap();
+
+    let Ok(multi_signature) = MultiSignature::decode(&mut &signature[..]) else {
+        return Err(ClaimitError::Other(
+            "MultiSignature decoding failed".to_string(),
+        ));
+    };
+
+    // Note: the extensions are encoded from the same params as in `create_payload`, so the
+    // signature matches
+    let call_data = api.tx().call_data(call)?;
+    let Ok(extensions) = TransactionExtensions::new(
+        api,
+        params.account_nonce,
+        &params.mortality,
+        params.metadata_hash,
+        fee_asset,
+    ) else {
+        return Err(ClaimitError::Other(
+            "Transaction extensions encoding failed".to_string(),
+        ));
+    };
+
+    // Apply the signature
+    let signed_extrinsic = SubmittableTransaction::from_bytes(
+        api.clone(),
+        extensions.signed_extrinsic(&call_data, &account_id, &multi_signature),
+    );
+
+    // check the TX validity (to debug in the js console if the extrinsic would work)
+    let dry_res = signed_extrinsic.validate().await;
+    info!("__dry_res: {:?}", dry_res);
+
+    Ok(signed_extrinsic.into_encoded())
+}
+
+/// Submit the signed extrinsic and watch it until finalized, returning the outcome of every child
+/// bounty claimed. Every stage the transaction reaches is sent as [`Output::TxProgress`].
+pub async fn submit_and_watch_tx<D: AssetHubDecoder>(
+    api: &OnlineClient<PolkadotConfig>,
+    tx_bytes: Vec<u8>,
+    child_bounties_ids: &ChildBountiesIds,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
+) -> Result<Vec<ClaimOutcome>, ClaimitError> {
+    // Results of the batch items, in the order the claims were submitted
+    let mut items = Vec::new();
+
+    let extrinsic = SubmittableTransaction::from_bytes(api.clone(), tx_bytes);
+
+    let mut tx_progress = extrinsic.submit_and_watch().await?;
+    let extrinsic_hash = tx_progress.extrinsic_hash();
+
+    while let Some(status) = tx_progress.next().await {
+        let progress = match status? {
+            TxStatus::Validated => TxProgress::new(TxStage::Validated, extrinsic_hash),
+            TxStatus::Broadcasted { .. } => TxProgress::new(TxStage::Broadcast, extrinsic_hash),
+            TxStatus::InBestBlock(in_block) => {
+                TxProgress::in_block(
+                    api,
+                    TxStage::InBestBlock,
+                    extrinsic_hash,
+                    in_block.block_hash(),
+                )
+                .await?
+            }
+            TxStatus::InFinalizedBlock(in_block) => {
+                let progress = TxProgress::in_block(
+                    api,
+                    TxStage::Finalized,
+                    extrinsic_hash,
+                    in_block.block_hash(),
+                )
+                .await?;
+                let block_number = progress.block_number();
+                let _ = tx.send_now(Output::TxProgress(request_id, progress));
+
+                let tx_events = in_block.fetch_events().await?;
+
+                // Iterate over events to retrieve the outcome of every claim
+                for event in tx_events.iter() {
+                    let event = event?;
+                    match (event.pallet_name(), event.variant_name()) {
+                        ("Utility", "ItemCompleted") => items.push(ClaimResult::Claimed),
+                        ("Utility", "ItemFailed") => {
+                            let reason =
+                                describe_dispatch_error(event.field_bytes(), api.metadata());
+                            items.push(ClaimResult::Failed(reason));
+                        }
+                        ("System", "ExtrinsicSuccess") => {
+                            return Ok(ClaimOutcome::from_items(child_bounties_ids, items))
+                        }
+                        ("System", "ExtrinsicFailed") => {
+                            // Note: the dispatch error is the first field, followed by the dispatch info
+                            let reason =
+                                describe_dispatch_error(event.field_bytes(), api.metadata());
+                            error!(
+                                "ExtrinsicFailed at block {block_number} extrinsic {:?}: {reason}",
+                                tx_events.extrinsic_hash()
+                            );
+                            return Ok(ClaimOutcome::all_failed(child_bounties_ids, reason));
+                        }
+                        (pallet, variant) => {
+                            // Note: the call wrapped may fail, or wait for other approvals, even if the extrinsic succeeds
+                            let dispatch_failure = D::dispatch_failure(&event, api.metadata())?;
+                            if let Some(outcomes) = ClaimOutcome::from_origin_event(
+                                pallet,
+                                variant,
+                                dispatch_failure,
+                                child_bounties_ids,
+                            ) {
+                                info!(
+                                    "{pallet}::{variant} at block {block_number} extrinsic {:?}",
+                                    tx_events.extrinsic_hash()
+                                );
+                                return Ok(outcomes);
+                            }
+                        }
+                    }
+                }
+
+                return Err(ClaimitError::Other(
+                    "An unexpected error occurred =/".into(),
+                ));
+            }
+            TxStatus::Error { message } => {
+                return Err(ClaimitError::Other(format!("TxStatus: {message:?}")))
+            }
+            TxStatus::Invalid { message } => {
+                return Err(ClaimitError::Other(format!("TxStatus: {message:?}")))
+            }
+            TxStatus::Dropped { message } => {
+                return Err(ClaimitError::Other(format!("TxStatus: {message:?}")))
+            }
+            _ => continue,
+        };
+        let _ = tx.send_now(Output::TxProgress(request_id, progress));
+    }
+    Err(ClaimitError::Other("TxStatus not available".into()))
+}
@@ -1,4 +1,9 @@
 #![allow(clippy::all)]
 
+pub mod asset_hub;
+pub mod dynamic;
+pub mod helpers;
+pub mod people;
 pub mod support;
+pub mod traits;
 pub mod utils;
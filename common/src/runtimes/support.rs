@@ -23,6 +23,9 @@ pub enum SupportedRelayRuntime {
     Polkadot,
     Kusama,
     Paseo,
+    Westend,
+    /// A local dev chain, e.g. spawned with zombienet, whose runtime is read from the live metadata
+    Local,
 }
 
 impl SupportedRelayRuntime {
@@ -31,6 +34,8 @@ impl SupportedRelayRuntime {
             Self::Polkadot => 0,
             Self::Kusama => 2,
             Self::Paseo => 0,
+            Self::Westend => 42,
+            Self::Local => 42,
         }
     }
 
@@ -39,6 +44,8 @@ impl SupportedRelayRuntime {
             Self::Polkadot => "wss://polkadot.rpc.turboflakes.io:443",
             Self::Kusama => "wss://kusama.rpc.turboflakes.io:443",
             Self::Paseo => "wss://paseo.rpc.turboflakes.io:443",
+            Self::Westend => "wss://westend-rpc.polkadot.io",
+            Self::Local => "ws://127.0.0.1:9944",
         }
     }
 
@@ -47,6 +54,8 @@ impl SupportedRelayRuntime {
             Self::Polkadot => "wss://people-polkadot.rpc.turboflakes.io:443",
             Self::Kusama => "wss://people-kusama.rpc.turboflakes.io:443",
             Self::Paseo => "wss://people-paseo.rpc.turboflakes.io:443",
+            Self::Westend => "wss://westend-people-rpc.polkadot.io",
+            Self::Local => "ws://127.0.0.1:9946",
         }
     }
 
@@ -55,38 +64,137 @@ impl SupportedRelayRuntime {
             Self::Polkadot => "wss://asset-hub-polkadot.rpc.turboflakes.io:443",
             Self::Kusama => "wss://asset-hub-kusama.rpc.turboflakes.io:443",
             Self::Paseo => "wss://asset-hub-paseo.rpc.turboflakes.io:443",
+            Self::Westend => "wss://westend-asset-hub-rpc.polkadot.io",
+            Self::Local => "ws://127.0.0.1:9945",
         }
     }
 
-    pub fn chain_specs(&self) -> &str {
+    /// RPC endpoints tried in order when connecting to the relay chain
+    pub fn rpc_urls(&self) -> Vec<&'static str> {
         match &self {
-            Self::Polkadot => POLKADOT_SPEC,
-            Self::Kusama => KUSAMA_SPEC,
-            Self::Paseo => PASEO_SPEC,
+            Self::Polkadot => vec![
+                self.default_rpc_url(),
+                "wss://rpc.ibp.network/polkadot",
+                "wss://polkadot.dotters.network",
+                "wss://rpc.polkadot.io",
+            ],
+            Self::Kusama => vec![
+                self.default_rpc_url(),
+                "wss://rpc.ibp.network/kusama",
+                "wss://kusama.dotters.network",
+                "wss://kusama-rpc.polkadot.io",
+            ],
+            Self::Paseo => vec![
+                self.default_rpc_url(),
+                "wss://rpc.ibp.network/paseo",
+                "wss://paseo.dotters.network",
+            ],
+            Self::Westend => vec![
+                self.default_rpc_url(),
+                "wss://rpc.ibp.network/westend",
+                "wss://westend.dotters.network",
+            ],
+            Self::Local => vec![self.default_rpc_url()],
         }
     }
 
-    pub fn chain_specs_people(&self) -> &str {
+    /// RPC endpoints tried in order when connecting to the people chain
+    pub fn people_rpc_urls(&self) -> Vec<&'static str> {
         match &self {
-            Self::Polkadot => POLKADOT_PEOPLE_SPEC,
-            Self::Kusama => KUSAMA_PEOPLE_SPEC,
-            Self::Paseo => PASEO_PEOPLE_SPEC,
+            Self::Polkadot => vec![
+                self.default_people_rpc_url(),
+                "wss://sys.ibp.network/people-polkadot",
+                "wss://people-polkadot.dotters.network",
+                "wss://polkadot-people-rpc.polkadot.io",
+            ],
+            Self::Kusama => vec![
+                self.default_people_rpc_url(),
+                "wss://sys.ibp.network/people-kusama",
+                "wss://people-kusama.dotters.network",
+                "wss://kusama-people-rpc.polkadot.io",
+            ],
+            Self::Paseo => vec![
+                self.default_people_rpc_url(),
+                "wss://sys.ibp.network/people-paseo",
+                "wss://people-paseo.dotters.network",
+            ],
+            Self::Westend => vec![
+                self.default_people_rpc_url(),
+                "wss://sys.ibp.network/people-westend",
+                "wss://people-westend.dotters.network",
+            ],
+            Self::Local => vec![self.default_people_rpc_url()],
         }
     }
 
-    pub fn chain_specs_asset_hub(&self) -> &str {
+    /// RPC endpoints tried in order when connecting to the asset hub chain
+    pub fn asset_hub_rpc_urls(&self) -> Vec<&'static str> {
         match &self {
-            Self::Polkadot => POLKADOT_ASSET_HUB_SPEC,
-            Self::Kusama => KUSAMA_ASSET_HUB_SPEC,
-            Self::Paseo => PASEO_ASSET_HUB_SPEC,
+            Self::Polkadot => vec![
+                self.default_asset_hub_rpc_url(),
+                "wss://sys.ibp.network/asset-hub-polkadot",
+                "wss://asset-hub-polkadot.dotters.network",
+                "wss://polkadot-asset-hub-rpc.polkadot.io",
+            ],
+            Self::Kusama => vec![
+                self.default_asset_hub_rpc_url(),
+                "wss://sys.ibp.network/asset-hub-kusama",
+                "wss://asset-hub-kusama.dotters.network",
+                "wss://kusama-asset-hub-rpc.polkadot.io",
+            ],
+            Self::Paseo => vec![
+                self.default_asset_hub_rpc_url(),
+                "wss://sys.ibp.network/asset-hub-paseo",
+                "wss://asset-hub-paseo.dotters.network",
+            ],
+            Self::Westend => vec![
+                self.default_asset_hub_rpc_url(),
+                "wss://sys.ibp.network/asset-hub-westend",
+                "wss://asset-hub-westend.dotters.network",
+            ],
+            Self::Local => vec![self.default_asset_hub_rpc_url()],
         }
     }
 
+    pub fn chain_specs(&self) -> Option<&str> {
+        match &self {
+            Self::Polkadot => Some(POLKADOT_SPEC),
+            Self::Kusama => Some(KUSAMA_SPEC),
+            Self::Paseo => Some(PASEO_SPEC),
+            Self::Westend | Self::Local => None,
+        }
+    }
+
+    pub fn chain_specs_people(&self) -> Option<&str> {
+        match &self {
+            Self::Polkadot => Some(POLKADOT_PEOPLE_SPEC),
+            Self::Kusama => Some(KUSAMA_PEOPLE_SPEC),
+            Self::Paseo => Some(PASEO_PEOPLE_SPEC),
+            Self::Westend | Self::Local => None,
+        }
+    }
+
+    pub fn chain_specs_asset_hub(&self) -> Option<&str> {
+        match &self {
+            Self::Polkadot => Some(POLKADOT_ASSET_HUB_SPEC),
+            Self::Kusama => Some(KUSAMA_ASSET_HUB_SPEC),
+            Self::Paseo => Some(PASEO_ASSET_HUB_SPEC),
+            Self::Westend | Self::Local => None,
+        }
+    }
+
+    /// Light client requires the chain specs of all chains to be bundled with the app
+    pub fn supports_light_client(&self) -> bool {
+        self.chain_specs().is_some()
+    }
+
     pub fn unit(&self) -> &'static str {
         match &self {
             Self::Polkadot => "DOT",
             Self::Kusama => "KSM",
             Self::Paseo => "PAS",
+            Self::Westend => "WND",
+            Self::Local => "UNIT",
         }
     }
 
@@ -95,12 +203,33 @@ impl SupportedRelayRuntime {
             Self::Polkadot => 10,
             Self::Kusama => 12,
             Self::Paseo => 10,
+            Self::Westend => 12,
+            Self::Local => 12,
+        }
+    }
+
+    /// Identicon theme wallets render the addresses of the network with
+    pub fn identicon(&self) -> &'static str {
+        match &self {
+            Self::Polkadot | Self::Kusama | Self::Paseo => "polkadot",
+            Self::Westend | Self::Local => "substrate",
         }
     }
 
     pub fn class(&self) -> String {
         self.to_string().to_lowercase()
     }
+
+    /// Default block explorer of the Asset Hub chain, where claims are submitted to
+    pub fn default_asset_hub_explorer_url(&self) -> Option<&'static str> {
+        match &self {
+            Self::Polkadot => Some("https://assethub-polkadot.subscan.io"),
+            Self::Kusama => Some("https://assethub-kusama.subscan.io"),
+            Self::Paseo => Some("https://assethub-paseo.subscan.io"),
+            Self::Westend => Some("https://assethub-westend.subscan.io"),
+            Self::Local => None,
+        }
+    }
 }
 
 impl Default for SupportedRelayRuntime {
@@ -121,6 +250,11 @@ impl From<AttrValue> for SupportedRelayRuntime {
             "Paseo" => Self::Paseo,
             "paseo" => Self::Paseo,
             "PAS" => Self::Paseo,
+            "Westend" => Self::Westend,
+            "westend" => Self::Westend,
+            "WND" => Self::Westend,
+            "Local" => Self::Local,
+            "local" => Self::Local,
             _ => unimplemented!("Chain prefix not supported"),
         }
     }
@@ -138,6 +272,11 @@ impl From<String> for SupportedRelayRuntime {
             "Paseo" => Self::Paseo,
             "paseo" => Self::Paseo,
             "PAS" => Self::Paseo,
+            "Westend" => Self::Westend,
+            "westend" => Self::Westend,
+            "WND" => Self::Westend,
+            "Local" => Self::Local,
+            "local" => Self::Local,
             _ => unimplemented!("Chain prefix not supported"),
         }
     }
@@ -159,6 +298,8 @@ impl std::fmt::Display for SupportedRelayRuntime {
             Self::Polkadot => write!(f, "Polkadot"),
             Self::Kusama => write!(f, "Kusama"),
             Self::Paseo => write!(f, "Paseo"),
+            Self::Westend => write!(f, "Westend"),
+            Self::Local => write!(f, "Local"),
         }
     }
 }
@@ -0,0 +1,178 @@
+use crate::errors::ClaimitError;
+use crate::runtimes::support::SupportedRelayRuntime;
+use crate::types::{
+    accounts::Balance,
+    batches::BatchMode,
+    bounties::ParentBounties,
+    child_bounties::{ChildBountiesIds, ChildBounty, ChildBountyId, ParentBountyId},
+    claims::{ClaimOrigin, ClaimOutcome},
+    compatibility::RuntimeCompatibility,
+    extensions::{SigningParams, SigningPayload},
+    fees::{FeeAsset, FeeEstimate},
+    multisigs::{Multisig, MultisigStatus},
+    payouts::PayoutEvent,
+    proxies::Proxy,
+    worker::{BlockNumber, Output, RequestId},
+};
+use std::collections::BTreeMap;
+use subxt::{
+    events::EventDetails, storage::Storage, utils::AccountId32, Metadata, OnlineClient,
+    PolkadotConfig,
+};
+use yew::platform::pinned::mpsc::UnboundedSender;
+
+// Note: futures are polled by the single threaded wasm executor, so there is no need
+// for them to be `Send`.
+
+/// Calls served by the Asset Hub chain of a network, implemented via [`asset_hub_runtime!`](crate::asset_hub_runtime).
+#[allow(async_fn_in_trait)]
+pub trait AssetHubRuntime {
+    /// Compare the metadata the runtime was generated from with the live one
+    fn compatibility(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<RuntimeCompatibility, ClaimitError>;
+
+    /// Fetch all child bounties, sending them over `tx` in chunks
+    async fn fetch_child_bounties(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError>;
+
+    /// Watch child bounties changes on every finalized block, sending them over `tx`
+    async fn subscribe_child_bounties(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError>;
+
+    async fn fetch_parent_bounties(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<ParentBounties, ClaimitError>;
+
+    /// Index child bounties payouts from the last finalized blocks, sending them over `tx`
+    async fn fetch_payouts_history(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError>;
+
+    /// Index child bounties payouts from finalized blocks, sending them over `tx`
+    async fn subscribe_payouts_history(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        from: Option<BlockNumber>,
+        tx: UnboundedSender<Output>,
+    ) -> Result<(), ClaimitError>;
+
+    async fn fetch_account_balance(
+        api: &OnlineClient<PolkadotConfig>,
+        account: AccountId32,
+    ) -> Result<Balance, ClaimitError>;
+
+    /// Find which of the accounts given the delegate is a proxy of, and so can claim on behalf of
+    async fn fetch_proxies(
+        api: &OnlineClient<PolkadotConfig>,
+        delegate: &AccountId32,
+        reals: Vec<AccountId32>,
+    ) -> Result<Vec<Proxy>, ClaimitError>;
+
+    /// Read the call hash, weight and approvals of the batch claiming the child bounties, as
+    /// dispatched by the multisig
+    async fn fetch_multisig_status(
+        api: &OnlineClient<PolkadotConfig>,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+        multisig: &Multisig,
+    ) -> Result<MultisigStatus, ClaimitError>;
+
+    /// Split the child bounties to claim in batches that fit within the extrinsic weight and length limits
+    async fn split_claim(
+        api: &OnlineClient<PolkadotConfig>,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+    ) -> Result<Vec<ChildBountiesIds>, ClaimitError>;
+
+    /// Create the payload of the batch claiming the child bounties from the `origin` given, along
+    /// with the params to submit it with and its estimated fee in the `fee_asset`, if any
+    async fn create_payload_tx(
+        api: &OnlineClient<PolkadotConfig>,
+        runtime: SupportedRelayRuntime,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+        origin: ClaimOrigin,
+        signer_address: String,
+        mortality_period: u64,
+        fee_asset: Option<FeeAsset>,
+    ) -> Result<(SigningPayload, SigningParams, Option<FeeEstimate>), ClaimitError>;
+
+    /// Apply the signature to the batch claiming the child bounties, built with the same params
+    /// and fee asset as its payload, submit it and watch it until finalized, returning the outcome
+    /// of every child bounty.
+    /// Every stage the transaction reaches is sent as [`Output::TxProgress`].
+    async fn sign_and_submit_tx(
+        api: &OnlineClient<PolkadotConfig>,
+        request_id: RequestId,
+        child_bounties_ids: ChildBountiesIds,
+        mode: BatchMode,
+        origin: ClaimOrigin,
+        signer_address: String,
+        signature: Vec<u8>,
+        params: SigningParams,
+        fee_asset: Option<FeeAsset>,
+        tx: UnboundedSender<Output>,
+    ) -> Result<Vec<ClaimOutcome>, ClaimitError>;
+}
+
+/// Decodes the storage values and events of an Asset Hub runtime, from its generated types or from
+/// the live metadata, so the logic built on them is shared by both (see [`helpers`](crate::runtimes::helpers)).
+#[allow(async_fn_in_trait)]
+pub trait AssetHubDecoder {
+    /// A child bounty as stored in `ChildBounties::ChildBounties`
+    type ChildBountyValue: PartialEq;
+
+    /// Fetch all child bounties at the block of the storage given, by child bounty id
+    async fn fetch_child_bounties_values(
+        storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    ) -> Result<BTreeMap<ChildBountyId, Self::ChildBountyValue>, ClaimitError>;
+
+    /// Fetch the description of a child bounty at the block of the storage given
+    async fn fetch_child_bounty_description(
+        storage: &Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        parent_id: ParentBountyId,
+        child_id: ChildBountyId,
+    ) -> Result<String, ClaimitError>;
+
+    fn to_child_bounty(
+        id: ChildBountyId,
+        value: &Self::ChildBountyValue,
+        description: String,
+    ) -> Result<ChildBounty, ClaimitError>;
+
+    /// The payout of a child bounties `Awarded` or `Claimed` event, None for any other event
+    fn payout_event(
+        event: &EventDetails<PolkadotConfig>,
+    ) -> Result<Option<PayoutEvent>, ClaimitError>;
+
+    /// Why the call wrapped by a `Proxy::ProxyExecuted` or `Multisig::MultisigExecuted` event
+    /// failed to dispatch, None if it succeeded or for any other event
+    fn dispatch_failure(
+        event: &EventDetails<PolkadotConfig>,
+        metadata: Metadata,
+    ) -> Result<Option<String>, ClaimitError>;
+}
+
+/// Calls served by the People chain of a network, implemented via [`people_runtime!`](crate::people_runtime).
+#[allow(async_fn_in_trait)]
+pub trait PeopleRuntime {
+    /// Compare the metadata the runtime was generated from with the live one
+    fn compatibility(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<RuntimeCompatibility, ClaimitError>;
+
+    /// Look up the display name of the identity, or the parent identity if it is a sub account
+    async fn fetch_display_name(
+        api: &OnlineClient<PolkadotConfig>,
+        address: &AccountId32,
+    ) -> Result<Option<String>, ClaimitError>;
+}
@@ -1,5 +1,9 @@
 use num_format::{Locale, ToFormattedString};
-use subxt::config::substrate::AccountId32;
+use sp_crypto_hashing::{blake2_128, twox_128, twox_64};
+use subxt::{config::substrate::AccountId32, error::DispatchError, ext::codec::Encode, Metadata};
+
+/// Pallet id used by pallet_treasury (and pallet_bounties) to derive its accounts
+pub const TREASURY_PALLET_ID: [u8; 8] = *b"py/trsry";
 
 pub fn get_child_bounty_id_from_storage_key(key: Vec<u8>) -> u32 {
     let s = &key[key.len() - 4..];
@@ -7,6 +11,101 @@ pub fn get_child_bounty_id_from_storage_key(key: Vec<u8>) -> u32 {
     u32::from_le_bytes(v)
 }
 
+/// Derive the account holding the funds of a parent bounty,
+/// equivalent to `PalletId::into_sub_account_truncating(("bt", bounty_id))` in pallet_bounties.
+pub fn get_bounty_account_id(pallet_id: [u8; 8], bounty_id: u32) -> AccountId32 {
+    let encoded = (b"modl", pallet_id, "bt", bounty_id).encode();
+    let mut bytes = [0u8; 32];
+    let n = encoded.len().min(32);
+    bytes[..n].copy_from_slice(&encoded[..n]);
+    AccountId32::from(bytes)
+}
+
+/// Read the treasury `PalletId` constant from the live metadata (the compiled-in metadata
+/// does not include the Treasury pallet), defaults to `TREASURY_PALLET_ID`.
+pub fn get_treasury_pallet_id(metadata: &Metadata) -> [u8; 8] {
+    metadata
+        .pallet_by_name("Treasury")
+        .and_then(|pallet| pallet.constant_by_name("PalletId"))
+        .and_then(|constant| constant.value().get(..8))
+        .and_then(|bytes| bytes.try_into().ok())
+        .unwrap_or(TREASURY_PALLET_ID)
+}
+
+/// Describe the SCALE encoded `DispatchError` at the start of `bytes`, with module errors
+/// decoded from the metadata, e.g. `ChildBounties::ParentBountyNotActive: The parent bounty is not in active state.`
+pub fn describe_dispatch_error(bytes: &[u8], metadata: Metadata) -> String {
+    match DispatchError::decode_from(bytes, metadata) {
+        Ok(DispatchError::Module(module_error)) => match module_error.details() {
+            Ok(details) => {
+                let docs = details.variant.docs.join(" ");
+                if docs.is_empty() {
+                    format!("{}::{}", details.pallet.name(), details.variant.name)
+                } else {
+                    format!(
+                        "{}::{}: {}",
+                        details.pallet.name(),
+                        details.variant.name,
+                        docs.trim()
+                    )
+                }
+            }
+            Err(_) => module_error.to_string(),
+        },
+        Ok(dispatch_error) => dispatch_error.to_string(),
+        Err(e) => format!("Unknown dispatch error: {e}"),
+    }
+}
+
+/// Describe why a SCALE encoded `DispatchResult` failed, if it did, e.g. from `Proxy::ProxyExecuted`
+pub fn describe_dispatch_result(bytes: &[u8], metadata: Metadata) -> Option<String> {
+    match bytes.split_first() {
+        Some((1, dispatch_error)) => Some(describe_dispatch_error(dispatch_error, metadata)),
+        _ => None,
+    }
+}
+
+/// Storage key of `Proxy::Proxies` for the account given (`Twox64Concat` hasher), built by hand
+/// since the Proxy pallet is not in the metadata the runtimes are generated from.
+pub fn get_proxies_storage_key(real: &AccountId32) -> Vec<u8> {
+    [
+        twox_128(b"Proxy").as_slice(),
+        &twox_128(b"Proxies"),
+        &twox_64(&real.0),
+        &real.0,
+    ]
+    .concat()
+}
+
+/// Storage key of `Multisig::Multisigs` for the multisig account (`Twox64Concat` hasher) and
+/// the call hash (`Blake2_128Concat` hasher), built by hand as the one of `Proxy::Proxies`.
+pub fn get_multisigs_storage_key(multisig: &AccountId32, call_hash: &[u8; 32]) -> Vec<u8> {
+    [
+        twox_128(b"Multisig").as_slice(),
+        &twox_128(b"Multisigs"),
+        &twox_64(&multisig.0),
+        &multisig.0,
+        &blake2_128(call_hash),
+        call_hash,
+    ]
+    .concat()
+}
+
+/// Storage key of `Assets::Account` for the asset id and the account (`Blake2_128Concat` hashers),
+/// built by hand as the one of `Proxy::Proxies`.
+pub fn get_asset_account_storage_key(asset_id: u32, account: &AccountId32) -> Vec<u8> {
+    let asset_id = asset_id.encode();
+    [
+        twox_128(b"Assets").as_slice(),
+        &twox_128(b"Account"),
+        &blake2_128(&asset_id),
+        &asset_id,
+        &blake2_128(&account.0),
+        &account.0,
+    ]
+    .concat()
+}
+
 pub fn str(bytes: Vec<u8>) -> String {
     format!("{}", String::from_utf8(bytes).expect("Data not utf-8"))
 }
@@ -0,0 +1,167 @@
+use crate::errors::ClaimitError;
+use crate::types::child_bounties::ChildBountiesIds;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use subxt::{
+    dynamic::{At, DecodedValue},
+    ext::codec::{Compact, Decode, Encode},
+    tx::Payload,
+    OnlineClient, PolkadotConfig,
+};
+
+/// Maximum number of claims in a single batch, also used when the limits can't be estimated
+pub const MAX_CALLS_PER_BATCH: usize = 50;
+
+/// Share (in percent) of the maximum extrinsic weight and length a batch may take,
+/// so there is room left for the batch call itself and for the estimation to be off
+const BATCH_LIMIT_PERCENT: u64 = 75;
+
+/// How the claim calls are wrapped in the transactions submitted
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BatchMode {
+    /// `Utility::batch_all`, either all claims in a batch succeed or none does
+    #[strum(to_string = "Batch all")]
+    BatchAll,
+    /// `Utility::force_batch`, claims that fail do not prevent the others from succeeding
+    #[default]
+    #[strum(to_string = "Force batch")]
+    ForceBatch,
+    /// One transaction per claim, without `Utility`
+    #[strum(to_string = "Single calls")]
+    Single,
+}
+
+impl BatchMode {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::BatchAll => "All claims in a transaction succeed or none does.",
+            Self::ForceBatch => "Claims that fail do not prevent the others from succeeding.",
+            Self::Single => "A transaction to sign for every child bounty.",
+        }
+    }
+}
+
+/// Maximum weight and length a normal extrinsic can take, from the `System` pallet constants
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchLimits {
+    pub max_ref_time: u64,
+    pub max_proof_size: u64,
+    pub max_length: u64,
+}
+
+impl BatchLimits {
+    pub fn from_constants(api: &OnlineClient<PolkadotConfig>) -> Result<Self, ClaimitError> {
+        let weights = constant(api, "BlockWeights")?;
+        let length = constant(api, "BlockLength")?;
+
+        // Note: `max_extrinsic` is optional, in which case the whole block is the limit
+        let max_weight = match weights
+            .at("per_class")
+            .at("normal")
+            .at("max_extrinsic")
+            .at(0)
+        {
+            Some(weight) => weight,
+            None => weights
+                .at("max_block")
+                .ok_or_else(|| ClaimitError::from("Missing max block weight"))?,
+        };
+
+        let number = |value: Option<&DecodedValue>, name: &str| {
+            value
+                .and_then(|v| v.as_u128())
+                .map(|n| n as u64)
+                .ok_or_else(|| ClaimitError::Other(format!("Missing {name}")))
+        };
+
+        Ok(Self {
+            max_ref_time: number(max_weight.at("ref_time"), "ref_time")?,
+            max_proof_size: number(max_weight.at("proof_size"), "proof_size")?,
+            max_length: number(length.at("max").at("normal"), "max length")?,
+        })
+    }
+
+    /// Number of calls with the weight and length given that fit in a batch, up to [`MAX_CALLS_PER_BATCH`]
+    pub fn max_calls(&self, ref_time: u64, proof_size: u64, length: u64) -> usize {
+        let fit = |max: u64, each: u64| (max * BATCH_LIMIT_PERCENT / 100) / each.max(1);
+
+        let calls = fit(self.max_ref_time, ref_time)
+            .min(fit(self.max_proof_size, proof_size))
+            .min(fit(self.max_length, length));
+
+        (calls as usize).clamp(1, MAX_CALLS_PER_BATCH)
+    }
+}
+
+/// Split the child bounties to claim in batches that fit within the extrinsic limits.
+///
+/// Claims cost about the same, so the weight of the first one (as given by `claim_call`) is
+/// used for all of them. In [`BatchMode::Single`] every child bounty is claimed on its own.
+pub async fn split_in_batches<Call: Payload>(
+    api: &OnlineClient<PolkadotConfig>,
+    child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+    claim_call: impl Fn(u32, u32) -> Call,
+) -> Result<Vec<ChildBountiesIds>, ClaimitError> {
+    let Some((parent_bounty_id, child_bounty_id)) = child_bounties_ids.first().copied() else {
+        return Ok(Vec::new());
+    };
+
+    let calls_per_batch = match mode {
+        BatchMode::Single => 1,
+        _ => max_calls_per_batch(api, &claim_call(parent_bounty_id, child_bounty_id))
+            .await
+            .inspect_err(|e| warn!("Batch limits estimation failed: {e:?}"))
+            .unwrap_or(MAX_CALLS_PER_BATCH),
+    };
+
+    Ok(child_bounties_ids
+        .chunks(calls_per_batch)
+        .map(|batch| batch.to_vec())
+        .collect())
+}
+
+async fn max_calls_per_batch<Call: Payload>(
+    api: &OnlineClient<PolkadotConfig>,
+    call: &Call,
+) -> Result<usize, ClaimitError> {
+    let call_data = api.tx().call_data(call)?;
+    let (ref_time, proof_size) = query_call_weight(api, &call_data).await?;
+
+    let limits = BatchLimits::from_constants(api)?;
+
+    Ok(limits.max_calls(ref_time, proof_size, call_data.len() as u64))
+}
+
+/// Weight (`ref_time`, `proof_size`) of the SCALE encoded call, as benchmarked by the runtime
+pub async fn query_call_weight(
+    api: &OnlineClient<PolkadotConfig>,
+    call_data: &[u8],
+) -> Result<(u64, u64), ClaimitError> {
+    let mut call_parameters = call_data.to_vec();
+    (call_data.len() as u32).encode_to(&mut call_parameters);
+
+    // RuntimeDispatchInfo { weight: { ref_time, proof_size }, class, partial_fee }
+    let info = api
+        .runtime_api()
+        .at_latest()
+        .await?
+        .call_raw(
+            "TransactionPaymentCallApi_query_call_info",
+            Some(&call_parameters),
+        )
+        .await?;
+    let (ref_time, proof_size, _class, _partial_fee) =
+        <(Compact<u64>, Compact<u64>, u8, u128)>::decode(&mut &info[..])
+            .map_err(subxt::Error::from)?;
+
+    Ok((ref_time.0, proof_size.0))
+}
+
+fn constant(api: &OnlineClient<PolkadotConfig>, name: &str) -> Result<DecodedValue, ClaimitError> {
+    api.constants()
+        .at(&subxt::dynamic::constant("System", name))?
+        .to_value()
+        .map_err(|e| ClaimitError::Other(format!("Value decoding failed: {e}")))
+}
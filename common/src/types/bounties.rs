@@ -0,0 +1,64 @@
+use crate::runtimes::{support::SupportedRelayRuntime, utils::amount_human};
+use crate::types::child_bounties::ParentBountyId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum_macros::Display;
+use subxt::utils::AccountId32;
+use yew::{html, html::IntoPropValue, Html};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ParentBounty {
+    pub id: ParentBountyId,
+    pub description: String,
+    /// total value approved for the bounty
+    pub value: u128,
+    /// value still held by the bounty account, not yet assigned to child bounties
+    pub remaining_value: u128,
+    /// curator fee agreed for the bounty
+    pub fee: u128,
+    pub status: ParentBountyStatus,
+    /// curator assigned or proposed, only available from `CuratorProposed` onwards
+    pub curator: Option<AccountId32>,
+}
+
+impl ParentBounty {
+    pub fn value_human(&self, runtime: SupportedRelayRuntime) -> String {
+        amount_human(self.value, runtime.decimals().into())
+    }
+
+    pub fn remaining_value_human(&self, runtime: SupportedRelayRuntime) -> String {
+        amount_human(self.remaining_value, runtime.decimals().into())
+    }
+
+    pub fn to_compact_string(&self) -> String {
+        let max = self
+            .description
+            .chars()
+            .map(|c| c.len_utf8())
+            .take(32)
+            .sum();
+        self.description[..max].to_string()
+    }
+}
+
+pub type ParentBounties = BTreeMap<ParentBountyId, ParentBounty>;
+
+#[derive(Clone, Debug, Display, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ParentBountyStatus {
+    Proposed,
+    Approved,
+    Funded,
+    #[strum(to_string = "Curator Proposed")]
+    CuratorProposed,
+    Active,
+    #[strum(to_string = "Pending Payout")]
+    PendingPayout,
+    #[strum(to_string = "Approved With Curator")]
+    ApprovedWithCurator,
+}
+
+impl IntoPropValue<Html> for ParentBountyStatus {
+    fn into_prop_value(self) -> Html {
+        html! {<>{self.to_string()}</>}
+    }
+}
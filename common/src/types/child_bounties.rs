@@ -21,10 +21,16 @@ pub struct ChildBounty {
     pub parent_id: ParentBountyId,
     pub description: String,
     pub value: u128,
+    /// curator fee agreed for the child bounty
+    pub fee: u128,
     pub status: Status,
-    pub beneficiary: AccountId32,
+    /// curator assigned or proposed, not available while the child bounty is only `Added`
+    pub curator: Option<AccountId32>,
+    /// beneficiary is only available once the child bounty is awarded (`Pending`)
+    pub beneficiary: Option<AccountId32>,
     pub beneficiary_identity: Option<String>,
-    pub unlock_at: u32,
+    /// block number from which the child bounty can be claimed, only available if awarded (`Pending`)
+    pub unlock_at: Option<u32>,
 }
 
 impl ChildBounty {
@@ -36,17 +42,33 @@ impl ChildBounty {
         amount_human(self.value, runtime.decimals().into())
     }
 
+    pub fn fee_human(&self, runtime: SupportedRelayRuntime) -> String {
+        amount_human(self.fee, runtime.decimals().into())
+    }
+
+    pub fn is_awarded(&self) -> bool {
+        self.status == Status::Pending
+    }
+
     pub fn is_claimable(&self, block_number: u32) -> bool {
-        self.unlock_at < block_number
+        match self.unlock_at {
+            Some(unlock_at) => self.is_awarded() && unlock_at < block_number,
+            None => false,
+        }
+    }
+
+    pub fn is_beneficiary(&self, account: &AccountId32) -> bool {
+        self.beneficiary.as_ref() == Some(account)
     }
 
     pub fn unlock_duration(&self, block_number: u32) -> String {
-        if !self.is_claimable(block_number) {
-            let n = self.unlock_at - block_number;
-            let d = Duration::new(n as u64 * 6, 0);
-            format_duration(d).to_string()
-        } else {
-            "".into()
+        match self.unlock_at {
+            Some(unlock_at) if !self.is_claimable(block_number) => {
+                let n = unlock_at.saturating_sub(block_number);
+                let d = Duration::new(n as u64 * 6, 0);
+                format_duration(d).to_string()
+            }
+            _ => "".into(),
         }
     }
 
@@ -54,8 +76,10 @@ impl ChildBounty {
         if let Some(identity) = &self.beneficiary_identity {
             let max = identity.chars().map(|c| c.len_utf8()).take(24).sum();
             (&identity[..max]).to_string()
+        } else if let Some(beneficiary) = &self.beneficiary {
+            compact(beneficiary)
         } else {
-            compact(&self.beneficiary)
+            String::new()
         }
     }
 }
@@ -65,10 +89,27 @@ pub type ChildBountiesIds = Vec<(ParentBountyId, ChildBountyId)>;
 
 #[derive(Clone, Debug, Display, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Status {
+    /// Child bounty is added, waiting for a curator to be proposed
+    Added,
+    /// Curator has been proposed but not yet accepted
+    #[strum(to_string = "Curator Proposed")]
+    CuratorProposed,
+    /// Curator has accepted and the child bounty is being worked on
+    Active,
+    /// Child bounty has been awarded to a beneficiary and is pending payout
     Pending,
     Claimed,
 }
 
+impl Status {
+    pub fn is_upcoming(&self) -> bool {
+        matches!(
+            self,
+            Status::Added | Status::CuratorProposed | Status::Active
+        )
+    }
+}
+
 impl IntoPropValue<Html> for Status {
     fn into_prop_value(self) -> Html {
         html! {<>{self.to_string()}</>}
@@ -80,6 +121,7 @@ pub enum Filter {
     All,
     Following(Vec<AccountId32>),
     Claimable(Vec<ChildBountyId>),
+    Upcoming,
 }
 
 impl Filter {
@@ -88,10 +130,11 @@ impl Filter {
             Filter::All => true,
             Filter::Following(accounts) => accounts
                 .iter()
-                .any(|account| *account == child_bounty.beneficiary),
+                .any(|account| child_bounty.is_beneficiary(account)),
             Filter::Claimable(child_bounty_ids) => child_bounty_ids
                 .iter()
                 .any(|id: &u32| *id == child_bounty.id),
+            Filter::Upcoming => child_bounty.status.is_upcoming(),
         }
     }
 
@@ -116,6 +159,7 @@ impl std::fmt::Display for Filter {
             Self::All => write!(f, "All"),
             Self::Following(_) => write!(f, "Following"),
             Self::Claimable(_) => write!(f, "Claimable"),
+            Self::Upcoming => write!(f, "Upcoming"),
         }
     }
 }
@@ -1,14 +1,28 @@
-use crate::types::child_bounties::ChildBountiesIds;
+use crate::errors::ClaimitError;
+use crate::types::{
+    batches::BatchMode,
+    child_bounties::{ChildBountiesIds, ChildBountyId, ParentBountyId},
+    extensions::{SigningParams, SigningPayload},
+    fees::{FeeAsset, FeeEstimate},
+    multisigs::{Multisig, MultisigApproval, MultisigStatus},
+    proxies::Proxy,
+    worker::{BlockNumber, RequestId},
+};
 use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use subxt::{
+    utils::{AccountId32, H256},
+    OnlineClient, PolkadotConfig,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ClaimStatus {
     /// Initialize claiming process
     Initializing,
-    /// Prepare payload to be ready for signing
+    /// Prepare payload of the current batch to be ready for signing (or split the claim in batches first)
     Preparing,
-    /// Sign payload via browser extension
-    Signing(String),
+    /// Sign payload via browser extension, or offline
+    Signing(SigningPayload),
     /// Submit signed payload
     Submitting(Vec<u8>),
     /// Complete claiming process
@@ -29,12 +43,211 @@ impl std::fmt::Display for ClaimStatus {
     }
 }
 
+/// The account a claim is dispatched from
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ClaimOrigin {
+    /// The signer itself
+    #[default]
+    Signer,
+    /// Via `Proxy::proxy`, the account the signer is a proxy of
+    Proxy(AccountId32),
+    /// Via `Multisig::as_multi` or `Multisig::approve_as_multi`, the multisig the signer is a signatory of
+    Multisig(MultisigApproval),
+}
+
+/// Result of claiming a child bounty
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ClaimResult {
+    Claimed,
+    /// The claim was approved by the signer on behalf of a multisig, but still needs other approvals
+    Approved,
+    /// The claim failed, with the decoded `DispatchError` as the reason
+    Failed(String),
+}
+
+/// Outcome of a child bounty claimed in a submitted transaction
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClaimOutcome {
+    pub parent_id: ParentBountyId,
+    pub child_id: ChildBountyId,
+    pub result: ClaimResult,
+}
+
+impl ClaimOutcome {
+    /// Pair the child bounties submitted with the results of the batch items, in order.
+    ///
+    /// A claim submitted as a single call has no batch items, in which case all are claimed.
+    pub fn from_items(child_bounties_ids: &ChildBountiesIds, items: Vec<ClaimResult>) -> Vec<Self> {
+        let single = items.is_empty();
+        let mut items = items.into_iter();
+        child_bounties_ids
+            .iter()
+            .map(|(parent_id, child_id)| Self {
+                parent_id: *parent_id,
+                child_id: *child_id,
+                result: match items.next() {
+                    Some(result) => result,
+                    None if single => ClaimResult::Claimed,
+                    None => ClaimResult::Failed("Not dispatched".to_string()),
+                },
+            })
+            .collect()
+    }
+
+    /// All the child bounties submitted failed for the same reason
+    pub fn all_failed(child_bounties_ids: &ChildBountiesIds, reason: String) -> Vec<Self> {
+        Self::from_items(
+            child_bounties_ids,
+            vec![ClaimResult::Failed(reason); child_bounties_ids.len()],
+        )
+    }
+
+    /// All the child bounties submitted were approved by a multisig signatory, but not claimed yet
+    pub fn all_approved(child_bounties_ids: &ChildBountiesIds) -> Vec<Self> {
+        Self::from_items(
+            child_bounties_ids,
+            vec![ClaimResult::Approved; child_bounties_ids.len()],
+        )
+    }
+
+    /// The outcome of all the child bounties submitted, if settled by the `Proxy` or `Multisig`
+    /// event given, i.e. a multisig approval or a wrapped call that failed to dispatch for the
+    /// reason given
+    pub fn from_origin_event(
+        pallet: &str,
+        variant: &str,
+        dispatch_failure: Option<String>,
+        child_bounties_ids: &ChildBountiesIds,
+    ) -> Option<Vec<Self>> {
+        match (pallet, variant) {
+            ("Multisig", "NewMultisig") | ("Multisig", "MultisigApproval") => {
+                Some(Self::all_approved(child_bounties_ids))
+            }
+            _ => dispatch_failure.map(|reason| Self::all_failed(child_bounties_ids, reason)),
+        }
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.result == ClaimResult::Claimed
+    }
+
+    pub fn is_approved(&self) -> bool {
+        self.result == ClaimResult::Approved
+    }
+}
+
+/// Stages a submitted transaction goes through until finalized
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TxStage {
+    /// Validated by the node and in its pool
+    Validated,
+    /// Broadcast to other nodes
+    Broadcast,
+    /// Included in a best block, which might still be retracted
+    #[strum(to_string = "In best block")]
+    InBestBlock,
+    Finalized,
+}
+
+/// Block a submitted transaction is included in
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxBlock {
+    /// hex encoded hash of the block
+    pub hash: String,
+    pub number: BlockNumber,
+}
+
+/// Progress of a submitted transaction, streamed while it is being watched
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxProgress {
+    pub stage: TxStage,
+    /// hex encoded hash of the extrinsic submitted
+    pub extrinsic_hash: String,
+    /// The block the extrinsic is included in, from `InBestBlock` onwards
+    pub block: Option<TxBlock>,
+}
+
+impl TxProgress {
+    pub fn new(stage: TxStage, extrinsic_hash: H256) -> Self {
+        Self {
+            stage,
+            extrinsic_hash: format!("{extrinsic_hash:?}"),
+            block: None,
+        }
+    }
+
+    /// Progress of a transaction included in the block given, with the block number read from its header
+    pub async fn in_block(
+        api: &OnlineClient<PolkadotConfig>,
+        stage: TxStage,
+        extrinsic_hash: H256,
+        block_hash: H256,
+    ) -> Result<Self, ClaimitError> {
+        let number = match api.backend().block_header(block_hash).await? {
+            Some(header) => header.number,
+            None => 0,
+        };
+
+        Ok(Self {
+            block: Some(TxBlock {
+                hash: format!("{block_hash:?}"),
+                number,
+            }),
+            ..Self::new(stage, extrinsic_hash)
+        })
+    }
+
+    pub fn block_number(&self) -> BlockNumber {
+        self.block
+            .as_ref()
+            .map(|block| block.number)
+            .unwrap_or_default()
+    }
+
+    pub fn extrinsic_hash_compact(&self) -> String {
+        let h = &self.extrinsic_hash;
+        if h.len() > 14 {
+            [&h[..8], &h[h.len() - 6..]].join("...")
+        } else {
+            h.clone()
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ClaimState {
     /// An aray of child bounty ids
     pub child_bounty_ids: ChildBountiesIds,
     /// The status of the claim.
     pub status: ClaimStatus,
+    /// The id of the last request sent to the worker on behalf of the claim.
+    pub request_id: Option<RequestId>,
+    /// The fee the claim is expected to be charged, available once the payload is prepared.
+    pub fee: Option<FeeEstimate>,
+    /// The params the payload was prepared with, to submit the signed claim with.
+    pub params: Option<SigningParams>,
+    /// How the claim calls are wrapped in the transactions submitted.
+    pub mode: BatchMode,
+    /// The batches the claim is split in, available once the claim is being prepared.
+    pub batches: Vec<ChildBountiesIds>,
+    /// The index of the batch being signed and submitted.
+    pub batch_index: usize,
+    /// The outcome of every child bounty in the batches submitted so far.
+    pub outcomes: Vec<ClaimOutcome>,
+    /// The progress of the transaction of the batch being submitted.
+    pub progress: Option<TxProgress>,
+    /// The accounts the signer is a proxy of, and so can claim on behalf of.
+    pub proxies: Vec<Proxy>,
+    /// The account the claim is dispatched from via `Proxy::proxy`, if not the signer itself.
+    pub proxy: Option<AccountId32>,
+    /// The multisig the claim is dispatched from, if the signer approves it as a signatory.
+    pub multisig: Option<Multisig>,
+    /// The approvals of the batch being signed by the multisig, available once the batch is prepared.
+    pub multisig_status: Option<MultisigStatus>,
+    /// The balances of the signer in the assets the fee can be paid with.
+    pub fee_asset_balances: Vec<(FeeAsset, u128)>,
+    /// The asset the fee is paid with, if not the native token.
+    pub fee_asset: Option<FeeAsset>,
 }
 
 impl ClaimState {
@@ -42,6 +255,86 @@ impl ClaimState {
         Self {
             child_bounty_ids,
             status: ClaimStatus::Initializing,
+            request_id: None,
+            fee: None,
+            params: None,
+            mode: BatchMode::default(),
+            batches: Vec::new(),
+            batch_index: 0,
+            outcomes: Vec::new(),
+            progress: None,
+            proxies: Vec::new(),
+            proxy: None,
+            multisig: None,
+            multisig_status: None,
+            fee_asset_balances: Vec::new(),
+            fee_asset: None,
+        }
+    }
+
+    /// The account the batch being signed is dispatched from
+    pub fn origin(&self, signer: &AccountId32) -> ClaimOrigin {
+        match (&self.multisig, &self.multisig_status, &self.proxy) {
+            (Some(multisig), Some(status), _) => {
+                ClaimOrigin::Multisig(status.approval(multisig, signer))
+            }
+            (None, _, Some(real)) => ClaimOrigin::Proxy(real.clone()),
+            _ => ClaimOrigin::Signer,
+        }
+    }
+
+    /// Whether the multisig status of the batch being signed is still to be fetched
+    pub fn needs_multisig_status(&self) -> bool {
+        self.multisig.is_some() && self.multisig_status.is_none()
+    }
+
+    pub fn has_approvals(&self) -> bool {
+        self.outcomes.iter().any(|outcome| outcome.is_approved())
+    }
+
+    /// The outcome of a child bounty, once the batch it belongs to is submitted
+    pub fn outcome(
+        &self,
+        parent_id: ParentBountyId,
+        child_id: ChildBountyId,
+    ) -> Option<&ClaimOutcome> {
+        self.outcomes
+            .iter()
+            .find(|outcome| outcome.parent_id == parent_id && outcome.child_id == child_id)
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.outcomes
+            .iter()
+            .any(|outcome| matches!(outcome.result, ClaimResult::Failed(_)))
+    }
+
+    /// The child bounty ids of the batch being signed and submitted
+    pub fn current_batch(&self) -> ChildBountiesIds {
+        self.batches
+            .get(self.batch_index)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn is_split(&self) -> bool {
+        !self.batches.is_empty()
+    }
+
+    pub fn has_next_batch(&self) -> bool {
+        self.batch_index + 1 < self.batches.len()
+    }
+
+    /// Progress of the claim, e.g. `Batch 2 of 3`, if there is more than one batch
+    pub fn batch_progress(&self) -> Option<String> {
+        if self.batches.len() > 1 {
+            Some(format!(
+                "Batch {} of {}",
+                self.batch_index + 1,
+                self.batches.len()
+            ))
+        } else {
+            None
         }
     }
 
@@ -49,6 +342,10 @@ impl ClaimState {
         self.status == ClaimStatus::Initializing
     }
 
+    pub fn is_completed(&self) -> bool {
+        self.status == ClaimStatus::Completed
+    }
+
     pub fn is_signing_or_submitting(&self) -> bool {
         match self.status {
             ClaimStatus::Signing(_) | ClaimStatus::Submitting(_) => true,
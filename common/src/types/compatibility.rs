@@ -0,0 +1,222 @@
+use crate::errors::ClaimitError;
+use serde::{Deserialize, Serialize};
+use subxt::{ext::codec::Decode, Metadata, OnlineClient, PolkadotConfig};
+
+/// Pallets the app encodes by hand to build a claim: the `ChildBounties` claim itself, its
+/// `Utility` batch, the `Proxy` and `Multisig` wrappers around it, and the `Assets` balance and
+/// `AssetConversion` quote of the asset its fee may be paid with
+pub const CLAIM_PALLETS: [&str; 6] = [
+    "ChildBounties",
+    "Utility",
+    "Proxy",
+    "Multisig",
+    "Assets",
+    "AssetConversion",
+];
+
+/// Pallets the Asset Hub runtime reads from or calls into
+pub const ASSET_HUB_PALLETS: [&str; 8] = [
+    "System",
+    "Bounties",
+    "ChildBounties",
+    "Utility",
+    "Proxy",
+    "Multisig",
+    "Assets",
+    "AssetConversion",
+];
+
+/// Pallets the People runtime reads from
+pub const PEOPLE_PALLETS: [&str; 1] = ["Identity"];
+
+/// Result of comparing the metadata the app was built with against the live runtime of a chain.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeCompatibility {
+    /// Chain name, e.g. `Asset Hub`
+    pub chain: String,
+    /// Spec version of the runtime the app was built with, if present in the compiled-in metadata
+    pub compiled_spec_version: Option<u32>,
+    /// Spec version of the live runtime
+    pub live_spec_version: u32,
+    /// Pallets whose live metadata no longer matches the compiled-in one
+    pub outdated_pallets: Vec<String>,
+    /// Whether the chain is decoded from the live metadata instead of the generated code
+    pub dynamic: bool,
+    /// Why the live runtime could not be checked at all, if it could not
+    pub error: Option<String>,
+}
+
+impl RuntimeCompatibility {
+    /// Compare every pallet of the compiled-in metadata with the live one. A required pallet
+    /// missing from the compiled-in metadata is outdated too, since its calls are encoded with
+    /// indices that could not be checked.
+    pub fn check(
+        chain: &str,
+        compiled_metadata: &[u8],
+        required_pallets: &[&str],
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Result<Self, ClaimitError> {
+        let compiled = Metadata::decode(&mut &compiled_metadata[..])
+            .map_err(|e| ClaimitError::Other(format!("Invalid compiled-in metadata: {e}")))?;
+        let live = api.metadata();
+
+        let outdated_pallets = compiled
+            .pallets()
+            .filter(|pallet| {
+                live.pallet_by_name(pallet.name())
+                    .is_none_or(|live_pallet| live_pallet.hash() != pallet.hash())
+            })
+            .map(|pallet| pallet.name().to_string())
+            .chain(
+                required_pallets
+                    .iter()
+                    .filter(|name| compiled.pallet_by_name(name).is_none())
+                    .map(|name| name.to_string()),
+            )
+            .collect();
+
+        // RuntimeVersion is encoded as (spec_name, impl_name, authoring_version, spec_version, ..)
+        let compiled_spec_version = compiled
+            .pallet_by_name("System")
+            .and_then(|pallet| pallet.constant_by_name("Version"))
+            .and_then(|constant| <(String, String, u32, u32)>::decode(&mut constant.value()).ok())
+            .map(|(_, _, _, spec_version)| spec_version);
+
+        Ok(Self {
+            chain: chain.to_string(),
+            compiled_spec_version,
+            live_spec_version: api.runtime_version().spec_version,
+            outdated_pallets,
+            dynamic: false,
+            error: None,
+        })
+    }
+
+    /// Check that the live metadata has every pallet required to decode the chain dynamically,
+    /// listing the missing ones as outdated
+    pub fn dynamic(
+        chain: &str,
+        required_pallets: &[&str],
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> Self {
+        let live = api.metadata();
+
+        let outdated_pallets = required_pallets
+            .iter()
+            .filter(|name| live.pallet_by_name(name).is_none())
+            .map(|name| name.to_string())
+            .collect();
+
+        Self {
+            chain: chain.to_string(),
+            compiled_spec_version: None,
+            live_spec_version: api.runtime_version().spec_version,
+            outdated_pallets,
+            dynamic: true,
+            error: None,
+        }
+    }
+
+    /// Compatibility of a chain whose live runtime could not be checked, which blocks claims
+    pub fn unverified(chain: &str, api: &OnlineClient<PolkadotConfig>, error: String) -> Self {
+        Self {
+            chain: chain.to_string(),
+            compiled_spec_version: None,
+            live_spec_version: api.runtime_version().spec_version,
+            outdated_pallets: Vec::new(),
+            dynamic: false,
+            error: Some(error),
+        }
+    }
+
+    pub fn is_outdated(&self) -> bool {
+        !self.outdated_pallets.is_empty() || self.error.is_some()
+    }
+
+    /// Claims are blocked when the calls the app encodes no longer match the live runtime,
+    /// or when that could not be checked
+    pub fn is_claim_blocked(&self) -> bool {
+        self.error.is_some()
+            || self
+                .outdated_pallets
+                .iter()
+                .any(|pallet| CLAIM_PALLETS.contains(&pallet.as_str()))
+    }
+
+    pub fn description(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!(
+                "The {} runtime (spec version {}) could not be checked against the app: {error}.",
+                self.chain, self.live_spec_version
+            );
+        }
+        if self.dynamic {
+            return format!(
+                "The {} runtime (spec version {}) is missing pallets required by the app: {}.",
+                self.chain,
+                self.live_spec_version,
+                self.outdated_pallets.join(", ")
+            );
+        }
+        let built_with = match self.compiled_spec_version {
+            Some(spec_version) => format!("built for spec version {spec_version}"),
+            None => "built for an older runtime".to_string(),
+        };
+        format!(
+            "App out of date with the {} runtime (spec version {}, {built_with}). Changed pallets: {}.",
+            self.chain,
+            self.live_spec_version,
+            self.outdated_pallets.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compatibility(outdated_pallets: &[&str], error: Option<&str>) -> RuntimeCompatibility {
+        RuntimeCompatibility {
+            chain: "Asset Hub".to_string(),
+            compiled_spec_version: Some(1_006_000),
+            live_spec_version: 1_007_000,
+            outdated_pallets: outdated_pallets
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            dynamic: false,
+            error: error.map(|error| error.to_string()),
+        }
+    }
+
+    #[test]
+    fn outdated_pallets_outside_the_claim_do_not_block_claims() {
+        let compatibility = compatibility(&["Bounties"], None);
+
+        assert!(compatibility.is_outdated());
+        assert!(!compatibility.is_claim_blocked());
+        assert!(compatibility
+            .description()
+            .contains("built for spec version 1006000"));
+        assert!(!compatibility(&[], None).is_outdated());
+    }
+
+    #[test]
+    fn outdated_claim_pallets_block_claims() {
+        for pallet in CLAIM_PALLETS {
+            assert!(
+                compatibility(&[pallet], None).is_claim_blocked(),
+                "{pallet}"
+            );
+        }
+    }
+
+    #[test]
+    fn unchecked_runtime_blocks_claims() {
+        let compatibility = compatibility(&[], Some("invalid metadata"));
+
+        assert!(compatibility.is_outdated());
+        assert!(compatibility.is_claim_blocked());
+        assert!(compatibility.description().contains("could not be checked"));
+    }
+}
@@ -1,13 +1,27 @@
-use crate::runtimes::utils::compact;
+use crate::runtimes::{support::SupportedRelayRuntime, utils::compact};
+use crate::types::fees::FeeAsset;
 use anyhow::anyhow;
+use frame_metadata::RuntimeMetadataPrefixed;
 use js_sys::Promise;
+use log::warn;
+use merkleized_metadata::{generate_metadata_digest, ExtraInfo};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sp_crypto_hashing::blake2_256;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use subxt::{
-    config::substrate::AccountId32,
-    ext::codec::{Compact, Encode},
-    utils::Era,
+    config::{
+        substrate::AccountId32, DefaultExtrinsicParams, DefaultExtrinsicParamsBuilder as TxParams,
+        ExtrinsicParams,
+    },
+    ext::{
+        codec::{Compact, Decode, Encode},
+        scale_value,
+    },
+    tx::Payload,
+    utils::{Era, MultiAddress, MultiSignature, H256},
     OnlineClient, PolkadotConfig,
 };
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -31,6 +45,8 @@ pub struct ExtensionState {
     pub signer: Option<ExtensionAccount>,
     /// The status of the claim.
     pub status: ExtensionStatus,
+    /// Signing methods advertised by the wallet connected
+    pub signing_methods: Vec<SigningMethod>,
 }
 
 impl ExtensionState {
@@ -38,6 +54,7 @@ impl ExtensionState {
         Self {
             signer,
             status: ExtensionStatus::Initialized,
+            signing_methods: Vec::new(),
         }
     }
 
@@ -52,16 +69,60 @@ impl ExtensionState {
     pub fn is_connected_or_ready(&self) -> bool {
         self.is_connected() || self.is_ready()
     }
+
+    /// Whether the signer signs offline, without a browser extension
+    pub fn is_offline(&self) -> bool {
+        self.signer
+            .as_ref()
+            .is_some_and(|signer| signer.is_offline())
+    }
+}
+
+/// Methods a wallet may expose on the signer it injects
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SigningMethod {
+    /// Signs the transaction payload given as JSON, see `create_payload_as_string`
+    #[serde(rename = "signPayload")]
+    SignPayload,
+    /// Signs the bytes given, e.g. the SCALE encoded signer payload. Only valid for a transaction
+    /// if the wallet does not wrap them in `<Bytes>`, as the polkadot-js family does.
+    #[serde(rename = "signRaw")]
+    SignRaw,
+}
+
+impl SigningMethod {
+    /// The method to sign with among the ones advertised, `signPayload` unless only `signRaw` is.
+    /// Wallets whose methods are unknown are expected to support `signPayload`.
+    pub fn preferred(methods: &[SigningMethod]) -> Self {
+        if methods.contains(&Self::SignRaw) && !methods.contains(&Self::SignPayload) {
+            Self::SignRaw
+        } else {
+            Self::SignPayload
+        }
+    }
+}
+
+impl std::fmt::Display for SigningMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SignPayload => write!(f, "signPayload"),
+            Self::SignRaw => write!(f, "signRaw"),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Extension {
-    /// wallet name
+    /// wallet name, as injected in `window.injectedWeb3`
     pub name: String,
     /// wallet description
     pub description: String,
-    /// version of the browser extension
+    /// whether the browser extension is injected in the page
     pub installed: bool,
+    /// version of the browser extension, as injected
+    pub version: Option<String>,
+    /// whether the wallet is one of `extensions_supported`, with an icon of its own
+    pub curated: bool,
 }
 
 impl Extension {
@@ -70,8 +131,26 @@ impl Extension {
             name,
             description,
             installed: false,
+            version: None,
+            curated: true,
         }
     }
+
+    /// Any other wallet injected in the page, e.g. the in-app browser of Nova or Fearless
+    pub fn injected(name: String, version: Option<String>) -> Self {
+        Self {
+            description: name.clone(),
+            name,
+            installed: true,
+            version,
+            curated: false,
+        }
+    }
+
+    pub fn icon(&self) -> Option<String> {
+        self.curated
+            .then(|| format!("/images/ext/{}.svg", self.name))
+    }
 }
 
 impl std::fmt::Display for Extension {
@@ -101,6 +180,16 @@ pub fn extensions_supported() -> Vec<Extension> {
     out
 }
 
+/// Source of the signer account given by address, its payloads signed offline (e.g. Polkadot Vault)
+pub const OFFLINE_SOURCE: &str = "offline";
+
+/// Maximum size of a UOS frame, larger payloads are split in frames shown in turn
+const UOS_FRAME_SIZE: usize = 1024;
+
+/// Maximum size of a UOS transaction, larger ones are only sent as the hash signed, since they
+/// would take too many frames to be scanned
+const UOS_MAX_PAYLOAD_SIZE: usize = 64 * UOS_FRAME_SIZE;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ExtensionAccount {
     /// account name
@@ -114,6 +203,20 @@ pub struct ExtensionAccount {
 }
 
 impl ExtensionAccount {
+    /// Signer account without a browser extension, its payloads are exported to be signed offline
+    pub fn offline(account: &AccountId32) -> Self {
+        Self {
+            name: "Offline signer".to_string(),
+            source: OFFLINE_SOURCE.to_string(),
+            r#type: "sr25519".to_string(),
+            address: account.to_string(),
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.source == OFFLINE_SOURCE
+    }
+
     pub fn to_compact_string(&self) -> String {
         match AccountId32::from_str(&self.address) {
             Ok(account) => compact(&account),
@@ -128,8 +231,14 @@ extern "C" {
     pub fn js_get_extensions_installed() -> Promise;
     #[wasm_bindgen(js_name = getAccounts)]
     pub fn js_get_accounts(source: String) -> Promise;
+    #[wasm_bindgen(js_name = getSigningMethods)]
+    pub fn js_get_signing_methods(source: String) -> Promise;
     #[wasm_bindgen(js_name = signPayload)]
     pub fn js_sign_payload(payload: String, source: String, address: String) -> Promise;
+    #[wasm_bindgen(js_name = signRaw)]
+    pub fn js_sign_raw(data: String, source: String, address: String) -> Promise;
+    #[wasm_bindgen(js_name = provideMetadata)]
+    pub fn js_provide_metadata(source: String, metadata: String) -> Promise;
 }
 
 fn to_hex(bytes: impl AsRef<[u8]>) -> String {
@@ -147,18 +256,30 @@ pub async fn get_extensions() -> Result<Vec<Extension>, anyhow::Error> {
     let extensions_installed_str = result
         .as_string()
         .ok_or(anyhow!("Error converting JsValue into String"))?;
-    let installed: Vec<String> = serde_json::from_str(&extensions_installed_str)?;
+    let installed: Vec<InjectedWallet> = serde_json::from_str(&extensions_installed_str)?;
 
+    // Note: wallets out of the curated list are listed after it, by the name they are injected with
     let mut extensions = extensions_supported();
-    extensions.iter_mut().for_each(|ext| {
-        if installed.contains(&ext.name) {
-            ext.installed = true;
+    for wallet in installed {
+        match extensions.iter_mut().find(|ext| ext.name == wallet.name) {
+            Some(ext) => {
+                ext.installed = true;
+                ext.version = wallet.version;
+            }
+            None => extensions.push(Extension::injected(wallet.name, wallet.version)),
         }
-    });
+    }
 
     Ok(extensions)
 }
 
+/// Wallet injected in `window.injectedWeb3`, as listed by `getExtensionsInstalled`
+#[derive(Deserialize)]
+struct InjectedWallet {
+    name: String,
+    version: Option<String>,
+}
+
 pub async fn get_accounts(source: String) -> Result<Vec<ExtensionAccount>, anyhow::Error> {
     let result = JsFuture::from(js_get_accounts(source))
         .await
@@ -171,24 +292,395 @@ pub async fn get_accounts(source: String) -> Result<Vec<ExtensionAccount>, anyho
     Ok(accounts)
 }
 
+/// Provide the wallet with the chain metadata, unless it already knows the runtime version.
+/// Returns whether it was provided, i.e. accepted by the user.
+pub async fn provide_metadata(
+    source: String,
+    metadata: &MetadataDef,
+) -> Result<bool, anyhow::Error> {
+    let result = JsFuture::from(js_provide_metadata(
+        source,
+        serde_json::to_string(metadata)?,
+    ))
+    .await
+    .map_err(|js_err| anyhow!("{js_err:?}"))?;
+
+    Ok(result.as_bool().unwrap_or_default())
+}
+
+/// Signing methods the wallet exposes on its signer, once enabled by `get_accounts`
+pub async fn get_signing_methods(source: String) -> Result<Vec<SigningMethod>, anyhow::Error> {
+    let result = JsFuture::from(js_get_signing_methods(source))
+        .await
+        .map_err(|js_err| anyhow!("{js_err:?}"))?;
+    let methods_str = result
+        .as_string()
+        .ok_or(anyhow!("Error converting JsValue into String"))?;
+    let methods: Vec<SigningMethod> = serde_json::from_str(&methods_str)?;
+
+    Ok(methods)
+}
+
+/// Number of blocks a claim transaction stays valid for, unless configured otherwise
+pub const DEFAULT_MORTALITY_PERIOD: u64 = 64;
+
+/// Block a mortal transaction is valid from, and for how many blocks.
+///
+/// The payload signed by the extension and the transaction submitted must be built with the same
+/// mortality, otherwise the signature does not match.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Mortality {
+    /// number of blocks the transaction stays valid for, rounded up to a power of two by the era
+    pub period: u64,
+    pub checkpoint_number: u64,
+    pub checkpoint_hash: H256,
+}
+
+impl Mortality {
+    /// Mortality starting at the latest finalized block
+    pub async fn from_latest_finalized(
+        api: &OnlineClient<PolkadotConfig>,
+        period: u64,
+    ) -> Result<Self, subxt::Error> {
+        let block = api.blocks().at_latest().await?;
+        Ok(Self {
+            period,
+            checkpoint_number: block.number().into(),
+            checkpoint_hash: block.hash(),
+        })
+    }
+
+    pub fn era(&self) -> Era {
+        Era::mortal(self.period, self.checkpoint_number)
+    }
+
+    /// Transaction params with the nonce given and this mortality
+    pub fn tx_params(
+        &self,
+        account_nonce: u64,
+    ) -> <DefaultExtrinsicParams<PolkadotConfig> as ExtrinsicParams<PolkadotConfig>>::Params {
+        TxParams::new()
+            .nonce(account_nonce)
+            .mortal_from_unchecked(self.period, self.checkpoint_number, self.checkpoint_hash)
+            .build()
+    }
+}
+
+/// What the payload was signed with besides the call, so the transaction submitted is built
+/// from the same data, otherwise the signature does not match
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SigningParams {
+    pub mortality: Mortality,
+    pub account_nonce: u64,
+    /// Metadata hash committed to by `CheckMetadataHash`, if enabled
+    pub metadata_hash: Option<H256>,
+}
+
+/// Version of the metadata the digest committed to by `CheckMetadataHash` is computed from (RFC-0078)
+const METADATA_DIGEST_VERSION: u32 = 15;
+
+/// Key of a metadata hash: the genesis hash and spec version of the runtime it is computed from,
+/// along with the token of the network
+type MetadataHashKey = (H256, u32, SupportedRelayRuntime);
+
+thread_local! {
+    /// Metadata hashes already computed, since fetching and merkleizing the metadata is costly
+    /// and only changes with a runtime upgrade
+    static METADATA_HASHES: RefCell<Vec<(MetadataHashKey, H256)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Hash of the live metadata as defined by RFC-0078, i.e. the root of its merkleized types along
+/// with the runtime version, the SS58 prefix and the token of the network.
+///
+/// It is committed to by the `CheckMetadataHash` transaction extension, so the Ledger generic app
+/// can decode and show what it signs. None if the runtime does not check it.
+pub async fn fetch_metadata_hash(
+    api: &OnlineClient<PolkadotConfig>,
+    runtime: SupportedRelayRuntime,
+) -> Result<Option<H256>, anyhow::Error> {
+    let metadata = api.metadata();
+    let version = metadata
+        .extrinsic()
+        .transaction_extension_version_to_use_for_decoding();
+    let is_checked = metadata
+        .extrinsic()
+        .transaction_extensions_by_version(version)
+        .is_some_and(|mut extensions| {
+            extensions.any(|ext| ext.identifier() == "CheckMetadataHash")
+        });
+    if !is_checked {
+        return Ok(None);
+    }
+
+    let key = (
+        api.genesis_hash(),
+        api.runtime_version().spec_version,
+        runtime,
+    );
+    let cached = METADATA_HASHES.with_borrow(|hashes| {
+        hashes
+            .iter()
+            .find(|(cached_key, _)| *cached_key == key)
+            .map(|(_, hash)| *hash)
+    });
+    if cached.is_some() {
+        return Ok(cached);
+    }
+
+    let (spec_name, base58_prefix) = system_version(api)?;
+    let bytes = fetch_metadata_at_version(api, METADATA_DIGEST_VERSION).await?;
+    let RuntimeMetadataPrefixed(_, runtime_metadata) =
+        RuntimeMetadataPrefixed::decode(&mut &bytes[..])?;
+
+    let extra_info = ExtraInfo {
+        spec_version: api.runtime_version().spec_version,
+        spec_name,
+        base58_prefix,
+        decimals: runtime.decimals() as u8,
+        token_symbol: runtime.unit().to_string(),
+    };
+    let digest = generate_metadata_digest(&runtime_metadata, extra_info)
+        .map_err(|e| anyhow!("Metadata digest failed: {e}"))?;
+    let hash = H256(digest.hash());
+    METADATA_HASHES.with_borrow_mut(|hashes| hashes.push((key, hash)));
+
+    Ok(Some(hash))
+}
+
+/// Metadata at the version given, SCALE encoded as `RuntimeMetadataPrefixed`
+async fn fetch_metadata_at_version(
+    api: &OnlineClient<PolkadotConfig>,
+    version: u32,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let bytes = api
+        .runtime_api()
+        .at_latest()
+        .await?
+        .call_raw("Metadata_metadata_at_version", Some(&version.encode()))
+        .await?;
+
+    Option::<Vec<u8>>::decode(&mut &bytes[..])?
+        .ok_or(anyhow!("Metadata V{version} is not available"))
+}
+
+/// Spec name and SS58 prefix of the runtime, read from the `System` constants
+fn system_version(api: &OnlineClient<PolkadotConfig>) -> Result<(String, u16), anyhow::Error> {
+    let metadata = api.metadata();
+    let system = metadata.pallet_by_name_err("System")?;
+    let constant = |name: &str| {
+        system
+            .constant_by_name(name)
+            .map(|constant| constant.value())
+            .ok_or(anyhow!("System constant {name} not found"))
+    };
+    // Note: the spec name is the first field of `RuntimeVersion`
+    let spec_name = String::decode(&mut constant("Version")?)?;
+    let ss58_prefix = u16::decode(&mut constant("SS58Prefix")?)?;
+
+    Ok((spec_name, ss58_prefix))
+}
+
+/// Chain metadata provided to a wallet via `metadata.provide`, so it can decode the claim it signs
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataDef {
+    pub chain: String,
+    pub genesis_hash: String,
+    /// identicon theme of the addresses
+    pub icon: String,
+    pub ss58_format: u16,
+    pub spec_version: u32,
+    pub token_decimals: u16,
+    pub token_symbol: String,
+    /// no type definitions are needed along with the metadata, its types are self-described
+    pub types: BTreeMap<String, String>,
+    /// metadata V15 SCALE encoded as hex
+    pub raw_metadata: String,
+}
+
+impl MetadataDef {
+    /// Definition of the Asset Hub of the `runtime` given, from the live metadata
+    pub async fn fetch(
+        api: &OnlineClient<PolkadotConfig>,
+        runtime: SupportedRelayRuntime,
+    ) -> Result<Self, anyhow::Error> {
+        let (_, ss58_format) = system_version(api)?;
+        // Note: the same version the metadata hash is computed from, which wallets decode
+        let metadata = fetch_metadata_at_version(api, METADATA_DIGEST_VERSION).await?;
+
+        Ok(Self {
+            chain: format!("{runtime} Asset Hub"),
+            genesis_hash: encode_then_hex(&api.genesis_hash()),
+            icon: runtime.identicon().to_string(),
+            ss58_format,
+            spec_version: api.runtime_version().spec_version,
+            token_decimals: runtime.decimals(),
+            token_symbol: runtime.unit().to_string(),
+            types: BTreeMap::new(),
+            raw_metadata: to_hex(&metadata),
+        })
+    }
+}
+
+/// Transaction extensions of a claim, SCALE encoded in the order the metadata lists them.
+///
+/// Subxt only encodes `CheckMetadataHash` disabled, so they are encoded here with the mode enabled
+/// whenever the metadata hash is known. The payload signed and the transaction submitted must be
+/// built from the same extensions, otherwise the signature does not match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionExtensions {
+    /// data included in the transaction, e.g. the era, the nonce and the tip
+    pub value: Vec<u8>,
+    /// data only signed over, e.g. the genesis hash and the metadata hash
+    pub implicit: Vec<u8>,
+    pub metadata_hash: Option<H256>,
+    /// asset the fee is paid with via `ChargeAssetTxPayment`, the native token if none
+    pub fee_asset: Option<FeeAsset>,
+}
+
+impl TransactionExtensions {
+    /// Extensions with the nonce and mortality given, and the metadata hash of the live metadata.
+    /// If the hash can not be computed the mode is left disabled, as only the Ledger generic app
+    /// requires it.
+    pub async fn fetch(
+        api: &OnlineClient<PolkadotConfig>,
+        runtime: SupportedRelayRuntime,
+        account_nonce: u64,
+        mortality: &Mortality,
+        fee_asset: Option<FeeAsset>,
+    ) -> Result<Self, anyhow::Error> {
+        let metadata_hash = fetch_metadata_hash(api, runtime)
+            .await
+            .inspect_err(|e| warn!("Metadata hash unavailable, CheckMetadataHash disabled: {e:?}"))
+            .ok()
+            .flatten();
+
+        Self::new(api, account_nonce, mortality, metadata_hash, fee_asset)
+    }
+
+    pub fn new(
+        api: &OnlineClient<PolkadotConfig>,
+        account_nonce: u64,
+        mortality: &Mortality,
+        metadata_hash: Option<H256>,
+        fee_asset: Option<FeeAsset>,
+    ) -> Result<Self, anyhow::Error> {
+        let metadata = api.metadata();
+        let runtime_version = api.runtime_version();
+        let version = metadata
+            .extrinsic()
+            .transaction_extension_version_to_use_for_decoding();
+        let extensions = metadata
+            .extrinsic()
+            .transaction_extensions_by_version(version)
+            .ok_or(anyhow!("Transaction extensions not found"))?;
+
+        let mut value = Vec::new();
+        let mut implicit = Vec::new();
+        for extension in extensions {
+            match extension.identifier() {
+                "CheckSpecVersion" => runtime_version.spec_version.encode_to(&mut implicit),
+                "CheckTxVersion" => runtime_version.transaction_version.encode_to(&mut implicit),
+                "CheckGenesis" => api.genesis_hash().encode_to(&mut implicit),
+                "CheckMortality" | "CheckEra" => {
+                    mortality.era().encode_to(&mut value);
+                    mortality.checkpoint_hash.encode_to(&mut implicit);
+                }
+                "CheckNonce" => Compact(account_nonce).encode_to(&mut value),
+                "ChargeTransactionPayment" => Compact(0u128).encode_to(&mut value),
+                // Tip followed by the asset id, i.e. `Option<Location>`, the native token if none
+                "ChargeAssetTxPayment" => {
+                    Compact(0u128).encode_to(&mut value);
+                    match fee_asset {
+                        Some(asset) => {
+                            value.push(0x01);
+                            value.extend(asset.location());
+                        }
+                        None => value.push(0x00),
+                    }
+                }
+                "CheckMetadataHash" => {
+                    // Mode: 0 disabled, 1 enabled
+                    (metadata_hash.is_some() as u8).encode_to(&mut value);
+                    metadata_hash.encode_to(&mut implicit);
+                }
+                // Any other extension must carry no data, e.g. `CheckNonZeroSender` or `CheckWeight`
+                identifier => {
+                    for ty in [extension.extra_ty(), extension.additional_ty()] {
+                        if scale_value::scale::decode_as_type(&mut &[][..], ty, metadata.types())
+                            .is_err()
+                        {
+                            return Err(anyhow!("Unsupported transaction extension {identifier}"));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            value,
+            implicit,
+            metadata_hash,
+            fee_asset,
+        })
+    }
+
+    /// The bytes the signature is over, i.e. the call followed by the extensions data, hashed
+    /// with blake2_256 if longer than 256 bytes
+    pub fn signer_payload(&self, call_data: &[u8]) -> Vec<u8> {
+        let payload = [call_data, &self.value, &self.implicit].concat();
+        if payload.len() > 256 {
+            blake2_256(&payload).to_vec()
+        } else {
+            payload
+        }
+    }
+
+    /// The signed transaction (v4) ready to be submitted, i.e. the signer address, its signature
+    /// and the extensions value followed by the call, prefixed by its length
+    pub fn signed_extrinsic(
+        &self,
+        call_data: &[u8],
+        account: &AccountId32,
+        signature: &MultiSignature,
+    ) -> Vec<u8> {
+        let extrinsic = [
+            // Note: the signed bit along with the extrinsic format version 4
+            [0b1000_0000 | 4].as_slice(),
+            &MultiAddress::<AccountId32, ()>::Id(account.clone()).encode(),
+            &signature.encode(),
+            &self.value,
+            call_data,
+        ]
+        .concat();
+
+        [Compact(extrinsic.len() as u32).encode(), extrinsic].concat()
+    }
+}
+
 /// Create payload as string to be signed via a browser extension (NOTE: currently only supports polkadot-js)
 ///
-/// Some parameters are hard-coded here and not taken from the partial_extrinsic itself (mortality_checkpoint, era, tip).
+/// The tip is hard-coded here and not taken from the partial_extrinsic itself. The `mode` and
+/// `metadataHash` of `CheckMetadataHash` are set from the extensions given, as required by Ledger,
+/// and so is the `assetId` of `ChargeAssetTxPayment` when the fee is paid in an asset.
 pub async fn create_payload_as_string(
     api: &OnlineClient<PolkadotConfig>,
     call_data: &[u8],
     account_nonce: u64,
     account_address: String,
+    mortality: &Mortality,
+    extensions: &TransactionExtensions,
 ) -> Result<String, anyhow::Error> {
     let genesis_hash = encode_then_hex(&api.genesis_hash());
     // These numbers aren't SCALE encoded; their bytes are just converted to hex:
     let spec_version = to_hex(&api.runtime_version().spec_version.to_be_bytes());
     let transaction_version = to_hex(&api.runtime_version().transaction_version.to_be_bytes());
     let nonce = to_hex(&account_nonce.to_be_bytes());
-    // If you construct a mortal transaction, then this block hash needs to correspond
-    // to the block number passed to `Era::mortal()`.
-    let mortality_checkpoint = encode_then_hex(&api.genesis_hash());
-    let era = encode_then_hex(&Era::Immortal);
+    // The checkpoint block hash needs to correspond to the block number passed to `Era::mortal()`.
+    let mortality_checkpoint = encode_then_hex(&mortality.checkpoint_hash);
+    let checkpoint_number = to_hex((mortality.checkpoint_number as u32).to_be_bytes());
+    let era = encode_then_hex(&mortality.era());
     let method = to_hex(call_data);
 
     let metadata = api.metadata();
@@ -202,38 +694,252 @@ pub async fn create_payload_as_string(
         .unwrap_or_default();
 
     let tip = encode_then_hex(&Compact(0u128));
+    let mode = extensions.metadata_hash.is_some() as u8;
 
-    let payload = json!({
+    let mut payload = json!({
         "specVersion": spec_version,
         "transactionVersion": transaction_version,
         "address": account_address,
         "blockHash": mortality_checkpoint,
-        "blockNumber": "0x00000000",
+        "blockNumber": checkpoint_number,
         "era": era,
         "genesisHash": genesis_hash,
         "method": method,
+        "mode": mode,
         "nonce": nonce,
         "signedExtensions": signed_extensions,
         "tip": tip,
         "version": 4,
     });
+    if let Some(metadata_hash) = extensions.metadata_hash {
+        payload["metadataHash"] = json!(encode_then_hex(&metadata_hash));
+    }
+    if let Some(asset) = extensions.fee_asset {
+        payload["assetId"] = json!(to_hex(asset.location()));
+    }
 
     Ok(payload.to_string())
 }
 
-/// Collect signature from a browser extension (NOTE: currently only supports polkadot-js)
+/// Payload of a claim transaction to be signed, via a browser extension or offline
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SigningPayload {
+    /// Payload as string to be signed via a browser extension, see `create_payload_as_string`
+    pub json: String,
+    /// SCALE encoded call
+    pub call_data: Vec<u8>,
+    /// SCALE encoded transaction extensions, i.e. their value followed by their implicit data
+    pub extensions: Vec<u8>,
+    /// SCALE encoded bytes the signature is over, i.e. the call followed by the transaction
+    /// extensions, hashed with blake2_256 if longer than 256 bytes
+    pub signer_payload: Vec<u8>,
+    pub genesis_hash: H256,
+    /// Metadata hash committed to by `CheckMetadataHash`, if enabled
+    pub metadata_hash: Option<H256>,
+}
+
+impl SigningPayload {
+    pub async fn new<Call: Payload>(
+        api: &OnlineClient<PolkadotConfig>,
+        runtime: SupportedRelayRuntime,
+        call: &Call,
+        account_nonce: u64,
+        account_address: String,
+        mortality: &Mortality,
+        fee_asset: Option<FeeAsset>,
+    ) -> Result<Self, anyhow::Error> {
+        let call_data = api.tx().call_data(call)?;
+
+        // Note: the extensions are encoded as in `sign_and_submit_tx`, so the signature matches
+        let extensions =
+            TransactionExtensions::fetch(api, runtime, account_nonce, mortality, fee_asset).await?;
+
+        let json = create_payload_as_string(
+            api,
+            &call_data,
+            account_nonce,
+            account_address,
+            mortality,
+            &extensions,
+        )
+        .await?;
+
+        Ok(Self {
+            json,
+            extensions: [extensions.value.as_slice(), &extensions.implicit].concat(),
+            signer_payload: extensions.signer_payload(&call_data),
+            call_data,
+            genesis_hash: api.genesis_hash(),
+            metadata_hash: extensions.metadata_hash,
+        })
+    }
+
+    pub fn call_data_hex(&self) -> String {
+        to_hex(&self.call_data)
+    }
+
+    pub fn signer_payload_hex(&self) -> String {
+        to_hex(&self.signer_payload)
+    }
+
+    /// Frames of the UOS (Universal Offline Signatures) payload to be scanned by Polkadot Vault,
+    /// i.e. `0x53 ++ crypto ++ action ++ public key ++ payload ++ genesis hash`, each one
+    /// prefixed by the number of frames and its index.
+    ///
+    /// The payload is the transaction, i.e. the call prefixed by its length followed by the
+    /// extensions, so Vault can decode and show it. Only a transaction too large to be scanned
+    /// is sent as the hash signed instead.
+    pub fn uos_frames(&self, account: &AccountId32, crypto_type: &str) -> Vec<Vec<u8>> {
+        let crypto = match crypto_type {
+            "ed25519" => 0x00,
+            "ecdsa" => 0x02,
+            _ => 0x01,
+        };
+        let transaction = [
+            Compact(self.call_data.len() as u32).encode(),
+            self.call_data.clone(),
+            self.extensions.clone(),
+        ]
+        .concat();
+        // Note: the signer payload of such a transaction is already its blake2_256 hash
+        let (action, payload) = if transaction.len() > UOS_MAX_PAYLOAD_SIZE {
+            (0x01, self.signer_payload.clone())
+        } else {
+            (0x02, transaction)
+        };
+        let uos = [
+            [0x53, crypto, action].as_slice(),
+            &account.0,
+            &payload,
+            &self.genesis_hash.0,
+        ]
+        .concat();
+
+        let chunks = uos.chunks(UOS_FRAME_SIZE).collect::<Vec<&[u8]>>();
+        chunks
+            .iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                [
+                    [0x00].as_slice(),
+                    &(chunks.len() as u16).to_be_bytes(),
+                    &(index as u16).to_be_bytes(),
+                    chunk,
+                ]
+                .concat()
+            })
+            .collect()
+    }
+}
+
+/// Signature pasted back from an offline signer, as a SCALE encoded `MultiSignature`.
+///
+/// A raw signature (e.g. from Polkadot Vault) is prefixed by the variant of the signer crypto,
+/// one already encoded as `MultiSignature` is kept as is if its variant is the signer crypto.
+pub fn multi_signature_from_hex(
+    signature: &str,
+    crypto_type: &str,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let signature = signature.trim();
+    let bytes = hex::decode(signature.strip_prefix("0x").unwrap_or(signature))?;
+
+    // MultiSignature variant and raw signature length of the signer crypto
+    let (variant, length) = match crypto_type {
+        "ed25519" => (0x00, 64),
+        "sr25519" => (0x01, 64),
+        "ecdsa" => (0x02, 65),
+        _ => return Err(anyhow!("Unsupported signer crypto {crypto_type}")),
+    };
+
+    match bytes.len() {
+        n if n == length => Ok([[variant].as_slice(), &bytes].concat()),
+        n if n == length + 1 && bytes[0] == variant => Ok(bytes),
+        n if n == length + 1 => Err(anyhow!(
+            "Signature encoded for another crypto than the {crypto_type} signer"
+        )),
+        n => Err(anyhow!("Invalid {crypto_type} signature of {n} bytes")),
+    }
+}
+
+/// Collect signature from a browser extension, as a SCALE encoded `MultiSignature`.
+///
+/// The JSON payload is signed via `signPayload`, otherwise the signer payload bytes are signed via
+/// `signRaw` and the raw signature is prefixed by the variant of the signer crypto. A `signRaw`
+/// signature over anything else than the bytes given, e.g. wrapped in `<Bytes>`, is rejected with
+/// the reason why.
 pub async fn collect_signature(
-    payload: String,
-    account_source: String,
-    account_address: String,
+    payload: &SigningPayload,
+    signer: &ExtensionAccount,
+    method: SigningMethod,
 ) -> Result<Vec<u8>, anyhow::Error> {
-    let result = JsFuture::from(js_sign_payload(payload, account_source, account_address))
+    let promise = match method {
+        SigningMethod::SignPayload => js_sign_payload(
+            payload.json.clone(),
+            signer.source.clone(),
+            signer.address.clone(),
+        ),
+        SigningMethod::SignRaw => js_sign_raw(
+            payload.signer_payload_hex(),
+            signer.source.clone(),
+            signer.address.clone(),
+        ),
+    };
+    let result = JsFuture::from(promise)
         .await
         .map_err(|js_err| anyhow!("{js_err:?}"))?;
 
     let signature = result
         .as_string()
         .ok_or(anyhow!("Error converting JsValue into String"))?;
-    let signature = hex::decode(&signature[2..])?;
-    Ok(signature)
+    match method {
+        SigningMethod::SignPayload => Ok(hex::decode(
+            signature.strip_prefix("0x").unwrap_or(&signature),
+        )?),
+        SigningMethod::SignRaw => multi_signature_from_hex(&signature, &signer.r#type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_method_falls_back_to_sign_raw() {
+        use SigningMethod::{SignPayload, SignRaw};
+
+        assert_eq!(
+            SigningMethod::preferred(&[SignPayload, SignRaw]),
+            SignPayload
+        );
+        assert_eq!(SigningMethod::preferred(&[SignRaw]), SignRaw);
+        // Wallets whose methods are unknown
+        assert_eq!(SigningMethod::preferred(&[]), SignPayload);
+    }
+
+    #[test]
+    fn multi_signature_from_a_pasted_signature() {
+        let raw = format!("0x{}", "ab".repeat(64));
+
+        let signature = multi_signature_from_hex(&raw, "sr25519").unwrap();
+        assert_eq!(signature[0], 0x01);
+        assert_eq!(signature[1..], [0xab; 64]);
+        assert_eq!(multi_signature_from_hex(&raw, "ed25519").unwrap()[0], 0x00);
+
+        // Already encoded as MultiSignature, only for the signer crypto
+        let encoded = hex::encode(&signature);
+        assert_eq!(
+            multi_signature_from_hex(&encoded, "sr25519").unwrap(),
+            signature
+        );
+        assert!(multi_signature_from_hex(&encoded, "ed25519").is_err());
+
+        // A raw ecdsa signature is 65 bytes long
+        let raw_ecdsa = format!("0x{}", "ab".repeat(65));
+        let signature = multi_signature_from_hex(&raw_ecdsa, "ecdsa").unwrap();
+        assert_eq!(signature[0], 0x02);
+        assert_eq!(signature.len(), 66);
+
+        assert!(multi_signature_from_hex("0xabcd", "sr25519").is_err());
+        assert!(multi_signature_from_hex("not hex", "sr25519").is_err());
+    }
 }
@@ -0,0 +1,211 @@
+use crate::errors::ClaimitError;
+use crate::runtimes::{support::SupportedRelayRuntime, utils::get_asset_account_storage_key};
+use crate::types::extensions::Mortality;
+use serde::{Deserialize, Serialize};
+use subxt::{
+    ext::codec::{Compact, Decode, Encode},
+    tx::Payload,
+    utils::{AccountId32, MultiSignature},
+    OnlineClient, PolkadotConfig,
+};
+
+/// Multilocation of the native token as seen from Asset Hub, i.e. `{ parents: 1, interior: Here }`
+const NATIVE_LOCATION: [u8; 2] = [0x01, 0x00];
+
+/// Sufficient assets of the Assets pallet the transaction fee can be paid with, via
+/// `ChargeAssetTxPayment` swapping them for the native token
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum FeeAsset {
+    Usdt,
+    Usdc,
+}
+
+impl FeeAsset {
+    /// Fee assets available on the Asset Hub of the relay chain given
+    pub fn supported(runtime: SupportedRelayRuntime) -> Vec<FeeAsset> {
+        match runtime {
+            SupportedRelayRuntime::Polkadot => vec![FeeAsset::Usdt, FeeAsset::Usdc],
+            SupportedRelayRuntime::Kusama => vec![FeeAsset::Usdt],
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Usdt => 1984,
+            Self::Usdc => 1337,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Usdt => "USDT",
+            Self::Usdc => "USDC",
+        }
+    }
+
+    pub fn decimals(&self) -> u32 {
+        6
+    }
+
+    /// SCALE encoded multilocation of the asset, as given to `ChargeAssetTxPayment`, i.e.
+    /// `{ parents: 0, interior: X2(PalletInstance(50), GeneralIndex(id)) }`
+    pub fn location(&self) -> Vec<u8> {
+        let mut location = vec![0x00, 0x02, 0x04, 50, 0x05];
+        Compact(self.id() as u128).encode_to(&mut location);
+        location
+    }
+}
+
+/// Balance of the account in the fee asset given, zero if the account holds none
+pub async fn fetch_fee_asset_balance(
+    api: &OnlineClient<PolkadotConfig>,
+    asset: FeeAsset,
+    account_id: &AccountId32,
+) -> Result<u128, ClaimitError> {
+    let key = get_asset_account_storage_key(asset.id(), account_id);
+    match api.storage().at_latest().await?.fetch_raw(key).await? {
+        // AssetAccount { balance, status, reason, extra }
+        Some(bytes) => Ok(u128::decode(&mut &bytes[..]).map_err(subxt::Error::from)?),
+        None => Ok(0),
+    }
+}
+
+/// Balances of the account in every fee asset supported by the runtime given
+pub async fn fetch_fee_asset_balances(
+    api: &OnlineClient<PolkadotConfig>,
+    runtime: SupportedRelayRuntime,
+    account_id: &AccountId32,
+) -> Result<Vec<(FeeAsset, u128)>, ClaimitError> {
+    let mut balances = Vec::new();
+    for asset in FeeAsset::supported(runtime) {
+        let balance = fetch_fee_asset_balance(api, asset, account_id).await?;
+        balances.push((asset, balance));
+    }
+    Ok(balances)
+}
+
+/// Breakdown of the fee charged for including a transaction in a block
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InclusionFee {
+    /// minimum fee charged for any transaction
+    pub base_fee: u128,
+    /// fee charged for the length of the encoded transaction
+    pub len_fee: u128,
+    /// fee charged for the weight of the transaction, adjusted to the chain congestion
+    pub adjusted_weight_fee: u128,
+}
+
+/// Fee a transaction is expected to be charged, as estimated by `TransactionPaymentApi` before signing
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// fee charged for the transaction, excluding the tip
+    pub partial_fee: u128,
+    /// only available for signed transactions paying the fee in the native token
+    pub inclusion_fee: Option<InclusionFee>,
+    pub tip: u128,
+    /// free balance of the signer paying the fee
+    pub free_balance: u128,
+    /// the asset the fee and the free balance are in, the native token if none
+    pub asset: Option<FeeAsset>,
+}
+
+impl FeeEstimate {
+    pub fn total(&self) -> u128 {
+        self.partial_fee + self.tip
+    }
+
+    /// Whether the free balance of the signer covers the fee
+    pub fn is_covered(&self) -> bool {
+        self.free_balance >= self.total()
+    }
+}
+
+/// Estimate the fee of the transaction `call` signed by `account_id`.
+///
+/// The fee only depends on the call and the length of the extrinsic, so it is estimated with a
+/// dummy signature the same size as the one the extension will provide.
+///
+/// If paid in a fee asset, the fee is quoted by `AssetConversionApi` as the amount of the asset
+/// swapped for the native fee, and `free_balance` is expected to be the balance of the asset.
+pub async fn estimate_fee<Call: Payload>(
+    api: &OnlineClient<PolkadotConfig>,
+    call: &Call,
+    account_id: &AccountId32,
+    account_nonce: u64,
+    mortality: &Mortality,
+    fee_asset: Option<FeeAsset>,
+    free_balance: u128,
+) -> Result<FeeEstimate, ClaimitError> {
+    let params = mortality.tx_params(account_nonce);
+    let mut partial_signed = api.tx().create_partial_offline(call, params)?;
+    let extrinsic = partial_signed
+        .sign_with_account_and_signature(account_id, &MultiSignature::Sr25519([0u8; 64]));
+
+    // Note: the extrinsic is built without asset id, the length charged accounts for its location
+    let asset_len = fee_asset.map_or(0, |asset| asset.location().len());
+    let mut call_parameters = extrinsic.encoded().to_vec();
+    ((extrinsic.encoded().len() + asset_len) as u32).encode_to(&mut call_parameters);
+
+    let runtime_api = api.runtime_api().at_latest().await?;
+
+    // RuntimeDispatchInfo { weight: { ref_time, proof_size }, class, partial_fee }
+    let info = runtime_api
+        .call_raw("TransactionPaymentApi_query_info", Some(&call_parameters))
+        .await?;
+    let (_ref_time, _proof_size, _class, partial_fee) =
+        <(Compact<u64>, Compact<u64>, u8, u128)>::decode(&mut &info[..])
+            .map_err(subxt::Error::from)?;
+
+    // FeeDetails { inclusion_fee: Option<{ base_fee, len_fee, adjusted_weight_fee }>, tip }
+    let details = runtime_api
+        .call_raw(
+            "TransactionPaymentApi_query_fee_details",
+            Some(&call_parameters),
+        )
+        .await?;
+    let (inclusion_fee, tip) = <(Option<(u128, u128, u128)>, u128)>::decode(&mut &details[..])
+        .map_err(subxt::Error::from)?;
+
+    if let Some(asset) = fee_asset {
+        // Amount of the asset to swap for exactly the native fee, including the pool fee
+        let mut quote_parameters = asset.location();
+        quote_parameters.extend(NATIVE_LOCATION);
+        (partial_fee + tip, true).encode_to(&mut quote_parameters);
+        let quote = runtime_api
+            .call_raw(
+                "AssetConversionApi_quote_price_tokens_for_exact_tokens",
+                Some(&quote_parameters),
+            )
+            .await?;
+        let Some(asset_fee) =
+            <Option<u128>>::decode(&mut &quote[..]).map_err(subxt::Error::from)?
+        else {
+            return Err(ClaimitError::Other(format!(
+                "No pool to swap {} for the fee",
+                asset.symbol()
+            )));
+        };
+
+        // Note: the tip is paid in the asset as well, so it is quoted along with the fee
+        return Ok(FeeEstimate {
+            partial_fee: asset_fee,
+            inclusion_fee: None,
+            tip: 0,
+            free_balance,
+            asset: Some(asset),
+        });
+    }
+
+    Ok(FeeEstimate {
+        partial_fee,
+        inclusion_fee: inclusion_fee.map(|(base_fee, len_fee, adjusted_weight_fee)| InclusionFee {
+            base_fee,
+            len_fee,
+            adjusted_weight_fee,
+        }),
+        tip,
+        free_balance,
+        asset: None,
+    })
+}
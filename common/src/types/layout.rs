@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct LayoutState {
     pub is_add_account_modal_visible: bool,
+    pub is_rpc_endpoints_modal_visible: bool,
     pub is_onboarding: bool,
     pub balance_mode: BalanceMode,
 }
@@ -11,6 +12,7 @@ impl LayoutState {
     pub fn new(is_onboarding: bool) -> Self {
         Self {
             is_add_account_modal_visible: false,
+            is_rpc_endpoints_modal_visible: false,
             is_onboarding,
             balance_mode: BalanceMode::TotalBalance,
         }
@@ -1,7 +1,14 @@
 pub mod accounts;
+pub mod batches;
+pub mod bounties;
 pub mod child_bounties;
 pub mod claims;
+pub mod compatibility;
 pub mod extensions;
+pub mod fees;
 pub mod layout;
+pub mod multisigs;
 pub mod network;
+pub mod payouts;
+pub mod proxies;
 pub mod worker;
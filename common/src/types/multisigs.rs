@@ -0,0 +1,203 @@
+use crate::errors::ClaimitError;
+use crate::runtimes::utils::{compact, get_multisigs_storage_key};
+use crate::types::{batches::query_call_weight, worker::BlockNumber};
+use serde::{Deserialize, Serialize};
+use sp_crypto_hashing::blake2_256;
+use subxt::{
+    ext::codec::{Decode, Encode},
+    utils::AccountId32,
+    OnlineClient, PolkadotConfig,
+};
+
+/// A multisig account, as derived by `pallet_multisig` from its signatories and threshold
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Multisig {
+    /// All the signatories, sorted as the pallet expects them
+    pub signatories: Vec<AccountId32>,
+    pub threshold: u16,
+}
+
+impl Multisig {
+    pub fn new(mut signatories: Vec<AccountId32>, threshold: u16) -> Result<Self, ClaimitError> {
+        signatories.sort();
+        signatories.dedup();
+
+        if signatories.len() < 2 {
+            return Err("A multisig needs at least two signatories".into());
+        }
+        if threshold < 2 || threshold as usize > signatories.len() {
+            return Err(ClaimitError::Other(format!(
+                "The threshold must be between 2 and {}",
+                signatories.len()
+            )));
+        }
+
+        Ok(Self {
+            signatories,
+            threshold,
+        })
+    }
+
+    /// The account of the multisig, i.e. `blake2_256("modlpy/utilisuba" ++ signatories ++ threshold)`
+    pub fn account(&self) -> AccountId32 {
+        AccountId32(blake2_256(
+            &(b"modlpy/utilisuba", &self.signatories, self.threshold).encode(),
+        ))
+    }
+
+    pub fn is_signatory(&self, account: &AccountId32) -> bool {
+        self.signatories.contains(account)
+    }
+
+    /// The signatories other than the one signing, as given to `as_multi` and `approve_as_multi`
+    pub fn other_signatories(&self, signer: &AccountId32) -> Vec<AccountId32> {
+        self.signatories
+            .iter()
+            .filter(|account| *account != signer)
+            .cloned()
+            .collect()
+    }
+
+    pub fn description(&self) -> String {
+        format!(
+            "{} · {} of {}",
+            compact(&self.account()),
+            self.threshold,
+            self.signatories.len()
+        )
+    }
+}
+
+/// Block number and extrinsic index of the first approval of a multisig operation
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Timepoint {
+    pub height: BlockNumber,
+    pub index: u32,
+}
+
+/// A multisig operation waiting for approvals, from `Multisig::Multisigs` storage
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingMultisig {
+    pub when: Timepoint,
+    pub deposit: u128,
+    pub depositor: AccountId32,
+    pub approvals: Vec<AccountId32>,
+}
+
+/// The call claiming the child bounties as dispatched by the multisig, and its approvals so far
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MultisigStatus {
+    pub call_hash: [u8; 32],
+    /// Weight of the call, the most the multisig execution may take
+    pub max_weight: (u64, u64),
+    pub pending: Option<PendingMultisig>,
+}
+
+impl MultisigStatus {
+    pub fn approvals(&self) -> usize {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.approvals.len())
+            .unwrap_or_default()
+    }
+
+    pub fn is_approved_by(&self, account: &AccountId32) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|pending| pending.approvals.contains(account))
+    }
+
+    pub fn call_hash_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.call_hash))
+    }
+
+    /// How the signer approves the call: the last approval needed executes it via `as_multi`,
+    /// the others only register it via `approve_as_multi`
+    pub fn approval(&self, multisig: &Multisig, signer: &AccountId32) -> MultisigApproval {
+        MultisigApproval {
+            threshold: multisig.threshold,
+            other_signatories: multisig.other_signatories(signer),
+            timepoint: self.pending.as_ref().map(|pending| pending.when),
+            call_hash: self.call_hash,
+            max_weight: self.max_weight,
+            execute: self.approvals() + 1 >= multisig.threshold as usize,
+        }
+    }
+}
+
+/// Everything needed to wrap the claim in `Multisig::as_multi` or `Multisig::approve_as_multi`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MultisigApproval {
+    pub threshold: u16,
+    pub other_signatories: Vec<AccountId32>,
+    /// `None` for the first approval
+    pub timepoint: Option<Timepoint>,
+    pub call_hash: [u8; 32],
+    pub max_weight: (u64, u64),
+    /// Whether this is the last approval needed, executing the call
+    pub execute: bool,
+}
+
+/// Read the approvals of the SCALE encoded call dispatched by the multisig, from `Multisig::Multisigs` storage
+pub async fn fetch_multisig_status(
+    api: &OnlineClient<PolkadotConfig>,
+    multisig: &Multisig,
+    call_data: Vec<u8>,
+) -> Result<MultisigStatus, ClaimitError> {
+    let call_hash = blake2_256(&call_data);
+    let max_weight = query_call_weight(api, &call_data).await?;
+
+    let key = get_multisigs_storage_key(&multisig.account(), &call_hash);
+    let pending = match api.storage().at_latest().await?.fetch_raw(key).await? {
+        Some(bytes) => {
+            // Multisig { when: Timepoint { height, index }, deposit, depositor, approvals }
+            let ((height, index), deposit, depositor, approvals) =
+                <((u32, u32), u128, AccountId32, Vec<AccountId32>)>::decode(&mut &bytes[..])
+                    .map_err(subxt::Error::from)?;
+            Some(PendingMultisig {
+                when: Timepoint { height, index },
+                deposit,
+                depositor,
+                approvals,
+            })
+        }
+        None => None,
+    };
+
+    Ok(MultisigStatus {
+        call_hash,
+        max_weight,
+        pending,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+    fn account(address: &str) -> AccountId32 {
+        AccountId32::from_str(address).unwrap()
+    }
+
+    #[test]
+    fn multisig_account_is_derived_from_its_signatories() {
+        let multisig =
+            Multisig::new(vec![account(CHARLIE), account(ALICE), account(BOB)], 2).unwrap();
+        assert_eq!(
+            multisig.account(),
+            account("5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7")
+        );
+        assert_eq!(
+            multisig.other_signatories(&account(BOB)),
+            vec![account(CHARLIE), account(ALICE)]
+        );
+
+        assert!(Multisig::new(vec![account(ALICE), account(ALICE)], 2).is_err());
+        assert!(Multisig::new(vec![account(ALICE), account(BOB)], 3).is_err());
+    }
+}
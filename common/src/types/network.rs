@@ -1,6 +1,12 @@
+use crate::errors::WorkerError;
 use crate::runtimes::support::SupportedRelayRuntime;
+use crate::types::compatibility::RuntimeCompatibility;
+use crate::types::worker::{BlockNumber, RequestId, RequestKind};
+use log::error;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use subxt::{OnlineClient, PolkadotConfig};
 
 pub type SubscriptionId = u32;
 
@@ -8,6 +14,7 @@ pub type SubscriptionId = u32;
 pub enum NetworkStatus {
     Initializing,
     Active,
+    Reconnecting,
     Inactive,
 }
 
@@ -16,6 +23,7 @@ impl NetworkStatus {
         match self {
             Self::Initializing => "text-orange",
             Self::Active => "text-gray-900",
+            Self::Reconnecting => "text-orange",
             Self::Inactive => "text-red",
         }
     }
@@ -26,11 +34,102 @@ impl std::fmt::Display for NetworkStatus {
         match self {
             Self::Initializing => write!(f, "Initializing"),
             Self::Active => write!(f, "Active"),
+            Self::Reconnecting => write!(f, "Reconnecting"),
             Self::Inactive => write!(f, "Inactive"),
         }
     }
 }
 
+/// Progress of the connection to the network endpoints, as reported by the worker.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Connecting(String),
+    Failed(String, String),
+    Retrying(u32),
+    Connected(String),
+    Disconnected(String),
+}
+
+impl std::fmt::Display for ConnectionEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connecting(url) => write!(f, "Connecting to {url}..."),
+            Self::Failed(url, reason) => write!(f, "Connection to {url} failed. {reason}"),
+            Self::Retrying(secs) => write!(f, "All endpoints failed, retrying in {secs}s..."),
+            Self::Connected(url) => write!(f, "Connected to {url}"),
+            Self::Disconnected(reason) => write!(f, "Connection lost. {reason}"),
+        }
+    }
+}
+
+/// Custom RPC endpoints set by the user, replacing the default ones of each chain when defined.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcEndpoints {
+    pub relay: Option<String>,
+    pub people: Option<String>,
+    pub asset_hub: Option<String>,
+}
+
+impl RpcEndpoints {
+    pub fn is_empty(&self) -> bool {
+        self.relay.is_none() && self.people.is_none() && self.asset_hub.is_none()
+    }
+
+    /// Returns the endpoints to connect to the relay chain, in order
+    pub fn relay_urls(&self, runtime: SupportedRelayRuntime) -> Vec<String> {
+        urls_or_default(&self.relay, runtime.rpc_urls())
+    }
+
+    /// Returns the endpoints to connect to the people chain, in order
+    pub fn people_urls(&self, runtime: SupportedRelayRuntime) -> Vec<String> {
+        urls_or_default(&self.people, runtime.people_rpc_urls())
+    }
+
+    /// Returns the endpoints to connect to the asset hub chain, in order
+    pub fn asset_hub_urls(&self, runtime: SupportedRelayRuntime) -> Vec<String> {
+        urls_or_default(&self.asset_hub, runtime.asset_hub_rpc_urls())
+    }
+}
+
+fn urls_or_default(custom: &Option<String>, default: Vec<&'static str>) -> Vec<String> {
+    match custom {
+        Some(url) => vec![url.clone()],
+        None => default.into_iter().map(String::from).collect(),
+    }
+}
+
+/// Follow the finalized blocks of the chain until the subscription drops, calling `on_block` with
+/// the number of every block. Returns why it dropped, so the connection can be re-established.
+pub async fn watch_finalized_blocks(
+    api: &OnlineClient<PolkadotConfig>,
+    mut on_block: impl FnMut(BlockNumber),
+) -> String {
+    let mut blocks_sub = match api.blocks().subscribe_finalized().await {
+        Ok(blocks_sub) => blocks_sub,
+        Err(e) => {
+            error!("error: {:?}", e);
+            return e.to_string();
+        }
+    };
+
+    while let Some(result) = blocks_sub.next().await {
+        match result {
+            Ok(block) => on_block(block.number()),
+            Err(e) => {
+                error!("{}", e);
+            }
+        }
+    }
+    "Finalized blocks subscription ended".into()
+}
+
+/// Validates that the url is a websocket endpoint, e.g. `wss://rpc.example.io` or `ws://127.0.0.1:9944`
+pub fn is_valid_rpc_url(url: &str) -> bool {
+    ["ws://", "wss://"]
+        .iter()
+        .any(|scheme| url.len() > scheme.len() && url.starts_with(scheme))
+}
+
 /// NetworkState is a shared state between all components.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct NetworkState {
@@ -46,10 +145,24 @@ pub struct NetworkState {
     pub fetches_counter: u32,
     /// Use light client connection to the network or switch to RPC via an IBP provider.
     pub use_light_client_as_network_provider: bool,
+    /// Custom RPC endpoints used instead of the default ones.
+    pub endpoints: RpcEndpoints,
+    /// Requests sent to the worker still waiting for completion.
+    pub requests: BTreeMap<RequestId, RequestKind>,
+    /// The last request that failed and the reason why.
+    pub last_error: Option<(RequestKind, WorkerError)>,
+    /// The last connection event reported by the worker.
+    pub connection: Option<ConnectionEvent>,
+    /// Compatibility of each live runtime with the one the app was built for.
+    pub compatibility: Vec<RuntimeCompatibility>,
 }
 
 impl NetworkState {
-    pub fn new(runtime: SupportedRelayRuntime, use_light_client: bool) -> Self {
+    pub fn new(
+        runtime: SupportedRelayRuntime,
+        use_light_client: bool,
+        endpoints: RpcEndpoints,
+    ) -> Self {
         // Generate a unique subscription_id
         let mut rng = rand::thread_rng();
         let subscription_id = rng.gen::<SubscriptionId>();
@@ -60,7 +173,13 @@ impl NetworkState {
             runtime,
             finalized_block_number: None,
             fetches_counter: 0,
-            use_light_client_as_network_provider: use_light_client,
+            use_light_client_as_network_provider: use_light_client
+                && runtime.supports_light_client(),
+            endpoints,
+            requests: BTreeMap::new(),
+            last_error: None,
+            connection: None,
+            compatibility: Vec::new(),
         }
     }
 
@@ -68,6 +187,10 @@ impl NetworkState {
         self.status == NetworkStatus::Initializing
     }
 
+    pub fn is_reconnecting(&self) -> bool {
+        self.status == NetworkStatus::Reconnecting
+    }
+
     pub fn is_active(&self) -> bool {
         self.status == NetworkStatus::Active
     }
@@ -76,8 +199,11 @@ impl NetworkState {
         self.fetches_counter > 0
     }
 
-    pub fn is_initializing_description(&self) -> &'static str {
-        "Initializing network. Hang tight..."
+    pub fn is_initializing_description(&self) -> String {
+        match &self.connection {
+            Some(event) => format!("{event} Hang tight..."),
+            None => "Initializing network. Hang tight...".into(),
+        }
     }
 
     pub fn is_fetching_description(&self) -> &'static str {
@@ -96,11 +222,65 @@ impl NetworkState {
         self.runtime.to_string().to_lowercase()
     }
 
-    pub fn provider_description(&self) -> &'static str {
-        if self.use_light_client_as_network_provider {
+    pub fn error_description(&self) -> Option<String> {
+        self.last_error
+            .as_ref()
+            .map(|(kind, err)| format!("{kind} request failed. {err}"))
+    }
+
+    pub fn connection_description(&self) -> Option<String> {
+        if self.is_active() {
+            return None;
+        }
+        self.connection.as_ref().map(|event| event.to_string())
+    }
+
+    /// Keep only the latest compatibility reported for each chain
+    pub fn update_compatibility(&mut self, compatibility: RuntimeCompatibility) {
+        self.compatibility
+            .retain(|c| c.chain != compatibility.chain);
+        self.compatibility.push(compatibility);
+    }
+
+    pub fn is_outdated(&self) -> bool {
+        self.compatibility.iter().any(|c| c.is_outdated())
+    }
+
+    pub fn is_claim_blocked(&self) -> bool {
+        self.compatibility.iter().any(|c| c.is_claim_blocked())
+    }
+
+    pub fn outdated_description(&self) -> Option<String> {
+        let descriptions = self
+            .compatibility
+            .iter()
+            .filter(|c| c.is_outdated())
+            .map(|c| c.description())
+            .collect::<Vec<_>>();
+        if descriptions.is_empty() {
+            None
+        } else {
+            Some(descriptions.join(" "))
+        }
+    }
+
+    pub fn provider_description(&self) -> String {
+        let provider = if self.use_light_client_as_network_provider {
             "Connected via light client"
+        } else if !self.endpoints.is_empty() {
+            "Connected via RPC (custom endpoints)"
         } else {
             "Connected via RPC (IBP provider)"
+        };
+        if self.is_dynamic() {
+            format!("{provider}, decoding runtimes from the live metadata")
+        } else {
+            provider.to_string()
         }
     }
+
+    /// Whether any chain is decoded from the live metadata instead of the generated code
+    pub fn is_dynamic(&self) -> bool {
+        self.compatibility.iter().any(|c| c.dynamic)
+    }
 }
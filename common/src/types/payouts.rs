@@ -0,0 +1,293 @@
+use crate::runtimes::{support::SupportedRelayRuntime, utils::amount_human};
+use crate::types::{
+    child_bounties::{ChildBountyId, ParentBountyId},
+    worker::BlockNumber,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum_macros::Display;
+use subxt::utils::AccountId32;
+use yew::{html, html::IntoPropValue, Html};
+
+/// Maximum number of finalized blocks walked back, either to index the payouts of accounts never
+/// indexed before or to resume from the last block indexed
+pub const PAYOUTS_HISTORY_MAX_BLOCKS: u32 = 14_400;
+
+/// Maximum number of payouts kept, the oldest ones being dropped first
+pub const PAYOUTS_HISTORY_MAX_PAYOUTS: usize = 1_000;
+
+/// Number of blocks indexed before the payouts found are sent, so progress is kept along the way
+pub const PAYOUTS_HISTORY_CHUNK_BLOCKS: u32 = 100;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Payout {
+    pub kind: PayoutKind,
+    pub parent_id: ParentBountyId,
+    pub child_id: ChildBountyId,
+    pub beneficiary: AccountId32,
+    /// amount paid to the beneficiary, only available once `Claimed`
+    pub amount: Option<u128>,
+    pub block_number: BlockNumber,
+    /// hex encoded hash of the extrinsic that emitted the event
+    pub extrinsic_hash: String,
+}
+
+impl Payout {
+    pub fn amount_human(&self, runtime: SupportedRelayRuntime) -> String {
+        match self.amount {
+            Some(amount) => amount_human(amount, runtime.decimals().into()),
+            None => "-".into(),
+        }
+    }
+
+    pub fn is_beneficiary(&self, account: &AccountId32) -> bool {
+        &self.beneficiary == account
+    }
+
+    pub fn extrinsic_hash_compact(&self) -> String {
+        let h = &self.extrinsic_hash;
+        if h.len() > 14 {
+            [&h[..8], &h[h.len() - 6..]].join("...")
+        } else {
+            h.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Display, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PayoutKind {
+    /// Child bounty awarded to the beneficiary (`ChildBounties::Awarded`)
+    Awarded,
+    /// Child bounty paid out to the beneficiary (`ChildBounties::Claimed`)
+    Claimed,
+}
+
+impl IntoPropValue<Html> for PayoutKind {
+    fn into_prop_value(self) -> Html {
+        html! {<>{self.to_string()}</>}
+    }
+}
+
+pub type Payouts = Vec<Payout>;
+
+/// The kind, parent id, child id, beneficiary and payout (if claimed) of a child bounties event
+pub type PayoutEvent = (
+    PayoutKind,
+    ParentBountyId,
+    ChildBountyId,
+    AccountId32,
+    Option<u128>,
+);
+
+/// Why blocks older than the ones indexed were not walked back
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PayoutsHistoryLimit {
+    /// Only the last `PAYOUTS_HISTORY_MAX_BLOCKS` finalized blocks are walked back
+    MaxBlocks,
+    /// The node no longer keeps the state of older blocks
+    Pruned,
+}
+
+impl std::fmt::Display for PayoutsHistoryLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MaxBlocks => write!(
+                f,
+                "only the last {PAYOUTS_HISTORY_MAX_BLOCKS} blocks are indexed"
+            ),
+            Self::Pruned => write!(f, "older blocks are pruned by the node"),
+        }
+    }
+}
+
+/// Ranges of finalized blocks already indexed, sorted and without overlaps
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IndexedBlocks(Vec<(BlockNumber, BlockNumber)>);
+
+impl IndexedBlocks {
+    /// Add the blocks from `from` to `to` (both included), merging the ranges they touch
+    pub fn insert(&mut self, from: BlockNumber, to: BlockNumber) {
+        let (mut from, mut to) = (from.min(to), from.max(to));
+        self.0.retain(|&(start, end)| {
+            let touches = start <= to.saturating_add(1) && from <= end.saturating_add(1);
+            if touches {
+                from = from.min(start);
+                to = to.max(end);
+            }
+            !touches
+        });
+        let index = self.0.partition_point(|&(start, _)| start < from);
+        self.0.insert(index, (from, to));
+    }
+
+    /// Forget the blocks older than the one given
+    pub fn drop_before(&mut self, block_number: BlockNumber) {
+        self.0.retain(|&(_, end)| end >= block_number);
+        if let Some((start, _)) = self.0.first_mut() {
+            *start = (*start).max(block_number);
+        }
+    }
+
+    /// Last block indexed
+    pub fn last(&self) -> Option<BlockNumber> {
+        self.0.last().map(|&(_, end)| end)
+    }
+
+    /// First block of the range ending with the last block indexed
+    pub fn since(&self) -> Option<BlockNumber> {
+        self.0.last().map(|&(start, _)| start)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PayoutsHistory {
+    /// finalized blocks already indexed for each account
+    #[serde(default)]
+    pub indexed: BTreeMap<AccountId32, IndexedBlocks>,
+    /// oldest block the last walk back could reach, and why it stopped there
+    #[serde(default)]
+    pub limit: Option<(BlockNumber, PayoutsHistoryLimit)>,
+    pub payouts: Payouts,
+}
+
+impl PayoutsHistory {
+    /// Merge the payouts found from block `from` to block `to`, keeping only the ones from the
+    /// accounts given, which are recorded as indexed over those blocks
+    pub fn merge(
+        &mut self,
+        payouts: Payouts,
+        from: BlockNumber,
+        to: BlockNumber,
+        accounts: &[AccountId32],
+    ) {
+        for payout in payouts {
+            if accounts.iter().any(|acc| payout.is_beneficiary(acc))
+                && !self.payouts.contains(&payout)
+            {
+                self.payouts.push(payout);
+            }
+        }
+        self.payouts
+            .sort_by(|a, b| b.block_number.cmp(&a.block_number));
+
+        for account in accounts {
+            self.indexed
+                .entry(account.clone())
+                .or_default()
+                .insert(from, to);
+        }
+
+        self.trim(PAYOUTS_HISTORY_MAX_PAYOUTS);
+    }
+
+    /// Keep at most `max_payouts`, dropping the oldest blocks along with the payouts found in them,
+    /// so the blocks still recorded as indexed keep all their payouts
+    pub fn trim(&mut self, max_payouts: usize) {
+        let Some(dropped) = self.payouts.get(max_payouts) else {
+            return;
+        };
+        let since = dropped.block_number + 1;
+
+        self.payouts.retain(|payout| payout.block_number >= since);
+        for blocks in self.indexed.values_mut() {
+            blocks.drop_before(since);
+        }
+        self.indexed.retain(|_, blocks| blocks.last().is_some());
+    }
+
+    /// Block to resume indexing from, i.e. the last one indexed for all the accounts given
+    /// that were indexed before
+    pub fn resume_from(&self, accounts: &[AccountId32]) -> Option<BlockNumber> {
+        accounts
+            .iter()
+            .filter_map(|account| self.indexed.get(account))
+            .filter_map(|blocks| blocks.last())
+            .min()
+    }
+
+    /// Whether any of the accounts given was never indexed
+    pub fn has_unindexed(&self, accounts: &[AccountId32]) -> bool {
+        accounts
+            .iter()
+            .any(|account| !self.indexed.contains_key(account))
+    }
+
+    /// First block of the latest range indexed for the account
+    pub fn indexed_since(&self, account: &AccountId32) -> Option<BlockNumber> {
+        self.indexed.get(account).and_then(|blocks| blocks.since())
+    }
+
+    pub fn by_account(&self, account: &AccountId32) -> Payouts {
+        self.payouts
+            .iter()
+            .filter(|payout| payout.is_beneficiary(account))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexed_blocks_merge_touching_ranges() {
+        let mut blocks = IndexedBlocks::default();
+        blocks.insert(201, 300);
+        blocks.insert(101, 200);
+        blocks.insert(10, 20);
+        assert_eq!(blocks, IndexedBlocks(vec![(10, 20), (101, 300)]));
+
+        blocks.insert(301, 301);
+        assert_eq!(blocks.since(), Some(101));
+        assert_eq!(blocks.last(), Some(301));
+
+        blocks.insert(15, 150);
+        assert_eq!(blocks, IndexedBlocks(vec![(10, 301)]));
+    }
+
+    #[test]
+    fn accounts_followed_later_are_indexed_on_their_own() {
+        let alice = AccountId32([1; 32]);
+        let bob = AccountId32([2; 32]);
+
+        let mut history = PayoutsHistory::default();
+        history.merge(Payouts::new(), 101, 200, &[alice.clone()]);
+        assert_eq!(
+            history.resume_from(&[alice.clone(), bob.clone()]),
+            Some(200)
+        );
+        assert!(history.has_unindexed(&[alice.clone(), bob.clone()]));
+
+        history.merge(Payouts::new(), 201, 201, &[alice.clone(), bob.clone()]);
+        assert_eq!(history.indexed_since(&alice), Some(101));
+        assert_eq!(history.indexed_since(&bob), Some(201));
+        assert!(!history.has_unindexed(&[alice, bob]));
+    }
+
+    #[test]
+    fn trim_drops_the_oldest_blocks_with_their_payouts() {
+        let alice = AccountId32([1; 32]);
+        let payout = |child_id, block_number| Payout {
+            kind: PayoutKind::Claimed,
+            parent_id: 1,
+            child_id,
+            beneficiary: alice.clone(),
+            amount: Some(1),
+            block_number,
+            extrinsic_hash: String::new(),
+        };
+
+        let mut history = PayoutsHistory::default();
+        history.merge(
+            vec![payout(1, 10), payout(2, 20), payout(3, 20), payout(4, 30)],
+            1,
+            40,
+            &[alice.clone()],
+        );
+        history.trim(2);
+
+        assert_eq!(history.payouts, vec![payout(4, 30)]);
+        assert_eq!(history.indexed_since(&alice), Some(21));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::errors::ClaimitError;
+use crate::runtimes::utils::{compact, get_proxies_storage_key};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use subxt::{ext::codec::Decode, utils::AccountId32, OnlineClient, PolkadotConfig};
+
+/// Proxy types allowed to dispatch `ChildBounties::claim_child_bounty` on behalf of the proxied account
+pub const CLAIM_PROXY_TYPES: [&str; 3] = ["Any", "NonTransfer", "Governance"];
+
+/// An account the signer is a proxy of, and so can claim on behalf of via `Proxy::proxy`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Proxy {
+    /// The proxied account the claim is dispatched from
+    pub real: AccountId32,
+    pub proxy_type: String,
+    /// Number of blocks a call must be announced for before it can be dispatched
+    pub delay: u32,
+}
+
+impl Proxy {
+    /// Only proxies without an announcement delay and of a type that allows claiming are supported
+    pub fn can_claim(&self) -> bool {
+        self.delay == 0 && CLAIM_PROXY_TYPES.contains(&self.proxy_type.as_str())
+    }
+
+    pub fn description(&self) -> String {
+        format!("{} · {}", compact(&self.real), self.proxy_type)
+    }
+}
+
+/// Find which of the accounts given the delegate is a proxy of, from `Proxy::Proxies` storage.
+///
+/// The storage is read by raw key, and the name of the proxy type (a runtime specific enum)
+/// is given by `proxy_type_name` from its index. When the delegate has more than one proxy
+/// type for the same account, the one that can claim is kept.
+pub async fn fetch_proxies(
+    api: &OnlineClient<PolkadotConfig>,
+    delegate: &AccountId32,
+    reals: Vec<AccountId32>,
+    proxy_type_name: impl Fn(u8) -> Option<String>,
+) -> Result<Vec<Proxy>, ClaimitError> {
+    let storage = api.storage().at_latest().await?;
+    let mut out = Vec::new();
+
+    for real in reals.into_iter().filter(|real| real != delegate) {
+        let Some(bytes) = storage.fetch_raw(get_proxies_storage_key(&real)).await? else {
+            continue;
+        };
+
+        // (BoundedVec<ProxyDefinition { delegate, proxy_type, delay }>, deposit)
+        let (definitions, _deposit) =
+            <(Vec<(AccountId32, u8, u32)>, u128)>::decode(&mut &bytes[..])
+                .map_err(subxt::Error::from)?;
+
+        out.extend(
+            definitions
+                .into_iter()
+                .filter(|(account, _, _)| account == delegate)
+                .map(|(_, proxy_type, delay)| Proxy {
+                    real: real.clone(),
+                    proxy_type: proxy_type_name(proxy_type)
+                        .unwrap_or_else(|| format!("ProxyType({proxy_type})")),
+                    delay,
+                }),
+        );
+    }
+
+    out.sort_by_key(|proxy| !proxy.can_claim());
+    let mut seen = BTreeSet::new();
+    out.retain(|proxy| seen.insert(proxy.real.clone()));
+
+    Ok(out)
+}
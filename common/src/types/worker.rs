@@ -1,37 +1,181 @@
+use crate::errors::WorkerError;
 use crate::runtimes::support::SupportedRelayRuntime;
 use crate::types::{
     accounts::Balance,
-    child_bounties::{ChildBounties, ChildBountiesIds},
-    network::SubscriptionId,
+    batches::BatchMode,
+    bounties::ParentBounties,
+    child_bounties::{ChildBounties, ChildBountiesIds, ChildBounty, ChildBountyId},
+    claims::{ClaimOrigin, ClaimOutcome, TxProgress},
+    compatibility::RuntimeCompatibility,
+    extensions::{MetadataDef, SigningParams, SigningPayload},
+    fees::{FeeAsset, FeeEstimate},
+    multisigs::{Multisig, MultisigStatus},
+    network::{ConnectionEvent, RpcEndpoints, SubscriptionId},
+    payouts::{Payouts, PayoutsHistoryLimit},
+    proxies::Proxy,
 };
 use serde::{Deserialize, Serialize};
+use strum_macros::Display;
 use subxt::utils::AccountId32;
 
 pub type BlockNumber = u32;
 ///  SignerAddress must be ss58 formatted address as string
 pub type SignerAddress = String;
+/// RequestId correlates an input request with all its outputs
+pub type RequestId = u32;
 /// UseLightClient instructs worker to start a light client connection to the network
 pub type UseLightClient = bool;
+/// MortalityPeriod is the number of blocks a transaction stays valid for
+pub type MortalityPeriod = u64;
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Input {
-    Start(SubscriptionId, SupportedRelayRuntime, UseLightClient),
-    FetchChildBounties,
-    FetchAccountBalance(AccountId32),
-    FetchAccountIdentity(AccountId32),
-    CreatePayloadTx(ChildBountiesIds, SignerAddress),
-    SignAndSubmitTx(ChildBountiesIds, SignerAddress, Vec<u8>),
+    Start(
+        SubscriptionId,
+        SupportedRelayRuntime,
+        UseLightClient,
+        RpcEndpoints,
+    ),
+    FetchChildBounties(RequestId),
+    SubscribeChildBounties(RequestId),
+    FetchParentBounties(RequestId),
+    FetchAccountBalance(RequestId, AccountId32),
+    FetchAccountIdentity(RequestId, AccountId32),
+    /// Find which of the accounts given the delegate is a proxy of
+    FetchProxies(RequestId, AccountId32, Vec<AccountId32>),
+    /// Read the balances of the account in the assets the claim fee can be paid with
+    FetchFeeAssetBalances(RequestId, AccountId32),
+    /// Index the payouts of the last finalized blocks, for accounts never indexed before
+    FetchPayoutsHistory(RequestId),
+    /// Index the payouts of every new finalized block, catching up since the block given first
+    SubscribePayoutsHistory(RequestId, Option<BlockNumber>),
+    SplitClaim(RequestId, ChildBountiesIds, BatchMode),
+    /// Read the approvals of the batch claiming the child bounties, as dispatched by the multisig
+    FetchMultisigStatus(RequestId, ChildBountiesIds, BatchMode, Multisig),
+    CreatePayloadTx(
+        RequestId,
+        ChildBountiesIds,
+        BatchMode,
+        ClaimOrigin,
+        SignerAddress,
+        MortalityPeriod,
+        Option<FeeAsset>,
+    ),
+    SignAndSubmitTx(
+        RequestId,
+        ChildBountiesIds,
+        BatchMode,
+        ClaimOrigin,
+        SignerAddress,
+        Vec<u8>,
+        SigningParams,
+        Option<FeeAsset>,
+    ),
+    /// Build the Asset Hub metadata definition to be provided to the wallet
+    FetchMetadataDef(RequestId),
     Finish,
 }
 
+impl Input {
+    /// Returns the request id and kind, if the input is a request
+    pub fn request(&self) -> Option<(RequestId, RequestKind)> {
+        match self {
+            Self::FetchChildBounties(id) => Some((*id, RequestKind::ChildBounties)),
+            Self::SubscribeChildBounties(id) => Some((*id, RequestKind::ChildBountiesUpdates)),
+            Self::FetchParentBounties(id) => Some((*id, RequestKind::ParentBounties)),
+            Self::FetchAccountBalance(id, _) => Some((*id, RequestKind::AccountBalance)),
+            Self::FetchAccountIdentity(id, _) => Some((*id, RequestKind::AccountIdentity)),
+            Self::FetchProxies(id, _, _) => Some((*id, RequestKind::Proxies)),
+            Self::FetchFeeAssetBalances(id, _) => Some((*id, RequestKind::FeeAssetBalances)),
+            Self::FetchPayoutsHistory(id) => Some((*id, RequestKind::PayoutsHistory)),
+            Self::SubscribePayoutsHistory(id, _) => Some((*id, RequestKind::PayoutsHistoryUpdates)),
+            Self::SplitClaim(id, _, _) => Some((*id, RequestKind::ClaimBatches)),
+            Self::FetchMultisigStatus(id, _, _, _) => Some((*id, RequestKind::MultisigStatus)),
+            Self::CreatePayloadTx(id, _, _, _, _, _, _) => Some((*id, RequestKind::TxPayload)),
+            Self::SignAndSubmitTx(id, _, _, _, _, _, _, _) => {
+                Some((*id, RequestKind::TxSubmission))
+            }
+            Self::FetchMetadataDef(id) => Some((*id, RequestKind::MetadataDef)),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Output {
     Active(SubscriptionId),
     BlockNumber(SubscriptionId, BlockNumber),
-    ChildBounties(ChildBounties),
-    AccountBalance(AccountId32, Balance),
-    AccountIdentity(AccountId32, Option<String>),
-    TxPayload(String),
-    TxCompleted(Vec<u32>),
-    Err(SubscriptionId),
+    ChildBounties(RequestId, ChildBounties),
+    ChildBountyAdded(RequestId, ChildBounty),
+    ChildBountyUpdated(RequestId, ChildBounty),
+    ChildBountyRemoved(RequestId, ChildBountyId),
+    ParentBounties(RequestId, ParentBounties),
+    AccountBalance(RequestId, AccountId32, Balance),
+    AccountIdentity(RequestId, AccountId32, Option<String>),
+    /// Accounts the delegate is a proxy of
+    Proxies(RequestId, AccountId32, Vec<Proxy>),
+    /// Balances of the account in the assets the claim fee can be paid with
+    FeeAssetBalances(RequestId, AccountId32, Vec<(FeeAsset, u128)>),
+    /// Payouts found from the first block to the last one given, both included
+    PayoutsHistory(RequestId, Payouts, BlockNumber, BlockNumber),
+    /// Blocks older than the one given could not be indexed
+    PayoutsHistoryLimit(RequestId, BlockNumber, PayoutsHistoryLimit),
+    /// Batches the claim is split in, to be signed and submitted in sequence
+    ClaimBatches(RequestId, Vec<ChildBountiesIds>),
+    /// Approvals of the batch to be signed on behalf of the multisig
+    MultisigStatus(RequestId, MultisigStatus),
+    TxPayload(
+        RequestId,
+        SigningPayload,
+        SigningParams,
+        Option<FeeEstimate>,
+    ),
+    /// Stage reached by the transaction submitted, streamed until it is finalized
+    TxProgress(RequestId, TxProgress),
+    /// Outcome of every child bounty claimed in the transaction finalized
+    TxCompleted(RequestId, Vec<ClaimOutcome>),
+    /// Asset Hub metadata definition, to be provided to the wallet
+    MetadataDef(RequestId, MetadataDef),
+    /// All responses of a streamed request have been sent
+    Completed(RequestId),
+    /// The request failed, no more responses will be sent
+    Failed(RequestId, WorkerError),
+    /// Compatibility of a live runtime with the one the app was built for
+    Compatibility(SubscriptionId, RuntimeCompatibility),
+    /// Progress of the connection to the network endpoints
+    Connection(SubscriptionId, ConnectionEvent),
+    Err(SubscriptionId, WorkerError),
+}
+
+/// Identifies what a request is about, so the app can tell which one failed
+#[derive(Clone, Debug, Display, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RequestKind {
+    #[strum(to_string = "Child bounties")]
+    ChildBounties,
+    #[strum(to_string = "Child bounties updates")]
+    ChildBountiesUpdates,
+    #[strum(to_string = "Parent bounties")]
+    ParentBounties,
+    #[strum(to_string = "Account balance")]
+    AccountBalance,
+    #[strum(to_string = "Account identity")]
+    AccountIdentity,
+    #[strum(to_string = "Proxies")]
+    Proxies,
+    #[strum(to_string = "Fee asset balances")]
+    FeeAssetBalances,
+    #[strum(to_string = "Payouts history")]
+    PayoutsHistory,
+    #[strum(to_string = "Payouts history updates")]
+    PayoutsHistoryUpdates,
+    #[strum(to_string = "Claim batches")]
+    ClaimBatches,
+    #[strum(to_string = "Multisig status")]
+    MultisigStatus,
+    #[strum(to_string = "Transaction payload")]
+    TxPayload,
+    #[strum(to_string = "Transaction submission")]
+    TxSubmission,
+    #[strum(to_string = "Metadata definition")]
+    MetadataDef,
 }
@@ -0,0 +1,1053 @@
+//! Runs the Polkadot Asset Hub runtime against the mock RPC backend, and checks the runtime decoded
+//! from the live metadata behaves the same.
+
+use claimit_common::errors::ClaimitError;
+use claimit_common::mock::MockRpcClient;
+use claimit_common::runtimes::dynamic::DynamicAssetHub;
+use claimit_common::runtimes::support::SupportedRelayRuntime;
+use claimit_common::runtimes::traits::AssetHubRuntime;
+use claimit_common::types::batches::{BatchMode, MAX_CALLS_PER_BATCH};
+use claimit_common::types::bounties::{ParentBounty, ParentBountyStatus};
+use claimit_common::types::child_bounties::{ChildBounties, ChildBountiesIds, Status};
+use claimit_common::types::claims::{ClaimOrigin, ClaimOutcome, ClaimResult, TxProgress, TxStage};
+use claimit_common::types::compatibility::RuntimeCompatibility;
+use claimit_common::types::extensions::{
+    fetch_metadata_hash, MetadataDef, Mortality, SigningParams, SigningPayload,
+    TransactionExtensions,
+};
+use claimit_common::types::fees::{fetch_fee_asset_balances, FeeAsset, FeeEstimate, InclusionFee};
+use claimit_common::types::multisigs::{Multisig, Timepoint};
+use claimit_common::types::network::watch_finalized_blocks;
+use claimit_common::types::payouts::{Payout, PayoutKind, Payouts, PayoutsHistoryLimit};
+use claimit_common::types::proxies::Proxy;
+use claimit_common::types::worker::{BlockNumber, Output};
+use claimit_polkadot_asset_hub::polkadot_asset_hub::PolkadotAssetHub;
+use futures::{executor::block_on, StreamExt};
+use serde_json::Value as JsonValue;
+use std::str::FromStr;
+use subxt::{
+    dynamic::Value,
+    ext::codec::{Decode, Encode},
+    utils::{AccountId32, Era, H256},
+    OnlineClient, PolkadotConfig,
+};
+use yew::platform::pinned::mpsc::unbounded;
+
+const METADATA: &[u8] =
+    include_bytes!("../artifacts/metadata/polkadot_asset_hub_metadata_small.scale");
+const ASSET_HUB: &str = include_str!("fixtures/asset_hub.json");
+const CLAIM_COMPLETED_WITH_ERRORS: &str = include_str!("fixtures/claim_completed_with_errors.json");
+const CLAIM_FAILED: &str = include_str!("fixtures/claim_failed.json");
+const HEAVY_CLAIM: &str = include_str!("fixtures/heavy_claim.json");
+const PROXIES: &str = include_str!("fixtures/proxies.json");
+const MULTISIGS: &str = include_str!("fixtures/multisigs.json");
+const FEE_ASSETS: &str = include_str!("fixtures/fee_assets.json");
+const PARENT_BOUNTIES: &str = include_str!("fixtures/parent_bounties.json");
+const CHILD_BOUNTY_UPDATES: &str = include_str!("fixtures/child_bounty_updates.json");
+const PAYOUTS: &str = include_str!("fixtures/payouts.json");
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+const DOT: u128 = 10_000_000_000;
+const FINALIZED_HEAD: H256 = H256::repeat_byte(0x22);
+
+async fn client(fixtures: &[&str]) -> OnlineClient<PolkadotConfig> {
+    fixtures
+        .iter()
+        .fold(MockRpcClient::new(METADATA), |mock, fixture| {
+            mock.with_fixture(fixture)
+        })
+        .client()
+        .await
+        .expect("mock client")
+}
+
+fn account(address: &str) -> AccountId32 {
+    AccountId32::from_str(address).unwrap()
+}
+
+/// A dummy sr25519 signature, the mock does not verify it
+fn signature() -> Vec<u8> {
+    let mut signature = vec![1u8];
+    signature.extend([0u8; 64]);
+    signature
+}
+
+fn finalized_head_mortality() -> Mortality {
+    Mortality {
+        period: 64,
+        checkpoint_number: 100,
+        checkpoint_hash: FINALIZED_HEAD,
+    }
+}
+
+fn signing_params() -> SigningParams {
+    SigningParams {
+        mortality: finalized_head_mortality(),
+        account_nonce: 0,
+        metadata_hash: None,
+    }
+}
+
+async fn fetch_child_bounties<R: AssetHubRuntime>() -> ChildBounties {
+    let api = client(&[ASSET_HUB]).await;
+    let (tx, rx) = unbounded();
+    R::fetch_child_bounties(&api, 1, tx).await.unwrap();
+
+    let mut child_bounties = ChildBounties::new();
+    for output in rx.collect::<Vec<_>>().await {
+        match output {
+            Output::ChildBounties(1, chunk) => child_bounties.extend(chunk),
+            _ => panic!("unexpected output"),
+        }
+    }
+    child_bounties
+}
+
+#[test]
+fn fetch_child_bounties_with_descriptions() {
+    block_on(async {
+        let child_bounties = fetch_child_bounties::<PolkadotAssetHub>().await;
+
+        // The child bounty without a description is left out
+        assert_eq!(
+            child_bounties.keys().copied().collect::<Vec<_>>(),
+            vec![10, 11, 12, 20]
+        );
+
+        let added = &child_bounties[&10];
+        assert_eq!(added.parent_id, 1);
+        assert_eq!(added.description, "Translate docs");
+        assert_eq!(added.status, Status::Added);
+        assert_eq!(added.curator, None);
+
+        let active = &child_bounties[&11];
+        assert_eq!(active.status, Status::Active);
+        assert_eq!(active.curator, Some(account(BOB)));
+
+        let pending = &child_bounties[&12];
+        assert_eq!(pending.status, Status::Pending);
+        assert_eq!(pending.value, 300 * DOT);
+        assert_eq!(pending.fee, DOT);
+        assert_eq!(pending.beneficiary, Some(account(ALICE)));
+        assert_eq!(pending.unlock_at, Some(100));
+    });
+}
+
+#[test]
+fn dynamic_runtime_decodes_the_same_child_bounties() {
+    block_on(async {
+        assert_eq!(
+            fetch_child_bounties::<DynamicAssetHub>().await,
+            fetch_child_bounties::<PolkadotAssetHub>().await
+        );
+    });
+}
+
+async fn subscribe_child_bounties<R: AssetHubRuntime>() -> Vec<Output> {
+    let api = client(&[ASSET_HUB, CHILD_BOUNTY_UPDATES]).await;
+    let (tx, rx) = unbounded();
+    // The subscription ends after the last finalized head of the fixture
+    assert!(R::subscribe_child_bounties(&api, 1, tx).await.is_err());
+    rx.collect().await
+}
+
+#[test]
+fn child_bounties_changes_on_every_finalized_block() {
+    block_on(async {
+        let outputs = subscribe_child_bounties::<PolkadotAssetHub>().await;
+
+        // The first block only sets the reference, the next one awards 11, adds 20 back and
+        // drops 12 once claimed
+        assert_eq!(outputs.len(), 3, "unexpected outputs {outputs:?}");
+        let Output::ChildBountyUpdated(1, awarded) = &outputs[0] else {
+            panic!("unexpected output {:?}", outputs[0]);
+        };
+        let Output::ChildBountyAdded(1, added) = &outputs[1] else {
+            panic!("unexpected output {:?}", outputs[1]);
+        };
+        assert_eq!(outputs[2], Output::ChildBountyRemoved(1, 12));
+
+        assert_eq!(awarded.id, 11);
+        assert_eq!(awarded.description, "Run a workshop");
+        assert_eq!(awarded.status, Status::Pending);
+        assert_eq!(awarded.beneficiary, Some(account(ALICE)));
+        assert_eq!(awarded.unlock_at, Some(200));
+        assert_eq!((added.parent_id, added.id), (2, 20));
+        assert_eq!(added.description, "Host a meetup");
+
+        assert_eq!(subscribe_child_bounties::<DynamicAssetHub>().await, outputs);
+    });
+}
+
+#[test]
+fn fetch_parent_bounties_with_remaining_value() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, PARENT_BOUNTIES]).await;
+
+        let parent_bounties = PolkadotAssetHub::fetch_parent_bounties(&api).await.unwrap();
+        assert_eq!(
+            parent_bounties[&1],
+            ParentBounty {
+                id: 1,
+                description: "Polkadot meetups".to_string(),
+                value: 1_000 * DOT,
+                // Whatever is still free in the bounty account
+                remaining_value: 400 * DOT,
+                fee: 10 * DOT,
+                status: ParentBountyStatus::Active,
+                curator: Some(account(BOB)),
+            }
+        );
+
+        // Funded, but with no description nor curator yet
+        let funded = &parent_bounties[&2];
+        assert_eq!(funded.status, ParentBountyStatus::Funded);
+        assert_eq!(funded.description, "");
+        assert_eq!(funded.remaining_value, 0);
+        assert_eq!(funded.curator, None);
+
+        let dynamic_parent_bounties = DynamicAssetHub::fetch_parent_bounties(&api).await.unwrap();
+        assert_eq!(dynamic_parent_bounties, parent_bounties);
+    });
+}
+
+#[test]
+fn fetch_account_balance() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        let balance = PolkadotAssetHub::fetch_account_balance(&api, account(ALICE))
+            .await
+            .unwrap();
+        assert_eq!(balance.free, 1_000 * DOT);
+        assert_eq!(balance.reserved, 5 * DOT);
+
+        let dynamic_balance = DynamicAssetHub::fetch_account_balance(&api, account(ALICE))
+            .await
+            .unwrap();
+        assert_eq!(dynamic_balance.free, balance.free);
+        assert_eq!(dynamic_balance.reserved, balance.reserved);
+
+        assert!(PolkadotAssetHub::fetch_account_balance(&api, account(BOB))
+            .await
+            .is_err());
+    });
+}
+
+/// Create the payload of the claim on Polkadot, mortal for 64 blocks
+async fn create_payload_tx<R: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+    origin: ClaimOrigin,
+    signer: &str,
+    fee_asset: Option<FeeAsset>,
+) -> (SigningPayload, SigningParams, Option<FeeEstimate>) {
+    R::create_payload_tx(
+        api,
+        SupportedRelayRuntime::Polkadot,
+        child_bounties_ids,
+        mode,
+        origin,
+        signer.to_string(),
+        64,
+        fee_asset,
+    )
+    .await
+    .unwrap()
+}
+
+#[test]
+fn create_payload_tx_is_mortal_from_the_finalized_head() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        let (payload, params, _) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::ForceBatch,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+        assert_eq!(params.mortality, finalized_head_mortality());
+
+        let payload: JsonValue = serde_json::from_str(&payload.json).unwrap();
+        assert_eq!(payload["blockHash"], format!("{:?}", FINALIZED_HEAD));
+        assert_eq!(payload["blockNumber"], "0x00000064");
+        assert_eq!(
+            payload["era"],
+            format!("0x{}", hex::encode(Era::mortal(64, 100).encode()))
+        );
+
+        let (_, dynamic_params, _) = create_payload_tx::<DynamicAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::ForceBatch,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+        assert_eq!(dynamic_params, params);
+    });
+}
+
+#[test]
+fn create_payload_tx_exports_the_signing_payload() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        let (payload, _, _) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+
+        let json: JsonValue = serde_json::from_str(&payload.json).unwrap();
+        assert_eq!(json["method"], payload.call_data_hex());
+        // ChildBounties::claim_child_bounty { parent_bounty_id: 1, child_bounty_id: 12 }
+        assert_eq!(payload.call_data_hex(), "0x42050430");
+        assert!(payload.signer_payload.starts_with(&payload.call_data));
+        assert_eq!(payload.genesis_hash, api.genesis_hash());
+
+        // A single frame: 0x00 ++ frames ++ index, then 0x53 ++ sr25519 ++ sign transaction
+        let frames = payload.uos_frames(&account(ALICE), "sr25519");
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0][..8],
+            [0x00, 0x00, 0x01, 0x00, 0x00, 0x53, 0x01, 0x02]
+        );
+        assert_eq!(frames[0][8..40], account(ALICE).0);
+        assert!(frames[0].ends_with(&api.genesis_hash().0));
+
+        // The transaction is decoded back into the call, prefixed by its length, and the extensions
+        let mut transaction = &frames[0][40..frames[0].len() - 32];
+        let call_data = Vec::<u8>::decode(&mut transaction).unwrap();
+        assert_eq!(call_data, payload.call_data);
+        assert_eq!(transaction, payload.extensions.as_slice());
+        assert_eq!(
+            [&call_data[..], transaction].concat(),
+            payload.signer_payload
+        );
+
+        let (dynamic_payload, _, _) = create_payload_tx::<DynamicAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+        assert_eq!(dynamic_payload.signer_payload, payload.signer_payload);
+    });
+}
+
+#[test]
+fn create_payload_tx_commits_to_the_metadata_hash() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+        let metadata_hash = fetch_metadata_hash(&api, SupportedRelayRuntime::Polkadot)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (payload, params, _) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+
+        let json: JsonValue = serde_json::from_str(&payload.json).unwrap();
+        assert_eq!(json["mode"], 1);
+        assert_eq!(json["metadataHash"], format!("{metadata_hash:?}"));
+        assert_eq!(payload.metadata_hash, Some(metadata_hash));
+        // Submitted with the same hash the payload commits to
+        assert_eq!(params.metadata_hash, Some(metadata_hash));
+        // The implicit data of CheckMetadataHash goes last: Some(metadata_hash)
+        assert!(payload
+            .signer_payload
+            .ends_with(&[[0x01].as_slice(), &metadata_hash.0].concat()));
+
+        // Another token commits to another hash
+        let kusama_hash = fetch_metadata_hash(&api, SupportedRelayRuntime::Kusama)
+            .await
+            .unwrap();
+        assert_ne!(kusama_hash, Some(metadata_hash));
+
+        // Encoded as subxt does when the mode is disabled
+        let call = subxt::dynamic::tx(
+            "ChildBounties",
+            "claim_child_bounty",
+            vec![Value::u128(1), Value::u128(12)],
+        );
+        let partial_signed = api
+            .tx()
+            .create_partial_offline(&call, params.mortality.tx_params(0))
+            .unwrap();
+        let extensions =
+            TransactionExtensions::new(&api, 0, &params.mortality, None, None).unwrap();
+        assert_eq!(
+            extensions.signer_payload(&payload.call_data),
+            partial_signed.signer_payload()
+        );
+    });
+}
+
+#[test]
+fn metadata_def_of_the_asset_hub() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        let metadata = MetadataDef::fetch(&api, SupportedRelayRuntime::Polkadot)
+            .await
+            .unwrap();
+        assert_eq!(metadata.chain, "Polkadot Asset Hub");
+        assert_eq!(metadata.genesis_hash, format!("{:?}", api.genesis_hash()));
+        assert_eq!(metadata.ss58_format, 0);
+        assert_eq!(metadata.spec_version, api.runtime_version().spec_version);
+        assert_eq!(
+            (metadata.token_decimals, metadata.token_symbol.as_str()),
+            (10, "DOT")
+        );
+        assert_eq!(metadata.icon, "polkadot");
+        // The metadata prefixed by its magic number, i.e. "meta"
+        assert!(metadata.raw_metadata.starts_with("0x6d657461"));
+
+        let json: JsonValue = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["ss58Format"], 0);
+        assert_eq!(json["types"], serde_json::json!({}));
+
+        let metadata = MetadataDef::fetch(&api, SupportedRelayRuntime::Westend)
+            .await
+            .unwrap();
+        assert_eq!(metadata.icon, "substrate");
+    });
+}
+
+#[test]
+fn create_payload_tx_estimates_the_fee() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        let (_, _, fee) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::ForceBatch,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+
+        let fee = fee.expect("fee estimate");
+        assert_eq!(fee.partial_fee, 180_000_000);
+        assert_eq!(
+            fee.inclusion_fee,
+            Some(InclusionFee {
+                base_fee: 100_000_000,
+                len_fee: 50_000_000,
+                adjusted_weight_fee: 30_000_000,
+            })
+        );
+        assert_eq!(fee.free_balance, 1_000 * DOT);
+        assert!(fee.is_covered());
+
+        let (_, _, dynamic_fee) = create_payload_tx::<DynamicAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::ForceBatch,
+            ClaimOrigin::Signer,
+            ALICE,
+            None,
+        )
+        .await;
+        assert_eq!(dynamic_fee, Some(fee));
+
+        // Bob has no account on chain to pay the fee with
+        let (_, _, fee) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::ForceBatch,
+            ClaimOrigin::Signer,
+            BOB,
+            None,
+        )
+        .await;
+        assert!(!fee.expect("fee estimate").is_covered());
+    });
+}
+
+#[test]
+fn fetch_fee_asset_balances_of_the_signer() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, FEE_ASSETS]).await;
+
+        let balances =
+            fetch_fee_asset_balances(&api, SupportedRelayRuntime::Polkadot, &account(ALICE))
+                .await
+                .unwrap();
+        assert_eq!(
+            balances,
+            vec![(FeeAsset::Usdt, 25_000_000), (FeeAsset::Usdc, 0)]
+        );
+
+        // Only USDT is offered on Kusama, and no asset on the test networks
+        let balances =
+            fetch_fee_asset_balances(&api, SupportedRelayRuntime::Kusama, &account(ALICE))
+                .await
+                .unwrap();
+        assert_eq!(balances, vec![(FeeAsset::Usdt, 25_000_000)]);
+        assert!(FeeAsset::supported(SupportedRelayRuntime::Paseo).is_empty());
+    });
+}
+
+#[test]
+fn create_payload_tx_pays_the_fee_in_an_asset() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, FEE_ASSETS]).await;
+        // { parents: 0, interior: X2(PalletInstance(50), GeneralIndex(1984)) }
+        let location = FeeAsset::Usdt.location();
+        assert_eq!(hex::encode(&location), "0002043205011f");
+
+        let (payload, _, fee) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Signer,
+            ALICE,
+            Some(FeeAsset::Usdt),
+        )
+        .await;
+
+        let json: JsonValue = serde_json::from_str(&payload.json).unwrap();
+        assert_eq!(json["assetId"], format!("0x{}", hex::encode(&location)));
+        // ChargeAssetTxPayment { tip: 0, asset_id: Some(location) }
+        let charge = [[0x00, 0x01].as_slice(), &location].concat();
+        assert!(payload
+            .signer_payload
+            .windows(charge.len())
+            .any(|window| window == charge));
+
+        // The native fee is quoted in the asset, and covered by the balance of the signer in it
+        let fee = fee.expect("fee estimate");
+        assert_eq!(fee.asset, Some(FeeAsset::Usdt));
+        assert_eq!(fee.partial_fee, 50_000);
+        assert_eq!(fee.inclusion_fee, None);
+        assert_eq!(fee.free_balance, 25_000_000);
+        assert!(fee.is_covered());
+
+        let (dynamic_payload, _, dynamic_fee) = create_payload_tx::<DynamicAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Signer,
+            ALICE,
+            Some(FeeAsset::Usdt),
+        )
+        .await;
+        assert_eq!(dynamic_payload.signer_payload, payload.signer_payload);
+        assert_eq!(dynamic_fee, Some(fee));
+
+        // Bob holds no USDC to pay the fee with
+        let (_, _, fee) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Signer,
+            BOB,
+            Some(FeeAsset::Usdc),
+        )
+        .await;
+        assert!(!fee.expect("fee estimate").is_covered());
+    });
+}
+
+#[test]
+fn fetch_proxies_of_the_signer() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, PROXIES]).await;
+        let reals = vec![account(ALICE), account(CHARLIE), account(BOB)];
+
+        let proxies = PolkadotAssetHub::fetch_proxies(&api, &account(BOB), reals.clone())
+            .await
+            .unwrap();
+        // Bob is a CancelProxy and a Governance proxy of Alice, only the latter can claim
+        assert_eq!(
+            proxies,
+            vec![
+                Proxy {
+                    real: account(ALICE),
+                    proxy_type: "Governance".to_string(),
+                    delay: 0,
+                },
+                Proxy {
+                    real: account(CHARLIE),
+                    proxy_type: "Any".to_string(),
+                    delay: 10,
+                },
+            ]
+        );
+        assert!(proxies[0].can_claim());
+        assert!(!proxies[1].can_claim());
+
+        let dynamic_proxies = DynamicAssetHub::fetch_proxies(&api, &account(BOB), reals)
+            .await
+            .unwrap();
+        assert_eq!(dynamic_proxies, proxies);
+
+        let proxies = PolkadotAssetHub::fetch_proxies(&api, &account(ALICE), vec![account(BOB)])
+            .await
+            .unwrap();
+        assert!(proxies.is_empty());
+    });
+}
+
+#[test]
+fn create_payload_tx_wraps_the_claim_in_proxy() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        let (payload, _, _) = create_payload_tx::<PolkadotAssetHub>(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            ClaimOrigin::Proxy(account(ALICE)),
+            BOB,
+            None,
+        )
+        .await;
+
+        let payload: JsonValue = serde_json::from_str(&payload.json).unwrap();
+        assert_eq!(payload["address"], BOB);
+        // Proxy::proxy { real: MultiAddress::Id(Alice), force_proxy_type: None, call }
+        let method = payload["method"].as_str().unwrap();
+        assert!(method.starts_with(&format!("0x2a0000{}00", hex::encode(account(ALICE).0))));
+    });
+}
+
+#[test]
+fn fetch_multisig_status_of_the_claim() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, MULTISIGS]).await;
+        let multisig =
+            Multisig::new(vec![account(ALICE), account(BOB), account(CHARLIE)], 2).unwrap();
+
+        // Alice already approved claiming child bounty 12
+        let status = PolkadotAssetHub::fetch_multisig_status(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            &multisig,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status.call_hash_hex(),
+            "0x9322cc1bf14a9cc43430d7256538d52c21a0d4901ff9b80624fdf26c1f5cd023"
+        );
+        assert_eq!(status.max_weight, (1_000_000_000, 10_000));
+        assert_eq!(status.approvals(), 1);
+        assert!(status.is_approved_by(&account(ALICE)));
+        assert_eq!(
+            status.pending.as_ref().map(|pending| pending.when),
+            Some(Timepoint {
+                height: 99,
+                index: 2
+            })
+        );
+
+        let dynamic_status = DynamicAssetHub::fetch_multisig_status(
+            &api,
+            vec![(1, 12)],
+            BatchMode::Single,
+            &multisig,
+        )
+        .await
+        .unwrap();
+        assert_eq!(dynamic_status, status);
+
+        // Bob's approval reaches the threshold, so it executes the call
+        let approval = status.approval(&multisig, &account(BOB));
+        assert!(approval.execute);
+
+        // Nobody approved claiming child bounty 13 yet
+        let status = PolkadotAssetHub::fetch_multisig_status(
+            &api,
+            vec![(1, 13)],
+            BatchMode::Single,
+            &multisig,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status.pending, None);
+        assert!(!status.approval(&multisig, &account(BOB)).execute);
+    });
+}
+
+#[test]
+fn create_payload_tx_wraps_the_claim_in_multisig() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, MULTISIGS]).await;
+        let multisig =
+            Multisig::new(vec![account(ALICE), account(BOB), account(CHARLIE)], 2).unwrap();
+        // threshold: 2, other_signatories: [Charlie, Alice]
+        let others = format!(
+            "020008{}{}",
+            hex::encode(account(CHARLIE).0),
+            hex::encode(account(ALICE).0)
+        );
+
+        for (child_bounty_id, prefix) in [
+            // Multisig::as_multi { threshold, other_signatories, maybe_timepoint: Some(99, 2), call, max_weight }
+            (12, format!("0x2901{others}01630000000200000042050430")),
+            // Multisig::approve_as_multi { threshold, other_signatories, maybe_timepoint: None, call_hash, max_weight }
+            (13, format!("0x2902{others}00")),
+        ] {
+            let status = PolkadotAssetHub::fetch_multisig_status(
+                &api,
+                vec![(1, child_bounty_id)],
+                BatchMode::Single,
+                &multisig,
+            )
+            .await
+            .unwrap();
+            let origin = ClaimOrigin::Multisig(status.approval(&multisig, &account(BOB)));
+
+            let (payload, _, _) = create_payload_tx::<PolkadotAssetHub>(
+                &api,
+                vec![(1, child_bounty_id)],
+                BatchMode::Single,
+                origin.clone(),
+                BOB,
+                None,
+            )
+            .await;
+
+            let payload: JsonValue = serde_json::from_str(&payload.json).unwrap();
+            assert_eq!(payload["address"], BOB);
+            let method = payload["method"].as_str().unwrap();
+            assert!(method.starts_with(&prefix));
+
+            let (dynamic_payload, _, _) = create_payload_tx::<DynamicAssetHub>(
+                &api,
+                vec![(1, child_bounty_id)],
+                BatchMode::Single,
+                origin,
+                BOB,
+                None,
+            )
+            .await;
+            let dynamic_payload: JsonValue = serde_json::from_str(&dynamic_payload.json).unwrap();
+            assert_eq!(dynamic_payload["method"], payload["method"]);
+        }
+    });
+}
+
+#[test]
+fn split_claim_in_batches() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+        let child_bounties_ids: Vec<(u32, u32)> = (0..60).map(|id| (1, id)).collect();
+
+        // Without the weight of a claim, batches fall back to the maximum number of calls
+        let batches =
+            PolkadotAssetHub::split_claim(&api, child_bounties_ids.clone(), BatchMode::ForceBatch)
+                .await
+                .unwrap();
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![MAX_CALLS_PER_BATCH, 60 - MAX_CALLS_PER_BATCH]
+        );
+
+        let batches = DynamicAssetHub::split_claim(&api, vec![(1, 12), (2, 20)], BatchMode::Single)
+            .await
+            .unwrap();
+        assert_eq!(batches, vec![vec![(1, 12)], vec![(2, 20)]]);
+
+        // A claim heavier than the maximum extrinsic weight still goes on its own
+        let api = client(&[ASSET_HUB, HEAVY_CLAIM]).await;
+        let batches =
+            PolkadotAssetHub::split_claim(&api, vec![(1, 12), (2, 20)], BatchMode::BatchAll)
+                .await
+                .unwrap();
+        assert_eq!(batches, vec![vec![(1, 12)], vec![(2, 20)]]);
+    });
+}
+
+/// Sign and submit the claim, also returning the progress streamed while the transaction is watched
+async fn sign_and_submit_tx<R: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+) -> (Result<Vec<ClaimOutcome>, ClaimitError>, Vec<TxProgress>) {
+    let (tx, rx) = unbounded();
+    let result = R::sign_and_submit_tx(
+        api,
+        1,
+        child_bounties_ids,
+        mode,
+        ClaimOrigin::Signer,
+        ALICE.to_string(),
+        signature(),
+        signing_params(),
+        None,
+        tx,
+    )
+    .await;
+
+    let progress = rx
+        .map(|output| match output {
+            Output::TxProgress(1, progress) => progress,
+            _ => panic!("unexpected output"),
+        })
+        .collect()
+        .await;
+    (result, progress)
+}
+
+#[test]
+fn claims_only_the_child_bounties_of_the_submitted_extrinsic() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, CLAIM_COMPLETED_WITH_ERRORS]).await;
+        let child_bounties_ids = vec![(1, 12), (2, 20)];
+
+        let (outcomes, _) = sign_and_submit_tx::<PolkadotAssetHub>(
+            &api,
+            child_bounties_ids.clone(),
+            BatchMode::ForceBatch,
+        )
+        .await;
+        assert_claimed_with_errors(&outcomes.unwrap());
+
+        let (outcomes, _) =
+            sign_and_submit_tx::<DynamicAssetHub>(&api, child_bounties_ids, BatchMode::ForceBatch)
+                .await;
+        assert_claimed_with_errors(&outcomes.unwrap());
+    });
+}
+
+fn assert_claimed_with_errors(outcomes: &[ClaimOutcome]) {
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!((outcomes[0].parent_id, outcomes[0].child_id), (1, 12));
+    assert_eq!(outcomes[0].result, ClaimResult::Claimed);
+    assert_eq!((outcomes[1].parent_id, outcomes[1].child_id), (2, 20));
+    assert!(
+        matches!(&outcomes[1].result, ClaimResult::Failed(reason) if reason.starts_with("ChildBounties::ParentBountyNotActive"))
+    );
+}
+
+#[test]
+fn streams_the_progress_of_the_submitted_extrinsic() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, CLAIM_COMPLETED_WITH_ERRORS]).await;
+        let block_hash = format!("{:?}", H256::repeat_byte(0x33));
+
+        for (_, progress) in [
+            sign_and_submit_tx::<PolkadotAssetHub>(&api, vec![(1, 12)], BatchMode::Single).await,
+            sign_and_submit_tx::<DynamicAssetHub>(&api, vec![(1, 12)], BatchMode::Single).await,
+        ] {
+            let stages: Vec<TxStage> = progress.iter().map(|p| p.stage).collect();
+            assert_eq!(
+                stages,
+                vec![
+                    TxStage::Validated,
+                    TxStage::Broadcast,
+                    TxStage::InBestBlock,
+                    TxStage::Finalized
+                ]
+            );
+            assert!(progress[0].block.is_none());
+            assert!(progress[1].block.is_none());
+            for p in &progress[2..] {
+                let block = p.block.as_ref().unwrap();
+                assert_eq!(
+                    (block.hash.clone(), block.number),
+                    (block_hash.clone(), 101)
+                );
+            }
+            // the extrinsic hash is known from the submission onwards
+            assert!(progress
+                .iter()
+                .all(|p| p.extrinsic_hash.len() == 66
+                    && p.extrinsic_hash == progress[0].extrinsic_hash));
+        }
+    });
+}
+
+#[test]
+fn failed_claim_fails_every_child_bounty() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, CLAIM_FAILED]).await;
+
+        let (outcomes, _) =
+            sign_and_submit_tx::<PolkadotAssetHub>(&api, vec![(1, 12)], BatchMode::ForceBatch)
+                .await;
+        let outcomes = outcomes.unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert!(
+            matches!(&outcomes[0].result, ClaimResult::Failed(reason) if reason.starts_with("Bad origin"))
+        );
+
+        let (outcomes, _) =
+            sign_and_submit_tx::<DynamicAssetHub>(&api, vec![(1, 12)], BatchMode::Single).await;
+        assert!(outcomes
+            .unwrap()
+            .iter()
+            .all(|outcome| !outcome.is_claimed()));
+    });
+}
+
+/// Payouts in block 101: child bounty 12 claimed by Alice in `Utility::batch`, then child bounty
+/// 21 awarded to Bob in `Utility::batch_all`
+fn payouts_of_block_101() -> Payouts {
+    vec![
+        Payout {
+            kind: PayoutKind::Claimed,
+            parent_id: 1,
+            child_id: 12,
+            beneficiary: account(ALICE),
+            amount: Some(299 * DOT),
+            block_number: 101,
+            extrinsic_hash: "0x20696185e6ba074c27735cbef93f9acdd5c31f175e927429c25bf59a3146071c"
+                .to_string(),
+        },
+        Payout {
+            kind: PayoutKind::Awarded,
+            parent_id: 2,
+            child_id: 21,
+            beneficiary: account(BOB),
+            amount: None,
+            block_number: 101,
+            extrinsic_hash: "0xf1130220570a77df17370fdc6dd55af8d7054316c639e1afcdacc37ee51cb41b"
+                .to_string(),
+        },
+    ]
+}
+
+#[test]
+fn fetch_payouts_history_stops_at_the_pruned_blocks() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, PAYOUTS]).await;
+        let (tx, rx) = unbounded();
+
+        PolkadotAssetHub::fetch_payouts_history(&api, 1, tx)
+            .await
+            .unwrap();
+
+        // The state of block 99 is discarded by the node, so only blocks 100 and 101 are indexed
+        let expected = vec![
+            Output::PayoutsHistory(1, payouts_of_block_101(), 100, 101),
+            Output::PayoutsHistoryLimit(1, 100, PayoutsHistoryLimit::Pruned),
+        ];
+        assert_eq!(rx.collect::<Vec<_>>().await, expected);
+
+        let (tx, rx) = unbounded();
+        DynamicAssetHub::fetch_payouts_history(&api, 1, tx)
+            .await
+            .unwrap();
+        assert_eq!(rx.collect::<Vec<_>>().await, expected);
+    });
+}
+
+async fn subscribe_payouts_history<R: AssetHubRuntime>(from: Option<BlockNumber>) -> Vec<Output> {
+    let api = client(&[ASSET_HUB, PAYOUTS]).await;
+    let (tx, rx) = unbounded();
+    // The subscription ends after the last finalized head of the fixture
+    assert!(R::subscribe_payouts_history(&api, 1, from, tx)
+        .await
+        .is_err());
+    rx.collect().await
+}
+
+#[test]
+fn subscribe_payouts_history_catches_up_from_the_last_indexed_block() {
+    block_on(async {
+        // Blocks 100 and 101 were finalized since block 99 was indexed
+        let outputs = subscribe_payouts_history::<PolkadotAssetHub>(Some(99)).await;
+        assert_eq!(
+            outputs,
+            vec![
+                Output::PayoutsHistory(1, payouts_of_block_101(), 100, 101),
+                Output::PayoutsHistory(1, Payouts::new(), 102, 102),
+            ]
+        );
+        assert_eq!(
+            subscribe_payouts_history::<DynamicAssetHub>(Some(99)).await,
+            outputs
+        );
+
+        // Nothing indexed yet, so only the blocks finalized from now on
+        assert_eq!(
+            subscribe_payouts_history::<PolkadotAssetHub>(None).await,
+            vec![
+                Output::PayoutsHistory(1, payouts_of_block_101(), 101, 101),
+                Output::PayoutsHistory(1, Payouts::new(), 102, 102),
+            ]
+        );
+    });
+}
+
+#[test]
+fn watch_finalized_blocks_until_the_subscription_drops() {
+    block_on(async {
+        let api = client(&[ASSET_HUB, PAYOUTS]).await;
+
+        let mut block_numbers = Vec::new();
+        let reason =
+            watch_finalized_blocks(&api, |block_number| block_numbers.push(block_number)).await;
+        assert_eq!(block_numbers, vec![101, 102]);
+        assert_eq!(reason, "Finalized blocks subscription ended");
+
+        // A node refusing the subscription is reported too, so the worker reconnects
+        let api = client(&[ASSET_HUB]).await;
+        let reason = watch_finalized_blocks(&api, |_| panic!("unexpected block")).await;
+        assert_ne!(reason, "Finalized blocks subscription ended");
+    });
+}
+
+#[test]
+fn compatibility_with_the_live_runtime() {
+    block_on(async {
+        let api = client(&[ASSET_HUB]).await;
+
+        // The live runtime is the one the app was built with
+        let compatibility =
+            RuntimeCompatibility::check("Asset Hub", METADATA, &["ChildBounties", "Utility"], &api)
+                .unwrap();
+        assert_eq!(compatibility.live_spec_version, 1);
+        assert!(!compatibility.is_outdated());
+
+        // A required pallet missing from the compiled-in metadata could not be checked, but only
+        // the ones claims are built from block them
+        let compatibility = RuntimeCompatibility::check(
+            "Asset Hub",
+            METADATA,
+            &["ChildBounties", "Treasury"],
+            &api,
+        )
+        .unwrap();
+        assert_eq!(compatibility.outdated_pallets, vec!["Treasury".to_string()]);
+        assert!(!compatibility.is_claim_blocked());
+
+        // Decoded dynamically, the required pallets are looked up in the live metadata
+        let compatibility =
+            RuntimeCompatibility::dynamic("Asset Hub", &["ChildBounties", "Utility"], &api);
+        assert!(compatibility.dynamic);
+        assert!(!compatibility.is_outdated());
+        let compatibility = RuntimeCompatibility::dynamic("Asset Hub", &["Treasury"], &api);
+        assert_eq!(compatibility.outdated_pallets, vec!["Treasury".to_string()]);
+    });
+}
@@ -0,0 +1,79 @@
+//! Runs the Polkadot People runtime against the mock RPC backend, and checks the runtime decoded
+//! from the live metadata behaves the same.
+
+use claimit_common::mock::MockRpcClient;
+use claimit_common::runtimes::dynamic::DynamicPeople;
+use claimit_common::runtimes::traits::PeopleRuntime;
+use claimit_polkadot_people::polkadot_people::PolkadotPeople;
+use futures::executor::block_on;
+use std::str::FromStr;
+use subxt::utils::AccountId32;
+use subxt::{OnlineClient, PolkadotConfig};
+
+const METADATA: &[u8] =
+    include_bytes!("../artifacts/metadata/polkadot_people_metadata_small.scale");
+const PEOPLE: &str = include_str!("fixtures/people.json");
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+async fn client() -> OnlineClient<PolkadotConfig> {
+    MockRpcClient::new(METADATA)
+        .with_fixture(PEOPLE)
+        .client()
+        .await
+        .expect("mock client")
+}
+
+async fn display_names<R: PeopleRuntime>() -> Vec<Option<String>> {
+    let api = client().await;
+
+    let mut names = Vec::new();
+    for address in [ALICE, BOB, CHARLIE] {
+        let account = AccountId32::from_str(address).unwrap();
+        names.push(R::fetch_display_name(&api, &account).await.unwrap());
+    }
+    names
+}
+
+#[test]
+fn fetch_display_names() {
+    block_on(async {
+        assert_eq!(
+            display_names::<PolkadotPeople>().await,
+            vec![
+                Some("Alice".to_string()),
+                Some("Alice/bob-sub".to_string()),
+                None
+            ]
+        );
+    });
+}
+
+#[test]
+fn dynamic_runtime_resolves_the_same_display_names() {
+    block_on(async {
+        assert_eq!(
+            display_names::<DynamicPeople>().await,
+            display_names::<PolkadotPeople>().await
+        );
+    });
+}
+
+#[test]
+fn compatibility_with_the_live_runtime() {
+    block_on(async {
+        let api = client().await;
+
+        let compatibility = PolkadotPeople::compatibility(&api).unwrap();
+        assert_eq!(compatibility.chain, "People");
+        assert!(!compatibility.dynamic);
+        assert!(!compatibility.is_outdated());
+        assert!(!compatibility.is_claim_blocked());
+
+        let compatibility = DynamicPeople::compatibility(&api).unwrap();
+        assert!(compatibility.dynamic);
+        assert!(!compatibility.is_outdated());
+    });
+}
@@ -25,8 +25,8 @@ subxt metadata --url wss://sys.turboflakes.io:443/people-polkadot --pallets Iden
 #
 # AssetHub Chains
 # subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-paseo -f bytes > paseo_asset_hub/artifacts/metadata/paseo_asset_hub_metadata.scale
-subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-paseo --pallets System,Utility,Bounties,ChildBounties -f bytes > paseo_asset_hub/artifacts/metadata/paseo_asset_hub_metadata_small.scale
+subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-paseo --pallets System,Utility,Bounties,ChildBounties,Proxy,Multisig,Assets,AssetConversion -f bytes > paseo_asset_hub/artifacts/metadata/paseo_asset_hub_metadata_small.scale
 # subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-kusama -f bytes > kusama_asset_hub/artifacts/metadata/kusama_asset_hub_metadata.scale
-subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-kusama --pallets System,Utility,Bounties,ChildBounties -f bytes > kusama_asset_hub/artifacts/metadata/kusama_asset_hub_metadata_small.scale
+subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-kusama --pallets System,Utility,Bounties,ChildBounties,Proxy,Multisig,Assets,AssetConversion -f bytes > kusama_asset_hub/artifacts/metadata/kusama_asset_hub_metadata_small.scale
 # subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-polkadot -f bytes > polkadot_asset_hub/artifacts/metadata/polkadot_asset_hub_metadata.scale
-subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-polkadot --pallets System,Utility,Bounties,ChildBounties -f bytes > polkadot_asset_hub/artifacts/metadata/polkadot_asset_hub_metadata_small.scale
+subxt metadata --url wss://sys.turboflakes.io:443/asset-hub-polkadot --pallets System,Utility,Bounties,ChildBounties,Proxy,Multisig,Assets,AssetConversion -f bytes > polkadot_asset_hub/artifacts/metadata/polkadot_asset_hub_metadata_small.scale
@@ -1,19 +1,32 @@
-use claimit_common::errors::ClaimitError;
-use claimit_common::runtimes::support::SupportedRelayRuntime;
+use claimit_common::errors::{ClaimitError, WorkerError};
+use claimit_common::runtimes::{
+    dynamic::{DynamicAssetHub, DynamicPeople},
+    support::SupportedRelayRuntime,
+    traits::{AssetHubRuntime, PeopleRuntime},
+};
 use claimit_common::types::{
+    batches::BatchMode,
     child_bounties::ChildBountiesIds,
-    network::SubscriptionId,
-    worker::{Input, Output, SignerAddress},
+    claims::ClaimOrigin,
+    compatibility::RuntimeCompatibility,
+    extensions::{MetadataDef, SigningParams},
+    fees::{self, FeeAsset},
+    multisigs::Multisig,
+    network::{watch_finalized_blocks, ConnectionEvent, RpcEndpoints, SubscriptionId},
+    worker::{BlockNumber, Input, MortalityPeriod, Output, RequestId, SignerAddress},
 };
-use claimit_kusama_asset_hub::kusama_asset_hub;
-use claimit_kusama_people::kusama_people;
-use claimit_paseo_asset_hub::paseo_asset_hub;
-use claimit_paseo_people::paseo_people;
-use claimit_polkadot_asset_hub::polkadot_asset_hub;
-use claimit_polkadot_people::polkadot_people;
+use claimit_kusama_asset_hub::kusama_asset_hub::KusamaAssetHub;
+use claimit_kusama_people::kusama_people::KusamaPeople;
+use claimit_paseo_asset_hub::paseo_asset_hub::PaseoAssetHub;
+use claimit_paseo_people::paseo_people::PaseoPeople;
+use claimit_polkadot_asset_hub::polkadot_asset_hub::PolkadotAssetHub;
+use claimit_polkadot_people::polkadot_people::PolkadotPeople;
+use futures::future::{abortable, AbortHandle};
 use futures::sink::SinkExt;
 use futures::stream::StreamExt;
+use gloo::timers::future::TimeoutFuture;
 use log::{error, warn};
+use std::ops::ControlFlow;
 use subxt::{
     backend::chain_head::{ChainHeadBackend, ChainHeadBackendBuilder},
     lightclient::LightClient,
@@ -31,343 +44,854 @@ use Client as RelayClient;
 use Client as PeopleClient;
 use Client as AssetHubClient;
 
+/// Maximum number of rounds through all the endpoints before giving up
+const MAX_CONNECTION_ROUNDS: u32 = 5;
+/// Delay in seconds before the second round, doubled on every round after
+const INITIAL_BACKOFF_SECS: u32 = 2;
+/// Maximum delay in seconds between rounds
+const MAX_BACKOFF_SECS: u32 = 60;
+
 #[reactor(Worker)]
 pub async fn worker(mut scope: ReactorScope<Input, Output>) {
-    'outer: while let Some(input) = scope.next().await {
-        if let Input::Start(sub_id, runtime, use_light_client) = input {
-            // Create API clients
-            let (relay_api, people_api, asset_hub_api) =
-                create_api_clients(runtime, use_light_client)
+    while let Some(input) = scope.next().await {
+        if let Input::Start(sub_id, runtime, use_light_client, endpoints) = input {
+            // Dispatch once to the runtime specific implementation of each chain
+            let flow = match runtime {
+                SupportedRelayRuntime::Polkadot => {
+                    serve::<PolkadotAssetHub, PolkadotPeople>(
+                        &mut scope,
+                        sub_id,
+                        runtime,
+                        use_light_client,
+                        endpoints,
+                    )
+                    .await
+                }
+                SupportedRelayRuntime::Kusama => {
+                    serve::<KusamaAssetHub, KusamaPeople>(
+                        &mut scope,
+                        sub_id,
+                        runtime,
+                        use_light_client,
+                        endpoints,
+                    )
+                    .await
+                }
+                SupportedRelayRuntime::Paseo => {
+                    serve::<PaseoAssetHub, PaseoPeople>(
+                        &mut scope,
+                        sub_id,
+                        runtime,
+                        use_light_client,
+                        endpoints,
+                    )
+                    .await
+                }
+                // Note: there is no generated code for these, so both chains are decoded from the live metadata
+                SupportedRelayRuntime::Westend | SupportedRelayRuntime::Local => {
+                    serve::<DynamicAssetHub, DynamicPeople>(
+                        &mut scope,
+                        sub_id,
+                        runtime,
+                        use_light_client,
+                        endpoints,
+                    )
                     .await
-                    .expect("expect valid API clients");
+                }
+            };
+            if flow.is_break() {
+                break;
+            }
+        }
+    }
+}
 
-            // Create unbounded channel to facilitate communication between the reactor and all background tasks
-            let (tx_inner_output, mut rx_inner_output) = unbounded::<Output>();
+/// Connect to the network and serve all requests until finished, reconnecting whenever a connection drops.
+/// Breaks if the worker should stop, continues if it should wait for a new start.
+async fn serve<A: AssetHubRuntime, P: PeopleRuntime>(
+    scope: &mut ReactorScope<Input, Output>,
+    sub_id: SubscriptionId,
+    runtime: SupportedRelayRuntime,
+    use_light_client: bool,
+    endpoints: RpcEndpoints,
+) -> ControlFlow<()> {
+    // Create unbounded channel to facilitate communication between the reactor and all background tasks
+    let (tx_inner_output, mut rx_inner_output) = unbounded::<Output>();
 
-            // Subscribe to relay finalized block
-            subscribe_finalized_block(&relay_api.clone(), sub_id, tx_inner_output.clone());
+    // Keep subscriptions abort handles so they can be stopped on reconnection
+    let mut subscriptions: Vec<AbortHandle> = Vec::new();
 
-            // Inform caller the API is ready and active
-            if scope.send(Output::Active(sub_id)).await.is_err() {
-                // sender closed, the bridge is disconnected
-                break;
+    'connect: loop {
+        // Create unbounded channel to be notified when any of the connections drops. A new one is
+        // created on every connection, so drops reported by the previous ones are discarded.
+        let (tx_disconnected, mut rx_disconnected) = unbounded::<String>();
+
+        // Create API clients
+        let (relay_api, people_api, asset_hub_api) =
+            match create_api_clients(scope, sub_id, runtime, use_light_client, &endpoints).await {
+                Ok(clients) => clients,
+                Err(e) => {
+                    error!("error: {:?}", e);
+                    let _ = scope.send(Output::Err(sub_id, e.into())).await;
+                    return ControlFlow::Continue(());
+                }
+            };
+
+        // Subscribe to relay finalized block
+        subscriptions.push(subscribe_finalized_block(
+            &relay_api.clone(),
+            sub_id,
+            tx_inner_output.clone(),
+            tx_disconnected.clone(),
+        ));
+
+        // Watch people and asset hub connections
+        subscriptions.push(watch_connection(
+            &people_api.clone(),
+            tx_disconnected.clone(),
+        ));
+        subscriptions.push(watch_connection(
+            &asset_hub_api.clone(),
+            tx_disconnected.clone(),
+        ));
+
+        // Compare the live runtimes with the ones the app was built for,
+        // falling back to decode them from the live metadata when they no longer match
+        let asset_hub_compatibility = match A::compatibility(&asset_hub_api) {
+            Ok(compatibility) if compatibility.is_outdated() && !compatibility.dynamic => {
+                warn!(
+                    "{} Falling back to the live metadata.",
+                    compatibility.description()
+                );
+                DynamicAssetHub::compatibility(&asset_hub_api)
             }
+            other => other,
+        };
+        let people_compatibility = match P::compatibility(&people_api) {
+            Ok(compatibility) if compatibility.is_outdated() && !compatibility.dynamic => {
+                warn!(
+                    "{} Falling back to the live metadata.",
+                    compatibility.description()
+                );
+                DynamicPeople::compatibility(&people_api)
+            }
+            other => other,
+        };
+        let asset_hub_dynamic = asset_hub_compatibility
+            .as_ref()
+            .is_ok_and(|compatibility| compatibility.dynamic);
+        let people_dynamic = people_compatibility
+            .as_ref()
+            .is_ok_and(|compatibility| compatibility.dynamic);
 
-            loop {
-                // Wait for Finish signal to break or continue
-                futures::select! {
-                    a = scope.next() => {
-                        match a {
-                            Some(Input::Finish) =>  {
-                                warn!("Finish API worker");
-                                break 'outer;
-                            },
-                            Some(Input::FetchChildBounties) => {
-                                fetch_child_bounties(&asset_hub_api.clone(), runtime.clone(), tx_inner_output.clone());
-                            }
-                            Some(Input::FetchAccountBalance(account_id)) => {
-                                fetch_account_balance(&asset_hub_api.clone(), account_id.clone(), runtime.clone(), tx_inner_output.clone());
-                            }
-                            Some(Input::FetchAccountIdentity(account_id)) => {
-                                fetch_account_identity(&people_api.clone(), account_id.clone(), runtime.clone(), tx_inner_output.clone());
-                            }
-                            Some(Input::CreatePayloadTx(child_bounty_ids, signer_address)) => {
-                                create_payload_tx(&asset_hub_api.clone(), child_bounty_ids.clone(), signer_address.clone(), runtime.clone(), tx_inner_output.clone());
-                            }
-                            Some(Input::SignAndSubmitTx(child_bounty_ids, signer_address, signature)) => {
-                                sign_and_submit_tx(&asset_hub_api.clone(), child_bounty_ids.clone(), signer_address.clone(), signature.clone(), runtime.clone(), tx_inner_output.clone());
-                            }
-                            _ => ()
+        // A runtime that could not be checked at all blocks claims as much as an outdated one
+        let mut claims_blocked: Option<String> = None;
+        for (chain, api, compatibility) in [
+            ("Asset Hub", &asset_hub_api, asset_hub_compatibility),
+            ("People", &people_api, people_compatibility),
+        ] {
+            let compatibility = compatibility.unwrap_or_else(|e| {
+                error!("error: {:?}", e);
+                RuntimeCompatibility::unverified(chain, api, e.to_string())
+            });
+            if compatibility.is_outdated() {
+                warn!("{}", compatibility.description());
+            }
+            if compatibility.is_claim_blocked() {
+                claims_blocked = Some(compatibility.description());
+            }
+            if scope
+                .send(Output::Compatibility(sub_id, compatibility))
+                .await
+                .is_err()
+            {
+                return ControlFlow::Break(());
+            }
+        }
+
+        // Inform caller the API is ready and active
+        if scope.send(Output::Active(sub_id)).await.is_err() {
+            // sender closed, the bridge is disconnected
+            return ControlFlow::Break(());
+        }
+
+        loop {
+            // Wait for Finish signal to break or continue
+            futures::select! {
+                a = scope.next() => {
+                    match a {
+                        Some(Input::Finish) =>  {
+                            warn!("Finish API worker");
+                            subscriptions.iter().for_each(|handle| handle.abort());
+                            return ControlFlow::Break(());
+                        },
+                        Some(Input::SplitClaim(request_id, _, _)) | Some(Input::FetchMultisigStatus(request_id, _, _, _)) | Some(Input::CreatePayloadTx(request_id, _, _, _, _, _, _)) | Some(Input::SignAndSubmitTx(request_id, _, _, _, _, _, _, _)) if claims_blocked.is_some() => {
+                            let description = claims_blocked.clone().unwrap_or_default();
+                            let _ = tx_inner_output.send_now(Output::Failed(request_id, WorkerError::MetadataError(description)));
                         }
-                    },
-                    b = rx_inner_output.next() => {
-                        match b {
-                            Some(data) => {
-                                if scope
-                                    .send(data)
-                                    .await
-                                    .is_err()
-                                {
-                                    break 'outer;
-                                }
+                        Some(input) => {
+                            let (asset_hub, people, tx) = (&asset_hub_api, &people_api, tx_inner_output.clone());
+                            match (asset_hub_dynamic, people_dynamic) {
+                                (false, false) => dispatch::<A, P>(input, runtime, asset_hub, people, tx, &mut subscriptions),
+                                (false, true) => dispatch::<A, DynamicPeople>(input, runtime, asset_hub, people, tx, &mut subscriptions),
+                                (true, false) => dispatch::<DynamicAssetHub, P>(input, runtime, asset_hub, people, tx, &mut subscriptions),
+                                (true, true) => dispatch::<DynamicAssetHub, DynamicPeople>(input, runtime, asset_hub, people, tx, &mut subscriptions),
                             }
-                            _ => ()
                         }
-                    },
-                }
+                        None => ()
+                    }
+                },
+                b = rx_inner_output.next() => {
+                    if let Some(data) = b {
+                        if scope.send(data).await.is_err() {
+                            return ControlFlow::Break(());
+                        }
+                    }
+                },
+                c = rx_disconnected.next() => {
+                    let reason = c.unwrap_or_default();
+                    warn!("Connection dropped: {reason}");
+                    // Stop all subscriptions, they will be requested again once reconnected
+                    subscriptions.drain(..).for_each(|handle| handle.abort());
+                    if scope
+                        .send(Output::Connection(sub_id, ConnectionEvent::Disconnected(reason)))
+                        .await
+                        .is_err()
+                    {
+                        return ControlFlow::Break(());
+                    }
+                    continue 'connect;
+                },
             }
         }
     }
 }
 
+/// Run a request against the runtime implementation of each chain
+fn dispatch<A: AssetHubRuntime, P: PeopleRuntime>(
+    input: Input,
+    runtime: SupportedRelayRuntime,
+    asset_hub_api: &AssetHubClient,
+    people_api: &PeopleClient,
+    tx: UnboundedSender<Output>,
+    subscriptions: &mut Vec<AbortHandle>,
+) {
+    match input {
+        Input::FetchChildBounties(request_id) => {
+            fetch_child_bounties::<A>(asset_hub_api, request_id, tx);
+        }
+        Input::SubscribeChildBounties(request_id) => {
+            subscriptions.push(subscribe_child_bounties::<A>(asset_hub_api, request_id, tx));
+        }
+        Input::FetchParentBounties(request_id) => {
+            fetch_parent_bounties::<A>(asset_hub_api, request_id, tx);
+        }
+        Input::FetchAccountBalance(request_id, account_id) => {
+            fetch_account_balance::<A>(asset_hub_api, request_id, account_id, tx);
+        }
+        Input::FetchAccountIdentity(request_id, account_id) => {
+            fetch_account_identity::<P>(people_api, request_id, account_id, tx);
+        }
+        Input::FetchProxies(request_id, delegate, reals) => {
+            fetch_proxies::<A>(asset_hub_api, request_id, delegate, reals, tx);
+        }
+        Input::FetchFeeAssetBalances(request_id, account) => {
+            fetch_fee_asset_balances(asset_hub_api, runtime, request_id, account, tx);
+        }
+        Input::FetchPayoutsHistory(request_id) => {
+            fetch_payouts_history::<A>(asset_hub_api, request_id, tx);
+        }
+        Input::SubscribePayoutsHistory(request_id, from) => {
+            subscriptions.push(subscribe_payouts_history::<A>(
+                asset_hub_api,
+                request_id,
+                from,
+                tx,
+            ));
+        }
+        Input::SplitClaim(request_id, child_bounty_ids, mode) => {
+            split_claim::<A>(asset_hub_api, request_id, child_bounty_ids, mode, tx);
+        }
+        Input::FetchMultisigStatus(request_id, child_bounty_ids, mode, multisig) => {
+            fetch_multisig_status::<A>(
+                asset_hub_api,
+                request_id,
+                child_bounty_ids,
+                mode,
+                multisig,
+                tx,
+            );
+        }
+        Input::CreatePayloadTx(
+            request_id,
+            child_bounty_ids,
+            mode,
+            origin,
+            signer_address,
+            mortality_period,
+            fee_asset,
+        ) => {
+            create_payload_tx::<A>(
+                asset_hub_api,
+                runtime,
+                request_id,
+                child_bounty_ids,
+                mode,
+                origin,
+                signer_address,
+                mortality_period,
+                fee_asset,
+                tx,
+            );
+        }
+        Input::SignAndSubmitTx(
+            request_id,
+            child_bounty_ids,
+            mode,
+            origin,
+            signer_address,
+            signature,
+            params,
+            fee_asset,
+        ) => {
+            sign_and_submit_tx::<A>(
+                asset_hub_api,
+                request_id,
+                child_bounty_ids,
+                mode,
+                origin,
+                signer_address,
+                signature,
+                params,
+                fee_asset,
+                tx,
+            );
+        }
+        Input::FetchMetadataDef(request_id) => {
+            fetch_metadata_def(asset_hub_api, runtime, request_id, tx);
+        }
+        _ => (),
+    }
+}
+
+/// Report a connection event to the caller
+async fn report(
+    scope: &mut ReactorScope<Input, Output>,
+    sub_id: SubscriptionId,
+    event: ConnectionEvent,
+) -> Result<(), ClaimitError> {
+    scope
+        .send(Output::Connection(sub_id, event))
+        .await
+        .map_err(|_| ClaimitError::Other("Bridge disconnected".into()))
+}
+
 /// Create API clients
 pub async fn create_api_clients(
+    scope: &mut ReactorScope<Input, Output>,
+    sub_id: SubscriptionId,
     runtime: SupportedRelayRuntime,
     use_light_client: bool,
+    endpoints: &RpcEndpoints,
 ) -> Result<(RelayClient, PeopleClient, AssetHubClient), ClaimitError> {
     if use_light_client {
+        let (Some(chain_specs), Some(chain_specs_people), Some(chain_specs_asset_hub)) = (
+            runtime.chain_specs(),
+            runtime.chain_specs_people(),
+            runtime.chain_specs_asset_hub(),
+        ) else {
+            return Err(ClaimitError::Other(format!(
+                "Light client is not supported on {runtime}"
+            )));
+        };
+
+        report(
+            scope,
+            sub_id,
+            ConnectionEvent::Connecting("light client".into()),
+        )
+        .await?;
+
         // Initiate light client (smoldot)
-        let (lc, rpc) = LightClient::relay_chain(runtime.chain_specs())
-            .expect("expect valid smoldot connection");
+        let (lc, rpc) = LightClient::relay_chain(chain_specs)?;
 
         let backend: ChainHeadBackend<PolkadotConfig> =
             ChainHeadBackendBuilder::default().build_with_background_driver(rpc.clone());
 
         // Create client from unstable backend (ie using new RPCs).
-        let relay_api = Client::from_backend(backend.into())
-            .await
-            .expect("expect valid RPC connection");
+        let relay_api = Client::from_backend(backend.into()).await?;
 
-        let people_rpc = lc
-            .parachain(runtime.chain_specs_people())
-            .expect("expect valid smoldot connection");
+        let people_rpc = lc.parachain(chain_specs_people)?;
 
-        let people_api = Client::from_rpc_client(people_rpc)
-            .await
-            .expect("expect valid RPC connection");
+        let people_api = Client::from_rpc_client(people_rpc).await?;
 
-        let asset_hub_rpc = lc
-            .parachain(runtime.chain_specs_asset_hub())
-            .expect("expect valid smoldot connection");
+        let asset_hub_rpc = lc.parachain(chain_specs_asset_hub)?;
 
-        let asset_hub_api = Client::from_rpc_client(asset_hub_rpc)
-            .await
-            .expect("expect valid RPC connection");
+        let asset_hub_api = Client::from_rpc_client(asset_hub_rpc).await?;
+
+        report(
+            scope,
+            sub_id,
+            ConnectionEvent::Connected("light client".into()),
+        )
+        .await?;
 
         Ok((relay_api, people_api, asset_hub_api))
     } else {
-        // Initiate RPC client from default RPCs provider
-        let relay_api = Client::from_url(runtime.default_rpc_url())
-            .await
-            .expect("expect valid RPC connection");
+        // Initiate RPC clients from the first endpoint available of each chain
+        let relay_api =
+            connect_with_failover(scope, sub_id, &endpoints.relay_urls(runtime)).await?;
 
-        let people_api = Client::from_url(runtime.default_people_rpc_url())
-            .await
-            .expect("expect valid RPC connection");
+        let people_api =
+            connect_with_failover(scope, sub_id, &endpoints.people_urls(runtime)).await?;
 
-        let asset_hub_api = Client::from_url(runtime.default_asset_hub_rpc_url())
-            .await
-            .expect("expect valid RPC connection");
+        let asset_hub_api =
+            connect_with_failover(scope, sub_id, &endpoints.asset_hub_urls(runtime)).await?;
 
         Ok((relay_api, people_api, asset_hub_api))
     }
 }
 
+/// Try each endpoint in turn, backing off between rounds until the maximum number of rounds.
+async fn connect_with_failover(
+    scope: &mut ReactorScope<Input, Output>,
+    sub_id: SubscriptionId,
+    urls: &[String],
+) -> Result<Client, ClaimitError> {
+    let mut backoff = INITIAL_BACKOFF_SECS;
+
+    for round in 1..=MAX_CONNECTION_ROUNDS {
+        for url in urls {
+            report(scope, sub_id, ConnectionEvent::Connecting(url.clone())).await?;
+
+            match Client::from_url(url).await {
+                Ok(client) => {
+                    report(scope, sub_id, ConnectionEvent::Connected(url.clone())).await?;
+                    return Ok(client);
+                }
+                Err(e) => {
+                    warn!("Connection to {url} failed: {e}");
+                    report(
+                        scope,
+                        sub_id,
+                        ConnectionEvent::Failed(url.clone(), e.to_string()),
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        if round < MAX_CONNECTION_ROUNDS {
+            report(scope, sub_id, ConnectionEvent::Retrying(backoff)).await?;
+            TimeoutFuture::new(backoff * 1_000).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF_SECS);
+        }
+    }
+
+    Err(ClaimitError::Other(format!(
+        "Unable to connect to any of the endpoints: {}",
+        urls.join(", ")
+    )))
+}
+
 /// Background task that subscribes finalized block and sends response over channel.
 pub fn subscribe_finalized_block(
     api: &OnlineClient<PolkadotConfig>,
     sub_id: SubscriptionId,
     tx: UnboundedSender<Output>,
+    tx_disconnected: UnboundedSender<String>,
+) -> AbortHandle {
+    let api = api.clone();
+
+    let (task, handle) = abortable(async move {
+        let reason = watch_finalized_blocks(&api, |block_number| {
+            let _ = tx.send_now(Output::BlockNumber(sub_id, block_number));
+        })
+        .await;
+        let _ = tx_disconnected.send_now(reason);
+    });
+    spawn_local(async move {
+        let _ = task.await;
+    });
+    handle
+}
+
+/// Background task that watches the finalized blocks subscription and notifies if it drops.
+pub fn watch_connection(
+    api: &OnlineClient<PolkadotConfig>,
+    tx_disconnected: UnboundedSender<String>,
+) -> AbortHandle {
+    let api = api.clone();
+
+    let (task, handle) = abortable(async move {
+        let reason = watch_finalized_blocks(&api, |_| {}).await;
+        let _ = tx_disconnected.send_now(reason);
+    });
+    spawn_local(async move {
+        let _ = task.await;
+    });
+    handle
+}
+
+/// Background task that fetches child bounties and sends response over channel.
+pub fn fetch_child_bounties<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
 ) {
     let api = api.clone();
+    let tx = tx.clone();
+    spawn_local(async move {
+        let response = A::fetch_child_bounties(&api, request_id, tx.clone()).await;
+        match response {
+            Ok(_) => {
+                let _ = tx.send_now(Output::Completed(request_id));
+            }
+            Err(e) => {
+                error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
+            }
+        }
+    });
+}
 
+/// Background task that watches child bounties changes on every finalized block and sends them over channel.
+pub fn subscribe_child_bounties<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
+) -> AbortHandle {
+    let api = api.clone();
+    let tx = tx.clone();
+    let (task, handle) = abortable(async move {
+        let response = A::subscribe_child_bounties(&api, request_id, tx.clone()).await;
+        if let Err(e) = response {
+            error!("error: {:?}", e);
+            let _ = tx.send_now(Output::Failed(request_id, e.into()));
+        }
+    });
     spawn_local(async move {
-        match api.blocks().subscribe_finalized().await {
-            Ok(mut blocks_sub) => {
-                while let Some(result) = blocks_sub.next().await {
-                    match result {
-                        Ok(block) => {
-                            let _ = tx.send_now(Output::BlockNumber(sub_id, block.number().into()));
-                        }
-                        Err(e) => {
-                            error!("{}", e);
-                        }
-                    }
-                }
+        let _ = task.await;
+    });
+    handle
+}
+
+/// Background task that fetches parent bounties and sends response over channel.
+pub fn fetch_parent_bounties<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
+) {
+    let api = api.clone();
+    let tx = tx.clone();
+    spawn_local(async move {
+        let response = A::fetch_parent_bounties(&api).await;
+        match response {
+            Ok(parent_bounties) => {
+                let _ = tx.send_now(Output::ParentBounties(request_id, parent_bounties));
             }
             Err(e) => {
                 error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
         }
     });
 }
 
-/// Background task that fetches child bounties and sends response over channel.
-pub fn fetch_child_bounties(
+/// Background task that builds the Asset Hub metadata definition and sends response over channel.
+pub fn fetch_metadata_def(
     api: &OnlineClient<PolkadotConfig>,
     runtime: SupportedRelayRuntime,
+    request_id: RequestId,
     tx: UnboundedSender<Output>,
 ) {
     let api = api.clone();
     let tx = tx.clone();
     spawn_local(async move {
-        let response = match runtime {
-            SupportedRelayRuntime::Polkadot => {
-                polkadot_asset_hub::fetch_child_bounties(&api, tx).await
+        let response = MetadataDef::fetch(&api, runtime).await;
+        match response {
+            Ok(metadata) => {
+                let _ = tx.send_now(Output::MetadataDef(request_id, metadata));
             }
-            SupportedRelayRuntime::Kusama => kusama_asset_hub::fetch_child_bounties(&api, tx).await,
-            SupportedRelayRuntime::Paseo => paseo_asset_hub::fetch_child_bounties(&api, tx).await,
-        };
+            Err(e) => {
+                error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(
+                    request_id,
+                    WorkerError::Other(e.to_string()),
+                ));
+            }
+        }
+    });
+}
+
+/// Background task that indexes child bounties payouts from the last finalized blocks and sends them over channel.
+pub fn fetch_payouts_history<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    tx: UnboundedSender<Output>,
+) {
+    let api = api.clone();
+    let tx = tx.clone();
+    spawn_local(async move {
+        let response = A::fetch_payouts_history(&api, request_id, tx.clone()).await;
         match response {
+            Ok(_) => {
+                let _ = tx.send_now(Output::Completed(request_id));
+            }
             Err(e) => {
                 error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
-            _ => (),
         }
     });
 }
 
+/// Background task that indexes child bounties payouts from finalized blocks and sends them over channel.
+pub fn subscribe_payouts_history<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    from: Option<BlockNumber>,
+    tx: UnboundedSender<Output>,
+) -> AbortHandle {
+    let api = api.clone();
+    let tx = tx.clone();
+    let (task, handle) = abortable(async move {
+        let response = A::subscribe_payouts_history(&api, request_id, from, tx.clone()).await;
+        if let Err(e) = response {
+            error!("error: {:?}", e);
+            let _ = tx.send_now(Output::Failed(request_id, e.into()));
+        }
+    });
+    spawn_local(async move {
+        let _ = task.await;
+    });
+    handle
+}
+
 /// Background task that fetches account balance and sends response over channel.
-pub fn fetch_account_balance(
+pub fn fetch_account_balance<A: AssetHubRuntime>(
     api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
     account_id: AccountId32,
-    runtime: SupportedRelayRuntime,
     tx: UnboundedSender<Output>,
 ) {
     let api = api.clone();
     let tx = tx.clone();
     spawn_local(async move {
-        let response = match runtime {
-            SupportedRelayRuntime::Polkadot => {
-                polkadot_asset_hub::fetch_account_balance(&api, account_id.clone()).await
-            }
-            SupportedRelayRuntime::Kusama => {
-                kusama_asset_hub::fetch_account_balance(&api, account_id.clone()).await
-            }
-            SupportedRelayRuntime::Paseo => {
-                paseo_asset_hub::fetch_account_balance(&api, account_id.clone()).await
-            }
-        };
+        let response = A::fetch_account_balance(&api, account_id.clone()).await;
         match response {
             Ok(balance) => {
-                let _ = tx.send_now(Output::AccountBalance(account_id, balance));
+                let _ = tx.send_now(Output::AccountBalance(request_id, account_id, balance));
             }
             Err(e) => {
                 error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
         }
     });
 }
 
 /// Background task that fetches account identity and sends response over channel.
-pub fn fetch_account_identity(
+pub fn fetch_account_identity<P: PeopleRuntime>(
     api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
     account_id: AccountId32,
-    runtime: SupportedRelayRuntime,
     tx: UnboundedSender<Output>,
 ) {
     let api = api.clone();
     let tx = tx.clone();
     spawn_local(async move {
-        let response = match runtime {
-            SupportedRelayRuntime::Polkadot => {
-                polkadot_people::fetch_display_name(&api, &account_id, None).await
+        let response = P::fetch_display_name(&api, &account_id).await;
+        match response {
+            Ok(identity) => {
+                let _ = tx.send_now(Output::AccountIdentity(request_id, account_id, identity));
             }
-            SupportedRelayRuntime::Kusama => {
-                kusama_people::fetch_display_name(&api, &account_id, None).await
+            Err(e) => {
+                error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
-            SupportedRelayRuntime::Paseo => {
-                paseo_people::fetch_display_name(&api, &account_id, None).await
+        }
+    });
+}
+
+/// Background task that fetches the accounts the delegate is a proxy of and sends response over channel.
+pub fn fetch_proxies<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    delegate: AccountId32,
+    reals: Vec<AccountId32>,
+    tx: UnboundedSender<Output>,
+) {
+    let api = api.clone();
+    let tx = tx.clone();
+    spawn_local(async move {
+        let response = A::fetch_proxies(&api, &delegate, reals).await;
+        match response {
+            Ok(proxies) => {
+                let _ = tx.send_now(Output::Proxies(request_id, delegate, proxies));
             }
-        };
+            Err(e) => {
+                error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
+            }
+        }
+    });
+}
+
+/// Background task that fetches the balances of the account in the fee assets and sends response over channel.
+pub fn fetch_fee_asset_balances(
+    api: &OnlineClient<PolkadotConfig>,
+    runtime: SupportedRelayRuntime,
+    request_id: RequestId,
+    account: AccountId32,
+    tx: UnboundedSender<Output>,
+) {
+    let api = api.clone();
+    let tx = tx.clone();
+    spawn_local(async move {
+        let response = fees::fetch_fee_asset_balances(&api, runtime, &account).await;
         match response {
-            Ok(identity) => {
-                let _ = tx.send_now(Output::AccountIdentity(account_id, identity));
+            Ok(balances) => {
+                let _ = tx.send_now(Output::FeeAssetBalances(request_id, account, balances));
             }
             Err(e) => {
                 error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
         }
     });
 }
 
-/// Background task that creates a payload and sends response over channel.
-pub fn create_payload_tx(
+/// Background task that splits a claim in batches and sends response over channel.
+pub fn split_claim<A: AssetHubRuntime>(
     api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
     child_bounties_ids: ChildBountiesIds,
-    signer_address: SignerAddress,
-    runtime: SupportedRelayRuntime,
+    mode: BatchMode,
     tx: UnboundedSender<Output>,
 ) {
     let api = api.clone();
     let tx = tx.clone();
 
     spawn_local(async move {
-        let response = match runtime {
-            SupportedRelayRuntime::Polkadot => {
-                polkadot_asset_hub::create_payload_tx(
-                    &api,
-                    child_bounties_ids.clone(),
-                    signer_address.clone(),
-                )
-                .await
+        let response = A::split_claim(&api, child_bounties_ids, mode).await;
+        match response {
+            Ok(batches) => {
+                let _ = tx.send_now(Output::ClaimBatches(request_id, batches));
             }
-            SupportedRelayRuntime::Kusama => {
-                kusama_asset_hub::create_payload_tx(
-                    &api,
-                    child_bounties_ids.clone(),
-                    signer_address.clone(),
-                )
-                .await
+            Err(e) => {
+                error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
-            SupportedRelayRuntime::Paseo => {
-                paseo_asset_hub::create_payload_tx(
-                    &api,
-                    child_bounties_ids.clone(),
-                    signer_address.clone(),
-                )
-                .await
+        }
+    });
+}
+
+/// Background task that fetches the approvals of a multisig claim and sends response over channel.
+pub fn fetch_multisig_status<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
+    child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+    multisig: Multisig,
+    tx: UnboundedSender<Output>,
+) {
+    let api = api.clone();
+    let tx = tx.clone();
+
+    spawn_local(async move {
+        let response = A::fetch_multisig_status(&api, child_bounties_ids, mode, &multisig).await;
+        match response {
+            Ok(status) => {
+                let _ = tx.send_now(Output::MultisigStatus(request_id, status));
             }
-        };
+            Err(e) => {
+                error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
+            }
+        }
+    });
+}
+
+/// Background task that creates a payload and sends response over channel.
+#[allow(clippy::too_many_arguments)]
+pub fn create_payload_tx<A: AssetHubRuntime>(
+    api: &OnlineClient<PolkadotConfig>,
+    runtime: SupportedRelayRuntime,
+    request_id: RequestId,
+    child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+    origin: ClaimOrigin,
+    signer_address: SignerAddress,
+    mortality_period: MortalityPeriod,
+    fee_asset: Option<FeeAsset>,
+    tx: UnboundedSender<Output>,
+) {
+    let api = api.clone();
+    let tx = tx.clone();
+
+    spawn_local(async move {
+        let response = A::create_payload_tx(
+            &api,
+            runtime,
+            child_bounties_ids,
+            mode,
+            origin,
+            signer_address,
+            mortality_period,
+            fee_asset,
+        )
+        .await;
         match response {
-            Ok(payload) => {
-                let _ = tx.send_now(Output::TxPayload(payload));
+            Ok((payload, params, fee)) => {
+                let _ = tx.send_now(Output::TxPayload(request_id, payload, params, fee));
             }
             Err(e) => {
                 error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
         }
     });
 }
 
 /// Background task that signs and submits transaction with the signature provided and sends response over channel.
-pub fn sign_and_submit_tx(
+#[allow(clippy::too_many_arguments)]
+pub fn sign_and_submit_tx<A: AssetHubRuntime>(
     api: &OnlineClient<PolkadotConfig>,
+    request_id: RequestId,
     child_bounties_ids: ChildBountiesIds,
+    mode: BatchMode,
+    origin: ClaimOrigin,
     signer_address: SignerAddress,
     signature: Vec<u8>,
-    runtime: SupportedRelayRuntime,
+    params: SigningParams,
+    fee_asset: Option<FeeAsset>,
     tx: UnboundedSender<Output>,
 ) {
     let api = api.clone();
     let tx = tx.clone();
 
     spawn_local(async move {
-        let response = match runtime {
-            SupportedRelayRuntime::Polkadot => {
-                polkadot_asset_hub::sign_and_submit_tx(
-                    &api,
-                    child_bounties_ids.clone(),
-                    signer_address.clone(),
-                    signature.clone(),
-                )
-                .await
-            }
-            SupportedRelayRuntime::Kusama => {
-                kusama_asset_hub::sign_and_submit_tx(
-                    &api,
-                    child_bounties_ids.clone(),
-                    signer_address.clone(),
-                    signature.clone(),
-                )
-                .await
-            }
-            SupportedRelayRuntime::Paseo => {
-                paseo_asset_hub::sign_and_submit_tx(
-                    &api,
-                    child_bounties_ids.clone(),
-                    signer_address.clone(),
-                    signature.clone(),
-                )
-                .await
-            }
-        };
+        let response = A::sign_and_submit_tx(
+            &api,
+            request_id,
+            child_bounties_ids,
+            mode,
+            origin,
+            signer_address,
+            signature,
+            params,
+            fee_asset,
+            tx.clone(),
+        )
+        .await;
         match response {
             Ok(result) => {
-                let _ = tx.send_now(Output::TxCompleted(result));
+                let _ = tx.send_now(Output::TxCompleted(request_id, result));
             }
             Err(e) => {
                 error!("error: {:?}", e);
+                let _ = tx.send_now(Output::Failed(request_id, e.into()));
             }
         }
     });